@@ -20,7 +20,28 @@ leibniz = {
         -- "InvalidBlob", -- an invalid blob literal was found (either bad hex data or incorrect syntax)
         -- "Syntax", -- a structure with incorrect syntax was found
         -- "Semicolon", -- a semicolon is missing
+        -- "TooManyJoins", -- a join clause contains more joins than max_joins
+        -- "ReservedTableName", -- a table name uses the reserved sqlite_ prefix
+        -- "UnknownPragma", -- a pragma name sqlite does not know, such as a typo
     },
+    -- opt-in diagnostics, these are disabled unless enabled here or via -E
+    enabled_rules = {
+        -- "ImplicitInsertColumns", -- INSERT is missing an explicit column list
+        -- "LineLength", -- a line is wider than max_line_length
+    },
+    -- errors with a severity below are not reported, one of "hint", "info",
+    -- "warning" or "error", the same as --min-severity
+    -- min_severity = "hint",
+    -- amount of joins a single join clause may contain before TooManyJoins is
+    -- emitted, sqlite itself refuses to join more than 64 tables
+    max_joins = 16,
+    -- maximum width of a line before LineLength is emitted, tabs advance to the
+    -- next multiple of tab_width
+    max_line_length = 120,
+    tab_width = 4,
+    -- sqlite version the sql is written for, features introduced in later
+    -- versions are reported as SqliteUnsupported
+    -- sqlite_version = "3.38.0",
     -- sqleibniz allows for writing custom rules with lua
     hooks = {
         {
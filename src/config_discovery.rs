@@ -0,0 +1,39 @@
+use std::path::{Path, PathBuf};
+
+/// file names of configurations, in the order they are looked for in each directory
+pub const CONFIG_NAMES: &[&str] = &["leibniz.lua", "leibniz.toml"];
+/// name of the file listing files excluded from analysis, see [crate::ignore]
+pub const IGNORE_NAME: &str = ".sqleibnizignore";
+/// a directory containing a file or directory with this name is the last directory searched for
+/// a configuration, placed by the user to keep configurations of enclosing directories from
+/// applying to a project
+pub const ROOT_MARKER: &str = ".sqleibniz-root";
+
+/// walks up from the directory start to the filesystem root and returns the first configuration
+/// found, see [CONFIG_NAMES]. The walk stops after the first directory containing
+/// [ROOT_MARKER].
+pub fn find_config(start: &Path) -> Option<PathBuf> {
+    find(start, CONFIG_NAMES)
+}
+
+/// walks up from the directory start like [find_config] and returns the first [IGNORE_NAME]
+/// found
+pub fn find_ignore_file(start: &Path) -> Option<PathBuf> {
+    find(start, &[IGNORE_NAME])
+}
+
+fn find(start: &Path, names: &[&str]) -> Option<PathBuf> {
+    for dir in start.ancestors() {
+        if let Some(config) = names
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+        {
+            return Some(config);
+        }
+        if dir.join(ROOT_MARKER).exists() {
+            break;
+        }
+    }
+    None
+}
@@ -2,9 +2,15 @@ use std::{fs, io::BufRead, path::PathBuf};
 
 use crate::{
     highlight::{builder, highlight},
-    types::{Token, rules::Rule},
+    types::{
+        Token,
+        rules::{Rule, Severity},
+    },
 };
 
+/// amount of lines [Error::print] shows before and after the line of an error by default
+pub const DEFAULT_CONTEXT_LINES: usize = 2;
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct ImprovedLine {
     pub snippet: &'static str,
@@ -16,6 +22,8 @@ pub struct Error {
     pub file: String,
     pub line: usize,
     pub rule: Rule,
+    /// severity of the error, see [Rule::severity]
+    pub severity: Severity,
     pub note: String,
     pub msg: String,
     pub start: usize,
@@ -24,6 +32,10 @@ pub struct Error {
     pub doc_url: Option<&'static str>,
 }
 
+/// callback receiving each error as soon as the lexer or parser finds it, see
+/// [crate::lexer::Lexer::on_error] and [crate::parser::Parser::on_error]
+pub type ErrorSink<'a> = Box<dyn FnMut(&Error) + 'a>;
+
 #[derive(Debug)]
 pub enum Color {
     Reset,
@@ -80,10 +92,34 @@ pub fn print_str_colored(b: &mut builder::Builder, s: &str, c: Color) {
 }
 
 impl Error {
-    pub fn print(&mut self, b: &mut builder::Builder, content: &[u8], tokens: &[Token]) {
-        print_str_colored(b, "error", Color::Red);
+    /// serializes self for --format json, line is one based like in the human readable output,
+    /// start and end are zero based character columns into the line, not byte offsets
+    pub fn as_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "file": self.file,
+            "line": self.line + 1,
+            "start": self.start,
+            "end": self.end,
+            "rule": self.rule.name(),
+            "severity": self.severity.name(),
+            "message": self.msg,
+            "note": self.note,
+            "doc_url": self.doc_url,
+        })
+    }
+
+    /// writes self highlighted to b, surrounded by context lines of content before and after the
+    /// line of the error, see [DEFAULT_CONTEXT_LINES]
+    pub fn print(
+        &mut self,
+        b: &mut builder::Builder,
+        content: &[u8],
+        tokens: &[Token],
+        context: usize,
+    ) {
+        print_str_colored(b, self.severity.name(), self.severity.color());
         b.write_char('[');
-        print_str_colored(b, self.rule.name(), Color::Red);
+        print_str_colored(b, self.rule.name(), self.severity.color());
         b.write_str("]: ");
         b.write_str(&self.msg);
         b.write_char('\n');
@@ -115,8 +151,8 @@ impl Error {
             self.end = 0;
         }
 
-        let start_line = self.line.saturating_sub(2);
-        let end_line = usize::min(self.line + 2, lines.len() - 1);
+        let start_line = self.line.saturating_sub(context);
+        let end_line = usize::min(self.line.saturating_add(context), lines.len() - 1);
 
         for (i, line) in lines.iter().enumerate().take(end_line + 1).skip(start_line) {
             print_str_colored(b, &format!(" {:02} | ", i + 1), Color::Blue);
@@ -125,21 +161,27 @@ impl Error {
             b.write_char('\n');
 
             if i == self.line {
-                let repeat = if self.end > self.start {
-                    self.end - self.start
+                // zero width errors, such as a missing token at the end of a line or the input,
+                // point at a position instead of underlining a span, clamped to the line end to
+                // not pad into empty space
+                let marker = if self.end > self.start {
+                    format!(
+                        "{}{}",
+                        " ".repeat(self.start),
+                        "~".repeat(self.end - self.start)
+                    )
                 } else {
-                    1
+                    format!(
+                        "{}^",
+                        " ".repeat(usize::min(self.start, line.chars().count()))
+                    )
                 };
 
                 print_str_colored(b, "    | ", Color::Blue);
                 print_str_colored(
                     b,
-                    &format!(
-                        "{}{} error occurs here.\n",
-                        " ".repeat(self.start),
-                        "~".repeat(repeat)
-                    ),
-                    Color::Red,
+                    &format!("{marker} {} occurs here.\n", self.severity.name()),
+                    self.severity.color(),
                 );
             }
         }
@@ -165,9 +207,9 @@ impl Error {
         }
         b.write_char('\n');
 
-        if self.doc_url.is_some() {
+        if let Some(doc_url) = self.doc_url {
             print_str_colored(b, "    ~ docs: ", Color::Blue);
-            b.write_str(self.doc_url.unwrap());
+            b.write_str(doc_url);
             b.write_char('\n');
         }
 
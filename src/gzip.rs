@@ -0,0 +1,89 @@
+use std::io::Read;
+
+use flate2::read::MultiGzDecoder;
+
+/// first two bytes of every gzip member, see: https://www.rfc-editor.org/rfc/rfc1952#section-2.3.1
+pub const MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// upper bound for the size of a decompressed file, guards against decompression bombs
+pub const MAX_DECOMPRESSED_SIZE: u64 = 1 << 30;
+
+/// is_gzip reports whether a file should be decompressed before analysis, either because its
+/// content starts with the gzip magic bytes or because its name ends with .gz
+pub fn is_gzip(name: &str, content: &[u8]) -> bool {
+    content.starts_with(&MAGIC) || name.ends_with(".gz")
+}
+
+/// decompress inflates all members of a gzip file and verifies their checksums, errors if the
+/// result exceeds [MAX_DECOMPRESSED_SIZE], see: https://www.rfc-editor.org/rfc/rfc1952
+pub fn decompress(content: &[u8]) -> Result<Vec<u8>, String> {
+    decompress_at_most(content, MAX_DECOMPRESSED_SIZE)
+}
+
+fn decompress_at_most(content: &[u8], limit: u64) -> Result<Vec<u8>, String> {
+    let mut out = vec![];
+    // reading one byte past the limit tells a file of exactly limit bytes from a larger one
+    MultiGzDecoder::new(content)
+        .take(limit + 1)
+        .read_to_end(&mut out)
+        .map_err(|err| err.to_string())?;
+    if out.len() as u64 > limit {
+        return Err(format!("decompressed size exceeds {limit} bytes"));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decompress, decompress_at_most, is_gzip};
+
+    /// gzip -9 of "VACUUM;\n", a single block with fixed huffman codes
+    const FIXED: [u8; 28] = [
+        0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0x0b, 0x73, 0x74, 0x0e, 0x0d,
+        0xf5, 0xb5, 0xe6, 0x02, 0x00, 0xb9, 0x84, 0xde, 0x35, 0x08, 0x00, 0x00, 0x00,
+    ];
+
+    /// gzip -0 of "VACUUM;\n", a single stored block
+    const STORED: [u8; 31] = [
+        0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0x03, 0x01, 0x08, 0x00, 0xf7, 0xff,
+        0x56, 0x41, 0x43, 0x55, 0x55, 0x4d, 0x3b, 0x0a, 0xb9, 0x84, 0xde, 0x35, 0x08, 0x00, 0x00,
+        0x00,
+    ];
+
+    #[test]
+    fn detection() {
+        assert!(is_gzip("dump.sql", &FIXED));
+        assert!(is_gzip("dump.sql.gz", b"VACUUM;"));
+        assert!(!is_gzip("dump.sql", b"VACUUM;"));
+    }
+
+    #[test]
+    fn single_member() {
+        assert_eq!(decompress(&FIXED).unwrap(), b"VACUUM;\n");
+        assert_eq!(decompress(&STORED).unwrap(), b"VACUUM;\n");
+    }
+
+    #[test]
+    fn concatenated_members() {
+        let content = [FIXED.as_slice(), STORED.as_slice()].concat();
+        assert_eq!(decompress(&content).unwrap(), b"VACUUM;\nVACUUM;\n");
+    }
+
+    #[test]
+    fn size_limit() {
+        assert_eq!(decompress_at_most(&FIXED, 8).unwrap(), b"VACUUM;\n");
+        assert_eq!(
+            decompress_at_most(&FIXED, 7),
+            Err("decompressed size exceeds 7 bytes".into())
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert!(decompress(b"VACUUM;").is_err());
+        assert!(decompress(&FIXED[..20]).is_err());
+        let mut corrupt = FIXED;
+        corrupt[20] ^= 1;
+        assert!(decompress(&corrupt).is_err());
+    }
+}
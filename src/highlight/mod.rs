@@ -15,12 +15,15 @@ impl Highlight for Color {
         match ttype {
             Type::Keyword(_) => Self::Magenta,
             // atoms
-            Type::String(_) | Type::Number(_) | Type::Blob(_) | Type::Boolean(_) => Self::Orange,
+            Type::String(_)
+            | Type::Integer(_)
+            | Type::Float(_)
+            | Type::Blob(_)
+            | Type::Boolean(_) => Self::Orange,
             // special symbols
             Type::Dollar
             | Type::Colon
             | Type::Asterisk
-            | Type::Question
             | Type::Param(_)
             | Type::Percent
             | Type::ParamName(_) => Self::Red,
@@ -35,6 +38,22 @@ impl Highlight for Color {
             | Type::BraceRight
             | Type::BracketLeft
             | Type::BracketRight => Self::White,
+            // arithmetic and comparison operators
+            Type::Plus
+            | Type::Minus
+            | Type::Slash
+            | Type::LessThan
+            | Type::GreaterThan
+            | Type::LessEq
+            | Type::GreaterEq
+            | Type::NotEqual
+            | Type::DoubleEqual => Self::Blue,
+            // bitwise operators
+            Type::Ampersand | Type::Pipe | Type::Tilde | Type::ShiftLeft | Type::ShiftRight => {
+                Self::Cyan
+            }
+            // string and json operators
+            Type::Concat | Type::Arrow | Type::DoubleArrow => Self::Green,
             _ => Self::Grey,
         }
     }
@@ -0,0 +1,150 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+
+/// Ignore holds the patterns of a .sqleibnizignore file, one gitignore-style glob per line:
+///
+/// - empty lines and lines starting with `#` are skipped
+/// - a pattern without a `/`, except a trailing one, matches at any depth, `*.gen.sql` is
+///   equivalent to `**/*.gen.sql`
+/// - other patterns are relative to the directory containing the ignore file
+/// - a pattern matching a directory matches all files in it, a trailing `/` only matches
+///   directories
+/// - `!` negates a pattern, re-including files matched by previous patterns, the last matching
+///   pattern decides
+pub struct Ignore {
+    /// directory containing the ignore file, patterns are matched relative to it
+    root: PathBuf,
+    globs: GlobSet,
+    /// for each glob in [Ignore::globs] whether its pattern was negated via `!`
+    negated: Vec<bool>,
+}
+
+impl Ignore {
+    /// reads and compiles the ignore file at path
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|err| {
+            format!(
+                "Issue trying to read ignore file '{}': [{}]",
+                path.display(),
+                err
+            )
+        })?;
+        // patterns are matched against canonical paths, thus the root has to be canonical too
+        let root = fs::canonicalize(path)
+            .ok()
+            .and_then(|path| Some(path.parent()?.to_path_buf()))
+            .unwrap_or_default();
+        Self::new(root, &content)
+            .map_err(|err| format!("Bad pattern in ignore file '{}': {}", path.display(), err))
+    }
+
+    fn new(root: PathBuf, content: &str) -> Result<Self, globset::Error> {
+        let mut builder = GlobSetBuilder::new();
+        let mut negated = vec![];
+        for line in content.lines().map(str::trim_end) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negate, pattern) = match line.strip_prefix('!') {
+                Some(pattern) => (true, pattern),
+                None => (false, line),
+            };
+            let (dir_only, pattern) = match pattern.strip_suffix('/') {
+                Some(pattern) => (true, pattern),
+                None => (false, pattern),
+            };
+            let pattern = match pattern.strip_prefix('/') {
+                Some(anchored) => anchored.to_string(),
+                None if pattern.contains('/') => pattern.to_string(),
+                None => format!("**/{pattern}"),
+            };
+
+            let mut patterns = vec![format!("{pattern}/**")];
+            if !dir_only {
+                patterns.push(pattern);
+            }
+            for pattern in patterns {
+                builder.add(GlobBuilder::new(&pattern).literal_separator(true).build()?);
+                negated.push(negate);
+            }
+        }
+        Ok(Self {
+            root,
+            globs: builder.build()?,
+            negated,
+        })
+    }
+
+    /// reports whether file is excluded from analysis, files outside of [Ignore::root] are never
+    /// excluded
+    pub fn is_ignored(&self, file: &Path) -> bool {
+        let Ok(relative) = file.strip_prefix(&self.root) else {
+            return false;
+        };
+        self.globs
+            .matches(relative)
+            .into_iter()
+            .max()
+            .is_some_and(|last| !self.negated[last])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::Ignore;
+
+    fn ignored(content: &str, file: &str) -> bool {
+        Ignore::new(PathBuf::from("/project"), content)
+            .unwrap()
+            .is_ignored(&Path::new("/project").join(file))
+    }
+
+    #[test]
+    fn patterns_without_slash_match_at_any_depth() {
+        assert!(ignored("*.gen.sql", "a.gen.sql"));
+        assert!(ignored("*.gen.sql", "db/migrations/a.gen.sql"));
+        assert!(!ignored("*.gen.sql", "a.sql"));
+    }
+
+    #[test]
+    fn patterns_with_slash_are_relative_to_the_root() {
+        assert!(ignored("db/*.sql", "db/a.sql"));
+        assert!(!ignored("db/*.sql", "nested/db/a.sql"));
+        assert!(!ignored("db/*.sql", "db/nested/a.sql"));
+        assert!(ignored("/a.sql", "a.sql"));
+        assert!(!ignored("/a.sql", "db/a.sql"));
+        assert!(ignored("**/*.sql", "db/nested/a.sql"));
+    }
+
+    #[test]
+    fn directories_match_their_files() {
+        assert!(ignored("vendor/**", "vendor/lib/a.sql"));
+        assert!(ignored("vendor", "vendor/lib/a.sql"));
+        assert!(ignored("vendor/", "nested/vendor/a.sql"));
+        assert!(!ignored("vendor/", "vendor"));
+    }
+
+    #[test]
+    fn negation_reincludes_files() {
+        let content = "# generated migrations\nmigrations/**\n\n!migrations/keep.sql\n";
+        assert!(ignored(content, "migrations/a.sql"));
+        assert!(!ignored(content, "migrations/keep.sql"));
+        assert!(ignored(
+            "!migrations/keep.sql\nmigrations/**",
+            "migrations/keep.sql"
+        ));
+    }
+
+    #[test]
+    fn files_outside_the_root_are_not_ignored() {
+        let ignore = Ignore::new(PathBuf::from("/project"), "**").unwrap();
+        assert!(ignore.is_ignored(Path::new("/project/a.sql")));
+        assert!(!ignore.is_ignored(Path::new("/other/a.sql")));
+    }
+}
@@ -30,7 +30,7 @@ pub fn distance(a: &[u8], b: &[u8]) -> usize {
 }
 
 #[cfg(test)]
-mod lev {
+mod tests {
     use super::distance;
 
     #[test]
@@ -1,16 +1,47 @@
 use std::f64;
 
 use crate::error::{self, Error, ImprovedLine};
+use crate::lev;
 use crate::types::{Keyword, Token, Type, rules::Rule};
 
 mod tests;
 
+/// names of the `@sqleibniz::` instructions with their usage, suggested for unknown instructions
+const INSTRUCTIONS: &[(&str, &str)] = &[
+    ("expect", "expect"),
+    ("node", "node(<node name>)"),
+    ("disable", "disable:<rule name>"),
+];
+
+/// returns the usage of the instruction with the smallest Levenshtein distance to the name of
+/// function, computed via lev::distance
+fn instruction_suggestion(function: &str) -> &'static str {
+    let name = function
+        .split(['(', ':'])
+        .next()
+        .unwrap_or_default()
+        .as_bytes();
+    INSTRUCTIONS
+        .iter()
+        .min_by_key(|(instruction, _)| lev::distance(name, instruction.as_bytes()))
+        .map(|(_, usage)| *usage)
+        .unwrap_or_default()
+}
+
 pub struct Lexer<'a> {
+    /// byte index of the current character into source
     pos: usize,
     line: usize,
+    /// column of the current character in the current line, counted in characters instead of
+    /// bytes, thus multi byte utf-8 characters take up a single column like in the rendered line
     line_pos: usize,
     name: &'a str,
     source: &'a Vec<u8>,
+    /// base position of the source, added to the positions of all tokens and errors, see
+    /// [Lexer::with_offset]
+    offset: (usize, usize),
+    /// called for each error as soon as it is found, see [Lexer::on_error]
+    on_error: Option<error::ErrorSink<'a>>,
     pub errors: Vec<Error>,
 }
 
@@ -22,15 +53,52 @@ impl<'a> Lexer<'a> {
             line_pos: 0,
             name,
             source,
+            offset: (0, 0),
+            on_error: None,
             errors: vec![],
         }
     }
 
+    /// sets the base (line, column) of the source, used for sql embedded in other files, such as
+    /// string literals in a host language. The line is added to all positions, the column only to
+    /// positions on the first line, since all following lines start at the hosts column 0.
+    pub fn with_offset(mut self, line: usize, column: usize) -> Self {
+        self.offset = (line, column);
+        self
+    }
+
+    /// registers f to be called with each error as soon as the lexer finds it, before it is
+    /// appended to [Lexer::errors]. Positions passed to f already include the offset set via
+    /// [Lexer::with_offset].
+    pub fn on_error(mut self, f: impl FnMut(&Error) + 'a) -> Self {
+        self.on_error = Some(Box::new(f));
+        self
+    }
+
+    /// offsets err by [Lexer::offset], passes it to [Lexer::on_error] and appends it to
+    /// [Lexer::errors]
+    fn emit(&mut self, mut err: Error) {
+        let (line, column) = self.offset;
+        if err.line == 0 {
+            err.start += column;
+            err.end += column;
+        }
+        err.line += line;
+        if let Some(f) = &mut self.on_error {
+            f(&err);
+        }
+        self.errors.push(err);
+    }
+
     fn advance(&mut self) {
         if self.is('\n') {
             self.line += 1;
             self.line_pos = 0;
-        } else {
+        } else if !self
+            .source
+            .get(self.pos + 1)
+            .is_some_and(|c| is_utf8_continuation(*c))
+        {
             self.line_pos += 1;
         }
         self.pos += 1;
@@ -41,6 +109,7 @@ impl<'a> Lexer<'a> {
             improved_line: None,
             file: self.name.to_string(),
             line: self.line,
+            severity: rule.severity(),
             rule,
             note: note.into(),
             msg: msg.into(),
@@ -56,8 +125,10 @@ impl<'a> Lexer<'a> {
             .is_some_and(|cc| *cc == c as u8)
     }
 
+    /// sqlite accepts all non ascii characters in identifiers, see: https://www.sqlite.org/lang_keywords.html,
+    /// since [Lexer::cur] yields bytes, these are all bytes >= 0x80
     fn is_ident(&self, c: char) -> bool {
-        matches!(c, 'a'..='z' | 'A'..='Z' | '_' | '0'..='9')
+        matches!(c, 'a'..='z' | 'A'..='Z' | '_' | '0'..='9' | '\u{80}'..='\u{ff}')
     }
 
     fn is(&self, c: char) -> bool {
@@ -69,13 +140,26 @@ impl<'a> Lexer<'a> {
     }
 
     /// Specifically matches https://www.sqlite.org/syntax/numeric-literal.html
-    fn is_sqlite_num(&self) -> bool {
+    fn is_sqlite_num(&self, hex: bool) -> bool {
         // exponent notation with +-
         // sqlite allows for separating numbers by _
         // floating point
         // hexadecimal
         // decimal
-        matches!(self.cur(), '+' | '-' | '_' | '.' | 'a'..='f' | 'A'..='F' | '0'..='9')
+        match self.cur() {
+            // signs are only part of a number if they follow the exponent (or another sign, to
+            // report 1e+-5 as a single bad literal), otherwise they are operators: 1e+5 vs 1+5.
+            // Hexadecimal literals have no exponent, e is a digit there: 0xE+1 is 14+1
+            '+' | '-' => {
+                !hex && self
+                    .pos
+                    .checked_sub(1)
+                    .and_then(|i| self.source.get(i))
+                    .is_some_and(|c| matches!(*c as char, 'e' | 'E' | '+' | '-'))
+            }
+            '_' | '.' | 'a'..='f' | 'A'..='F' | '0'..='9' => true,
+            _ => false,
+        }
     }
 
     fn cur(&self) -> char {
@@ -95,10 +179,36 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// creates a token spanning the current and the next character, advances past the current
+    /// character, the next one is skipped by the advance at the end of [Lexer::run]'s loop
+    fn double(&mut self, ttype: Type) -> Token {
+        let t = Token {
+            ttype,
+            start: self.line_pos,
+            end: self.line_pos + 1,
+            line: self.line,
+        };
+        self.advance();
+        t
+    }
+
+    /// creates a token spanning the current and the next two characters, see [Lexer::double]
+    fn triple(&mut self, ttype: Type) -> Token {
+        let t = Token {
+            ttype,
+            start: self.line_pos,
+            end: self.line_pos + 2,
+            line: self.line,
+        };
+        self.advance();
+        self.advance();
+        t
+    }
+
     /// progresses in the input until ',\n or EOF are hit.
     fn string(&mut self) -> Result<Token, Box<error::Error>> {
-        let start = self.pos;
         let line_start = self.line_pos;
+        let mut content = vec![];
         while !self.is_eof() {
             let end = self.line_pos;
             let line = self.line;
@@ -110,7 +220,6 @@ impl<'a> Lexer<'a> {
                     line_start,
                     Rule::UnterminatedString,
                 );
-                err.end += 1;
                 err.line = line;
                 err.doc_url =
                     Some("https://www.sqlite.org/lang_expr.html#literal_values_constants_");
@@ -120,22 +229,20 @@ impl<'a> Lexer<'a> {
                 });
                 return Err(Box::new(err));
             } else if self.is('\'') {
-                return Ok(Token {
-                    line: self.line,
-                    ttype: Type::String(
-                        String::from_utf8(
-                            self.source
-                                // +1 to skip the ' from the start of the string
-                                .get(start + 1..self.pos)
-                                .unwrap_or_default()
-                                .to_vec(),
-                        )
-                        .unwrap_or_default(),
-                    ),
-                    end: end + 2,
-                    start: line_start,
-                });
+                // a doubled '' is an escaped ', see:
+                // https://www.sqlite.org/lang_expr.html#literal_values_constants_
+                if self.next_is('\'') {
+                    self.advance();
+                } else {
+                    return Ok(Token {
+                        line: self.line,
+                        ttype: Type::String(String::from_utf8(content).unwrap_or_default()),
+                        end: end + 2,
+                        start: line_start,
+                    });
+                }
             }
+            content.push(self.source[self.pos]);
         }
         Err(Box::new(self.err(
             "Impossible case",
@@ -145,10 +252,88 @@ impl<'a> Lexer<'a> {
         )))
     }
 
+    /// lexes an identifier quoted by quote, either " or `, a doubled quote is an escaped quote,
+    /// see: https://www.sqlite.org/lang_keywords.html
+    fn quoted_ident(&mut self, quote: char) -> Result<Token, Box<error::Error>> {
+        let line_start = self.line_pos;
+        let mut content = vec![];
+        loop {
+            self.advance();
+            if self.is_eof() || self.is('\n') {
+                let mut err = self.err(
+                    "Unterminated quoted identifier",
+                    &format!("Consider adding a {} at the end of this identifier", quote),
+                    line_start,
+                    Rule::UnterminatedString,
+                );
+                err.doc_url = Some("https://www.sqlite.org/lang_keywords.html");
+                err.improved_line = Some(ImprovedLine {
+                    snippet: if quote == '`' { "`" } else { "\"" },
+                    start: err.end,
+                });
+                return Err(Box::new(err));
+            } else if self.is(quote) {
+                if !self.next_is(quote) {
+                    return Ok(Token {
+                        ttype: Type::Ident(String::from_utf8(content).unwrap_or_default()),
+                        start: line_start,
+                        end: self.line_pos,
+                        line: self.line,
+                    });
+                }
+                // skip the first quote of the escaped quote
+                self.advance();
+            }
+            content.push(self.source[self.pos]);
+        }
+    }
+
+    /// lexes a [bracket quoted] identifier, brackets have no escape, the identifier ends at the
+    /// first ]. Returns None without consuming anything if the current [ is not followed by a ]
+    /// on the same line, since it is a plain [ in that case.
+    ///
+    /// sqlite has no array subscripts or other syntax using brackets, thus a [ with a closing ]
+    /// on the same line always starts an identifier. Empty [], nested [ and brackets spanning
+    /// lines are kept as bracket tokens, so the parser reports them instead of the lexer
+    /// silently producing an identifier out of unrelated tokens.
+    fn bracket_ident(&mut self) -> Option<Token> {
+        let content = &self.source[self.pos + 1..];
+        let len = content
+            .iter()
+            .position(|c| matches!(c, b']' | b'[' | b'\n'))
+            .filter(|len| *len > 0 && content[*len] == b']')?;
+        let ttype = Type::Ident(String::from_utf8(content[..len].to_vec()).unwrap_or_default());
+        let start = self.line_pos;
+        for _ in 0..=len {
+            self.advance();
+        }
+        Some(Token {
+            ttype,
+            start,
+            end: self.line_pos,
+            line: self.line,
+        })
+    }
+
     pub fn run(&mut self) -> Vec<Token> {
+        let mut toks = self.lex();
+        let (line, column) = self.offset;
+        if line != 0 || column != 0 {
+            for tok in &mut toks {
+                if tok.line == 0 {
+                    tok.start += column;
+                    tok.end += column;
+                }
+                tok.line += line;
+            }
+        }
+        toks
+    }
+
+    fn lex(&mut self) -> Vec<Token> {
         let mut r = vec![];
         if self.source.is_empty() {
-            self.errors.push(self.err(
+            self.emit(self.err(
                 "No content found in source file",
                 &format!("consider adding statements to '{}'", self.name),
                 0,
@@ -161,41 +346,61 @@ impl<'a> Lexer<'a> {
             match self.cur() {
                 // skipping whitespace
                 '\t' | '\r' | ' ' | '\n' => {}
+                '/' if !self.next_is('*') => r.push(self.single(Type::Slash)),
                 // comments, see: https://www.sqlite.org/lang_comment.html
                 '/' => {
-                    if self.next_is('*') {
-                        while !self.is_eof() {
+                    let line = self.line;
+                    let line_start = self.line_pos;
+                    // skip '/', the '*' is skipped in the loop, thus /*/ is not a closed comment
+                    self.advance();
+                    loop {
+                        self.advance();
+                        if self.is_eof() {
+                            let mut err = self.err(
+                                "Unterminated comment",
+                                "sqlite ends a comment missing its closing */ at the end of the input, consider adding */ to end the comment",
+                                line_start,
+                                Rule::Syntax,
+                            );
+                            err.line = line;
+                            err.end = line_start + 2;
+                            err.doc_url = Some("https://www.sqlite.org/lang_comment.html");
+                            self.emit(err);
+                            break;
+                        }
+                        if self.is('*') && self.next_is('/') {
+                            // skip '*', the advance at the end of the loop skips '/'
                             self.advance();
-                            if self.is('*') && self.next_is('/') {
-                                break;
-                            }
+                            break;
                         }
                     }
                 }
+                // json operators, see: https://www.sqlite.org/json1.html#jptr
+                '-' if self.next_is('>') => {
+                    let t = if self.source.get(self.pos + 2).is_some_and(|c| *c == b'>') {
+                        self.triple(Type::DoubleArrow)
+                    } else {
+                        self.double(Type::Arrow)
+                    };
+                    r.push(t)
+                }
+                '-' if !self.next_is('-') => r.push(self.single(Type::Minus)),
                 // comments, see: https://www.sqlite.org/lang_comment.html
                 '-' => {
                     // skip --
                     self.advance();
-                    if !self.is('-') {
-                        self.errors.push(self.err(
-                            "'-' is not a valid symbol at this point",
-                            "If you meant a comment, those are prefixed with '--'",
-                            self.line_pos,
-                            Rule::Syntax,
-                        ));
-                        break;
-                    }
-
                     self.advance();
 
                     while !self.is_eof() {
                         if self.is('\n') {
                             break;
                         } else if self.is('@') {
+                            // include the @ in front of the instruction
+                            let line_start = self.line_pos;
                             self.advance(); // skip '@'
                             let start = self.pos;
 
-                            while !self.is_eof() && !self.cur().is_whitespace() {
+                            while !self.is_eof() && !self.cur().is_ascii_whitespace() {
                                 self.advance();
                             }
 
@@ -205,7 +410,7 @@ impl<'a> Lexer<'a> {
                             let mut err = self.err(
                                 "Unknown sqleibniz instruction",
                                 "placeholder",
-                                self.line_pos,
+                                line_start,
                                 Rule::BadSqleibnizInstruction,
                             );
 
@@ -214,14 +419,49 @@ impl<'a> Lexer<'a> {
                                     "expect" => {
                                         r.push(self.single(Type::InstructionExpect));
                                     }
+                                    // node(<node name>)
+                                    _ if function
+                                        .strip_prefix("node(")
+                                        .and_then(|name| name.strip_suffix(')'))
+                                        .is_some_and(|name| {
+                                            !name.is_empty()
+                                                && name.bytes().all(|c| c.is_ascii_alphanumeric())
+                                        }) =>
+                                    {
+                                        let name = &function["node(".len()..function.len() - 1];
+                                        r.push(Token {
+                                            ttype: Type::InstructionNode(name.to_string()),
+                                            start: line_start,
+                                            end: self.line_pos,
+                                            line: self.line,
+                                        });
+                                    }
+                                    // disable:<rule name>
+                                    _ if function.starts_with("disable:") => {
+                                        let name = &function["disable:".len()..];
+                                        match Rule::from_name(name) {
+                                            Some(rule) => r.push(Token {
+                                                ttype: Type::InstructionDisable(rule),
+                                                start: line_start,
+                                                end: self.line_pos,
+                                                line: self.line,
+                                            }),
+                                            None => {
+                                                err.msg = "Unknown rule".into();
+                                                err.note = format!(
+                                                    "`{name}` is not a rule, rules are named like in `-D`, for instance Quirk or UnknownKeyword"
+                                                );
+                                                self.emit(err);
+                                            }
+                                        }
+                                    }
                                     _ => {
                                         err.note = format!(
-                                            "`{}` is not a valid sqleibniz instruction",
-                                            function
+                                            "`{}` is not a valid sqleibniz instruction, did you mean: `sqleibniz::{}`",
+                                            function,
+                                            instruction_suggestion(function)
                                         );
-                                        err.start = start - 1;
-                                        err.end = self.pos;
-                                        self.errors.push(err);
+                                        self.emit(err);
                                     }
                                 }
                             } else {
@@ -229,9 +469,7 @@ impl<'a> Lexer<'a> {
                                     "`{}` is not a valid sqleibniz instruction",
                                     instruction
                                 );
-                                err.start = start - 1;
-                                err.end = self.pos;
-                                self.errors.push(err);
+                                self.emit(err);
                             }
 
                             // skip rest of the line
@@ -247,20 +485,179 @@ impl<'a> Lexer<'a> {
                 // string, see: https://www.sqlite.org/lang_expr.html#literal_values_constants_
                 '\'' => match self.string() {
                     Ok(str_tok) => r.push(str_tok),
-                    Err(err) => self.errors.push(*err),
+                    Err(err) => self.emit(*err),
+                },
+                // quoted identifier, see: https://www.sqlite.org/lang_keywords.html
+                '"' => match self.quoted_ident('"') {
+                    Ok(ident) => {
+                        let mut err = self.err(
+                            "Double quoted identifier",
+                            "sqlite treats a double quoted identifier not matching any column as a string literal instead of reporting an error, use single quotes for strings",
+                            ident.start,
+                            Rule::Quirk,
+                        );
+                        // the end of quoted identifiers is the closing quote
+                        err.end = ident.end + 1;
+                        err.doc_url = Some(
+                            "https://www.sqlite.org/quirks.html#double_quoted_string_literals_are_accepted",
+                        );
+                        self.emit(err);
+                        r.push(ident)
+                    }
+                    Err(err) => self.emit(*err),
+                },
+                // mysql style quoted identifier, accepted by sqlite for compatibility, see:
+                // https://www.sqlite.org/lang_keywords.html
+                '`' => match self.quoted_ident('`') {
+                    Ok(ident) => {
+                        let mut err = self.err(
+                            "Backtick quoted identifier",
+                            "backtick quoting is a MySQL extension sqlite only accepts for compatibility, use \"double quotes\" or [brackets] instead",
+                            ident.start,
+                            Rule::Quirk,
+                        );
+                        // the end of quoted identifiers is the closing quote
+                        err.end = ident.end + 1;
+                        err.doc_url = Some("https://www.sqlite.org/lang_keywords.html");
+                        self.emit(err);
+                        r.push(ident)
+                    }
+                    Err(err) => self.emit(*err),
                 },
                 '*' => r.push(self.single(Type::Asterisk)),
                 ';' => r.push(self.single(Type::Semicolon)),
                 ',' => r.push(self.single(Type::Comma)),
                 '%' => r.push(self.single(Type::Percent)),
+                '=' if self.next_is('=') => {
+                    let t = self.double(Type::DoubleEqual);
+                    r.push(t)
+                }
                 '=' => r.push(self.single(Type::Equal)),
+                '+' => r.push(self.single(Type::Plus)),
+                '<' if self.next_is('<') => {
+                    let t = self.double(Type::ShiftLeft);
+                    r.push(t)
+                }
+                '<' if self.next_is('=') => {
+                    let t = self.double(Type::LessEq);
+                    r.push(t)
+                }
+                '<' if self.next_is('>') => {
+                    let t = self.double(Type::NotEqual);
+                    r.push(t)
+                }
+                '<' => r.push(self.single(Type::LessThan)),
+                '>' if self.next_is('>') => {
+                    let t = self.double(Type::ShiftRight);
+                    r.push(t)
+                }
+                '>' if self.next_is('=') => {
+                    let t = self.double(Type::GreaterEq);
+                    r.push(t)
+                }
+                '>' => r.push(self.single(Type::GreaterThan)),
+                '!' if self.next_is('=') => {
+                    let t = self.double(Type::NotEqual);
+                    r.push(t)
+                }
+                '|' if self.next_is('|') => {
+                    let t = self.double(Type::Concat);
+                    r.push(t)
+                }
+                '|' => r.push(self.single(Type::Pipe)),
+                '&' => r.push(self.single(Type::Ampersand)),
+                '~' => r.push(self.single(Type::Tilde)),
+                // named bind parameters, see: https://www.sqlite.org/lang_expr.html#parameters
+                ':' | '@' | '$' if self.next().is_some_and(|c| self.is_ident(c)) => {
+                    let start = self.pos;
+                    let line_start = self.line_pos;
+                    // skip prefix
+                    self.advance();
+                    while !self.is_eof() && self.is_ident(self.cur()) {
+                        self.advance();
+                    }
+                    let chars = self.source.get(start..self.pos).unwrap_or_default();
+                    r.push(Token {
+                        line: self.line,
+                        ttype: Type::ParamName(
+                            String::from_utf8(chars.to_vec()).unwrap_or_default(),
+                        ),
+                        start: line_start,
+                        end: self.line_pos,
+                    });
+                    continue;
+                }
                 '@' => r.push(self.single(Type::At)),
                 ':' => r.push(self.single(Type::Colon)),
                 '$' => r.push(self.single(Type::Dollar)),
-                '?' => r.push(self.single(Type::Question)),
+                // numbered bind parameters, see: https://www.sqlite.org/lang_expr.html#parameters
+                '?' => {
+                    let line_start = self.line_pos;
+                    // skip '?'
+                    self.advance();
+                    let start = self.pos;
+                    while !self.is_eof() && self.cur().is_ascii_digit() {
+                        self.advance();
+                    }
+                    let digits = self.source.get(start..self.pos).unwrap_or_default();
+                    let counter = if digits.is_empty() {
+                        Some(Type::PARAM_AUTO)
+                    } else {
+                        std::str::from_utf8(digits)
+                            .ok()
+                            .and_then(|d| d.parse::<usize>().ok())
+                            .filter(|c| (1..=Type::MAX_PARAM).contains(c))
+                    };
+                    let Some(counter) = counter else {
+                        let mut err = self.err(
+                            "Invalid bind parameter",
+                            &format!(
+                                "numbered bind parameters have to be between ?1 and ?{}",
+                                Type::MAX_PARAM
+                            ),
+                            line_start,
+                            Rule::InvalidNumericLiteral,
+                        );
+                        err.doc_url = Some("https://www.sqlite.org/lang_expr.html#parameters");
+                        self.emit(err);
+                        // continue with an anonymous parameter, so the parser does not report
+                        // the missing expression as well
+                        r.push(Token {
+                            line: self.line,
+                            ttype: Type::Param(Type::PARAM_AUTO),
+                            start: line_start,
+                            end: self.line_pos,
+                        });
+                        continue;
+                    };
+                    r.push(Token {
+                        line: self.line,
+                        ttype: Type::Param(counter),
+                        start: line_start,
+                        end: self.line_pos,
+                    });
+                    continue;
+                }
                 '(' => r.push(self.single(Type::BraceLeft)),
                 ')' => r.push(self.single(Type::BraceRight)),
-                '[' => r.push(self.single(Type::BracketLeft)),
+                // identifier quoted as in MS Access and SQL Server, accepted by sqlite for
+                // compatibility, see: https://www.sqlite.org/lang_keywords.html
+                '[' => match self.bracket_ident() {
+                    Some(ident) => {
+                        let mut err = self.err(
+                            "Bracket quoted identifier",
+                            "bracket quoting is a MS Access and SQL Server extension sqlite only accepts for compatibility, use \"double quotes\" instead",
+                            ident.start,
+                            Rule::Quirk,
+                        );
+                        // the end of quoted identifiers is the closing quote
+                        err.end = ident.end + 1;
+                        err.doc_url = Some("https://www.sqlite.org/lang_keywords.html");
+                        self.emit(err);
+                        r.push(ident)
+                    }
+                    None => r.push(self.single(Type::BracketLeft)),
+                },
                 ']' => r.push(self.single(Type::BracketRight)),
                 // numbers, see: https://www.sqlite.org/lang_expr.html#literal_values_constants_
                 '0'..='9' | '.' => {
@@ -296,7 +693,7 @@ impl<'a> Lexer<'a> {
 
                     // number state machine
                     let start = self.pos;
-                    while !self.is_eof() && self.is_sqlite_num() {
+                    while !self.is_eof() && self.is_sqlite_num(is_hex) {
                         self.advance();
                     }
 
@@ -312,11 +709,13 @@ impl<'a> Lexer<'a> {
                         .collect::<String>();
 
                     if is_hex {
-                        match i64::from_str_radix(&str, 16) {
+                        // hexadecimal literals are 64-bit two's-complement integers, thus
+                        // 0x8000000000000000 is i64::MIN
+                        match u64::from_str_radix(&str, 16) {
                             Ok(number) => {
                                 r.push(Token {
                                     line: self.line,
-                                    ttype: Type::Number(number as f64),
+                                    ttype: Type::Integer(number as i64),
                                     start: line_start,
                                     end: self.line_pos,
                                 });
@@ -330,15 +729,21 @@ impl<'a> Lexer<'a> {
                                 );
                                 err.doc_url =
                                     Some("https://www.sqlite.org/syntax/numeric-literal.html");
-                                self.errors.push(err);
+                                self.emit(err);
                             }
                         };
                     } else {
-                        match str.parse::<f64>() {
-                            Ok(number) => {
+                        // decimal literals without a decimal point or exponent are integers,
+                        // unless they do not fit into 64 bits, then sqlite reads them as floats
+                        let number = match str.parse::<i64>() {
+                            Ok(number) => Ok(Type::Integer(number)),
+                            Err(_) => str.parse::<f64>().map(Type::Float),
+                        };
+                        match number {
+                            Ok(ttype) => {
                                 r.push(Token {
                                     line: self.line,
-                                    ttype: Type::Number(number),
+                                    ttype,
                                     start: line_start,
                                     end: self.line_pos,
                                 });
@@ -352,71 +757,58 @@ impl<'a> Lexer<'a> {
                                 );
                                 err.doc_url =
                                     Some("https://www.sqlite.org/syntax/numeric-literal.html");
-                                self.errors.push(err);
+                                self.emit(err);
                             }
                         };
                     };
                     // this skips the advance at the bottom of the while loop
                     continue;
                 }
-                // blobs, see above
-                'X' | 'x' => {
+                // blobs, see above, x and X not followed by ' are identifiers
+                'X' | 'x' if self.next_is('\'') => {
                     let line_start = self.line_pos;
                     let line = self.line;
-                    if self.next_is('\'') {
-                        self.advance(); // skip X
-                        if let Ok(str_tok) = self.string() {
-                            if let Type::String(str) = &str_tok.ttype {
-                                let mut had_bad_hex = false;
-                                for (idx, c) in str.chars().enumerate() {
-                                    if !c.is_ascii_hexdigit() {
-                                        let mut err = self.err("Bad blob data", &format!("a Blob is hexadecimal data, '{}' is not valid hex (a..=f, A..=F, 0..=9)", c), line_start+2+idx, Rule::InvalidBlob);
-                                        err.end = line_start + 2 + idx;
-                                        err.doc_url = Some(
-                                            "https://www.sqlite.org/lang_expr.html#literal_values_constants_",
-                                        );
-                                        self.errors.push(err);
-                                        had_bad_hex = true;
-                                        break;
-                                    }
-                                }
-                                if had_bad_hex {
+                    self.advance(); // skip X
+                    if let Ok(str_tok) = self.string() {
+                        if let Type::String(str) = &str_tok.ttype {
+                            let mut had_bad_hex = false;
+                            for (idx, c) in str.chars().enumerate() {
+                                if !c.is_ascii_hexdigit() {
+                                    let mut err = self.err("Bad blob data", &format!("a Blob is hexadecimal data, '{}' is not valid hex (a..=f, A..=F, 0..=9)", c), line_start+2+idx, Rule::InvalidBlob);
+                                    err.end = line_start + 2 + idx + 1;
+                                    err.doc_url = Some(
+                                        "https://www.sqlite.org/lang_expr.html#literal_values_constants_",
+                                    );
+                                    self.emit(err);
+                                    had_bad_hex = true;
                                     break;
                                 }
-                                r.push(Token {
-                                    line,
-                                    ttype: Type::Blob(str.as_bytes().to_vec()),
-                                    start: str_tok.start,
-                                    end: str_tok.end,
-                                });
                             }
-                        } else {
-                            let mut err = self.err(
-                                "Unterminated blob string",
-                                "a Blob is hexadecimal data prefixed with X' and postfixed with ', you forgot the closing '",
-                                line_start,
-                                Rule::InvalidBlob,
-                            );
-                            err.line = line;
-                            err.doc_url = Some(
-                                "https://www.sqlite.org/lang_expr.html#literal_values_constants_",
-                            );
-                            self.errors.push(err);
+                            if had_bad_hex {
+                                break;
+                            }
+                            r.push(Token {
+                                line,
+                                ttype: Type::Blob(str.as_bytes().to_vec()),
+                                start: str_tok.start,
+                                end: str_tok.end,
+                            });
                         }
                     } else {
                         let mut err = self.err(
-                            "Malformed blob",
-                            "a Blob is hexadecimal data prefixed with X' and postfixed with '",
-                            self.line_pos,
+                            "Unterminated blob string",
+                            "a Blob is hexadecimal data prefixed with X' and postfixed with ', you forgot the closing '",
+                            line_start,
                             Rule::InvalidBlob,
                         );
+                        err.line = line;
                         err.doc_url =
                             Some("https://www.sqlite.org/lang_expr.html#literal_values_constants_");
-                        self.errors.push(err);
+                        self.emit(err);
                     }
                 }
                 // identifiers / keywords: https://www.sqlite.org/lang_keywords.html
-                'a'..='z' | 'A'..='Z' | '_' => {
+                'a'..='z' | 'A'..='Z' | '_' | '\u{80}'..='\u{ff}' => {
                     let start = self.pos;
                     let line_start = self.line_pos;
                     while !self.is_eof() && self.is_ident(self.cur()) {
@@ -454,15 +846,16 @@ impl<'a> Lexer<'a> {
                         self.line_pos,
                         Rule::UnknownCharacter,
                     );
+                    err.end = self.line_pos + 1;
                     err.doc_url = Some("https://www.sqlite.org/syntax/expr.html");
-                    self.errors.push(err);
+                    self.emit(err);
                 }
             }
             self.advance();
         }
 
         if r.is_empty() && self.errors.is_empty() {
-            self.errors.push(self.err(
+            self.emit(self.err(
                 "No statements found in source file",
                 &format!("consider adding statements to '{}'", self.name),
                 0,
@@ -473,3 +866,8 @@ impl<'a> Lexer<'a> {
         r
     }
 }
+
+/// continuation bytes are the second to fourth byte of a multi byte utf-8 character
+fn is_utf8_continuation(c: u8) -> bool {
+    c & 0b1100_0000 == 0b1000_0000
+}
@@ -52,7 +52,10 @@ mod should_pass {
         string,
         string: "'text'"=vec![Type::String(String::from("text"))],
         empty_string: "''"=vec![Type::String(String::from(""))],
-        string_with_ending: "'str';"=vec![Type::String(String::from("str")), Type::Semicolon]
+        string_with_ending: "'str';"=vec![Type::String(String::from("str")), Type::Semicolon],
+        escaped_quote: "'it''s'"=vec![Type::String(String::from("it's"))],
+        only_escaped_quote: "''''"=vec![Type::String(String::from("'"))],
+        escaped_quotes_with_ending: "'''a''';"=vec![Type::String(String::from("'a'")), Type::Semicolon]
     }
 
     test_group_pass_assert! {
@@ -68,21 +71,109 @@ mod should_pass {
         at: "@"=vec![Type::At],
         colon: ":"=vec![Type::Colon],
         dollar: "$"=vec![Type::Dollar],
-        question: "?"=vec![Type::Question]
+        question: "?"=vec![Type::Param(Type::PARAM_AUTO)]
+    }
+
+    test_group_pass_assert! {
+        param,
+        numbered: "?12"=vec![Type::Param(12)],
+        numbered_max: "?32766"=vec![Type::Param(Type::MAX_PARAM)],
+        numbered_with_following: "?1,?"=vec![Type::Param(1), Type::Comma, Type::Param(Type::PARAM_AUTO)],
+        spaced_number: "? 1"=vec![Type::Param(Type::PARAM_AUTO), Type::Integer(1)],
+        colon: ":name"=vec![Type::ParamName(String::from(":name"))],
+        at: "@name"=vec![Type::ParamName(String::from("@name"))],
+        dollar: "$name"=vec![Type::ParamName(String::from("$name"))],
+        digits: ":1"=vec![Type::ParamName(String::from(":1"))],
+        in_expr: "x=:a_1"=vec![Type::Ident(String::from("x")), Type::Equal, Type::ParamName(String::from(":a_1"))]
+    }
+
+    test_group_pass_assert! {
+        operator,
+        plus: "+"=vec![Type::Plus],
+        minus: "-"=vec![Type::Minus],
+        minus_before_comment: "- -- comment"=vec![Type::Minus],
+        slash: "/"=vec![Type::Slash],
+        slash_after_comment: "/* comment */ /"=vec![Type::Slash],
+        less_than: "<"=vec![Type::LessThan],
+        greater_than: ">"=vec![Type::GreaterThan],
+        less_eq: "<="=vec![Type::LessEq],
+        greater_eq: ">="=vec![Type::GreaterEq],
+        not_equal: "!="=vec![Type::NotEqual],
+        not_equal_angled: "<>"=vec![Type::NotEqual],
+        double_equal: "=="=vec![Type::DoubleEqual],
+        concat: "||"=vec![Type::Concat],
+        ampersand: "&"=vec![Type::Ampersand],
+        pipe: "|"=vec![Type::Pipe],
+        tilde: "~"=vec![Type::Tilde],
+        arrow: "->"=vec![Type::Arrow],
+        double_arrow: "->>"=vec![Type::DoubleArrow],
+        double_arrow_then_greater: "->>>"=vec![Type::DoubleArrow, Type::GreaterThan],
+        arrow_without_spaces: "a->'$'"=vec![Type::Ident("a".into()), Type::Arrow, Type::String("$".into())],
+        json_extraction_chain: "json -> '$.a' ->> '$.b'"=vec![
+            Type::Ident("json".into()),
+            Type::Arrow,
+            Type::String("$.a".into()),
+            Type::DoubleArrow,
+            Type::String("$.b".into()),
+        ],
+        arrow_before_comment: "a -> --comment"=vec![Type::Ident("a".into()), Type::Arrow],
+        minus_then_greater: "- >"=vec![Type::Minus, Type::GreaterThan],
+        shift_left: "<<"=vec![Type::ShiftLeft],
+        shift_right: ">>"=vec![Type::ShiftRight],
+        concat_then_pipe: "|||"=vec![Type::Concat, Type::Pipe],
+        concat_strings: "'a' || 'b'"=vec![Type::String("a".into()), Type::Concat, Type::String("b".into())],
+        concat_without_spaces: "'a'||'b'"=vec![Type::String("a".into()), Type::Concat, Type::String("b".into())],
+        shift_left_then_less_eq: "<<<="=vec![Type::ShiftLeft, Type::LessEq],
+        shift_right_then_greater_eq: ">>>="=vec![Type::ShiftRight, Type::GreaterEq],
+        spaced_less_thans: "< <"=vec![Type::LessThan, Type::LessThan],
+        spaced_greater_thans: "> >"=vec![Type::GreaterThan, Type::GreaterThan],
+        bitwise_expression: "~a & 1 | b << 2 >> c"=vec![
+            Type::Tilde,
+            Type::Ident("a".into()),
+            Type::Ampersand,
+            Type::Integer(1),
+            Type::Pipe,
+            Type::Ident("b".into()),
+            Type::ShiftLeft,
+            Type::Integer(2),
+            Type::ShiftRight,
+            Type::Ident("c".into()),
+        ],
+        minus_not_comment: "1-2"=vec![Type::Integer(1), Type::Minus, Type::Integer(2)],
+        slash_not_comment: "1/2"=vec![Type::Integer(1), Type::Slash, Type::Integer(2)],
+        expression: "1+2<=3"=vec![Type::Integer(1), Type::Plus, Type::Integer(2), Type::LessEq, Type::Integer(3)],
+        // two character operators are matched greedily
+        not_equal_then_equal: "<>="=vec![Type::NotEqual, Type::Equal],
+        less_eq_then_greater: "<=>"=vec![Type::LessEq, Type::GreaterThan],
+        greater_then_less: "><"=vec![Type::GreaterThan, Type::LessThan],
+        comparison_without_spaces: "1<2"=vec![Type::Integer(1), Type::LessThan, Type::Integer(2)]
     }
 
     test_group_pass_assert! {
         number,
         // edge cases
-        zero: "0"=vec![Type::Number(0.0),],
-        zero_float: ".0"=vec![Type::Number(0.0),],
-        zero_hex: "0x0"=vec![Type::Number(0.0),],
-        zero_float_with_prefix_zero: "0.0"=vec![Type::Number(0.0),],
+        zero: "0"=vec![Type::Integer(0),],
+        zero_float: ".0"=vec![Type::Float(0.0),],
+        zero_hex: "0x0"=vec![Type::Integer(0),],
+        zero_float_with_prefix_zero: "0.0"=vec![Type::Float(0.0),],
+
+        float_all_paths: "1_000.12_000e+3_5"=vec![Type::Float(1.00012e+38),],
+        float_all_paths2: ".1_000e-1_2"=vec![Type::Float(1e-13),],
+        hex: "0xABCDEF"=vec![Type::Integer(0xABCDEF),],
+        hex_large_x: "0XABCDEF"=vec![Type::Integer(0xABCDEF)],
 
-        float_all_paths: "1_000.12_000e+3_5"=vec![Type::Number(1.00012e+38),],
-        float_all_paths2: ".1_000e-1_2"=vec![Type::Number(1e-13),],
-        hex: "0xABCDEF"=vec![Type::Number(0xABCDEF as f64),],
-        hex_large_x: "0XABCDEF"=vec![Type::Number(0xABCDEF as f64)]
+        // integer and float distinction
+        float_exponent_without_point: "1e5"=vec![Type::Float(1e5)],
+        // 19 digits, f64 only holds 53 bits of mantissa and would round to 9223372036854775808
+        integer_beyond_f64_precision: "9223372036854775807"=vec![Type::Integer(i64::MAX)],
+        integer_beyond_f64_precision2: "1234567890123456789"=vec![Type::Integer(1234567890123456789)],
+        integer_with_underscores: "9_223_372_036_854_775_807"=vec![Type::Integer(i64::MAX)],
+        integer_out_of_range_is_float: "9223372036854775808"=vec![Type::Float(9223372036854775808.0)],
+        hex_twos_complement: "0x8000000000000000"=vec![Type::Integer(i64::MIN)],
+        hex_all_set: "0xFFFFFFFFFFFFFFFF"=vec![Type::Integer(-1)],
+        // e is a hex digit, not an exponent, the sign is an operator
+        hex_e_plus: "0xE+1"=vec![Type::Integer(0xE), Type::Plus, Type::Integer(1)],
+        hex_e_minus: "0x1e-1"=vec![Type::Integer(0x1e), Type::Minus, Type::Integer(1)]
     }
 
     test_group_pass_assert! {
@@ -92,15 +183,23 @@ mod should_pass {
         empty_small: "x''"=vec![Type::Blob(vec![])],
 
         filled: "X'12345'"=vec![Type::Blob(vec![49, 50, 51, 52, 53])],
-        filled_small: "x'1234567'"=vec![Type::Blob(vec![49, 50, 51, 52, 53, 54, 55])]
+        filled_small: "x'1234567'"=vec![Type::Blob(vec![49, 50, 51, 52, 53, 54, 55])],
+
+        // without a quote following, x is an identifier
+        no_quotes: "X"=vec![Type::Ident(String::from("X"))],
+        no_quotes_small: "x"=vec![Type::Ident(String::from("x"))],
+        ident_starting_with_x: "xml"=vec![Type::Ident(String::from("xml"))]
     }
 
     test_group_pass_assert! {
         sqleibniz_instruction,
         with_description: "--@sqleibniz::expect description"=vec![Type::InstructionExpect],
         without_description: "--@sqleibniz::expect"=vec![Type::InstructionExpect],
-        with_description_with_following: "--@sqleibniz::expect\n5"=vec![Type::InstructionExpect, Type::Number(5.0)],
-        with_description_with_more_following: "--@sqleibniz::expect\n5;12;"=vec![Type::InstructionExpect, Type::Number(5.0), Type::Semicolon, Type::Number(12.0), Type::Semicolon]
+        with_description_with_following: "--@sqleibniz::expect\n5"=vec![Type::InstructionExpect, Type::Integer(5)],
+        with_description_with_more_following: "--@sqleibniz::expect\n5;12;"=vec![Type::InstructionExpect, Type::Integer(5), Type::Semicolon, Type::Integer(12), Type::Semicolon],
+        node: "--@sqleibniz::node(Vacuum)"=vec![Type::InstructionNode(String::from("Vacuum"))],
+        node_with_following: "-- @sqleibniz::node(Vacuum)\nVACUUM;"=vec![Type::InstructionNode(String::from("Vacuum")), Type::Keyword(crate::types::Keyword::VACUUM), Type::Semicolon],
+        disable: "-- @sqleibniz::disable:Quirk"=vec![Type::InstructionDisable(crate::types::rules::Rule::Quirk)]
     }
 }
 
@@ -116,6 +215,7 @@ mod should_fail {
     test_group_fail! {
         string,
         unterminated_string_eof: "'",
+        unterminated_string_escaped_quote: "'it''",
         unterminated_string_with_space: "'\n\t\r\n "
     }
 
@@ -124,7 +224,9 @@ mod should_fail {
         line_comment: "-- comment",
         line_comment_with_newline: "--comment\n",
         multiline_comment_single_line: "/**/",
-        multiline_comment: "/*\n\n\n*/"
+        multiline_comment: "/*\n\n\n*/",
+        unterminated_multiline_comment: "/* never closed",
+        unterminated_multiline_comment_slash: "/*/"
     }
 
     test_group_fail! {
@@ -138,14 +240,14 @@ mod should_fail {
         bad_float_with_e: ".e",
         bad_float_with_large_e: ".E",
         bad_float_multiple_e: ".eeee",
-        bad_float_combination: "12.e+-15"
+        bad_float_combination: "12.e+-15",
+        // hexadecimal literals are limited to 64 bits
+        bad_hex_too_large: "0x10000000000000000"
     }
 
     test_group_fail! {
         blob,
         // edge cases
-        no_quotes: "X",
-        no_quotes_small: "x",
         unterminated: "X'",
         unterminated_small: "x'",
         unterminated1: "X'12819281",
@@ -153,9 +255,297 @@ mod should_fail {
         bad_hex: "X'1281928FFFY'"
     }
 
+    test_group_fail! {
+        quoted_ident,
+        unterminated: "\"hello",
+        unterminated_escaped_quote: "\"hello\"\"",
+        unterminated_at_newline: "\"hello\n\""
+    }
+
+    test_group_fail! {
+        backtick_ident,
+        unterminated: "`",
+        unterminated_name: "`my_table",
+        unterminated_escaped_backtick: "`hello``"
+    }
+
+    test_group_fail! {
+        operator,
+        // sqlite has no logical not operator, only !=
+        bang: "!"
+    }
+
+    #[test]
+    fn param() {
+        for input in ["?0", "?32767", "?99999999999999999999999"] {
+            let source = input.as_bytes().to_vec();
+            let mut l = crate::lexer::Lexer::new(&source, "lexer_tests_fail");
+            let toks = l.run();
+            assert_eq!(l.errors.len(), 1);
+            assert_eq!(
+                toks.into_iter().map(|tok| tok.ttype).collect::<Vec<_>>(),
+                vec![crate::types::Type::Param(crate::types::Type::PARAM_AUTO)]
+            );
+        }
+    }
+
     test_group_fail! {
         sqleibniz_instruction,
         none: "--@sqleibniz",
-        unknown: "--@sqleibniz::unknown"
+        unknown: "--@sqleibniz::unknown",
+        node_without_name: "--@sqleibniz::node()",
+        node_unterminated: "--@sqleibniz::node(Vacuum",
+        node_bad_name: "--@sqleibniz::node(Vac-uum)",
+        disable_unknown_rule: "--@sqleibniz::disable:Quirks",
+        disable_without_rule: "--@sqleibniz::disable:"
+    }
+}
+
+#[cfg(test)]
+mod offset {
+    use crate::{lexer, parser::Parser, types::Type};
+
+    #[test]
+    fn tokens_are_offset() {
+        let input = "VACUUM;\nVACUUM;".as_bytes().to_vec();
+        let mut l = lexer::Lexer::new(&input, "lexer_tests_offset").with_offset(10, 4);
+        let toks = l.run();
+        assert_eq!(l.errors.len(), 0);
+        assert_eq!(
+            toks.iter()
+                .map(|t| (t.line, t.start))
+                .collect::<Vec<(usize, usize)>>(),
+            vec![(10, 4), (10, 10), (11, 0), (11, 6)]
+        );
+        assert_eq!(toks[0].ttype, Type::Keyword(crate::types::Keyword::VACUUM));
+    }
+
+    #[test]
+    fn lexer_errors_are_offset() {
+        let input = "\n'unterminated".as_bytes().to_vec();
+        let mut l = lexer::Lexer::new(&input, "lexer_tests_offset").with_offset(10, 4);
+        l.run();
+        assert_eq!(l.errors.len(), 1);
+        assert_eq!(l.errors[0].line, 11);
+        assert_eq!(l.errors[0].start, 0);
+    }
+
+    #[test]
+    fn instruction_errors_use_line_columns() {
+        let input = "VACUUM;\n-- @sqleibniz::foo".as_bytes().to_vec();
+        let mut l = lexer::Lexer::new(&input, "lexer_tests_offset");
+        l.run();
+        assert_eq!(l.errors.len(), 1);
+        assert_eq!((l.errors[0].start, l.errors[0].end), (3, 18));
+    }
+
+    #[test]
+    fn unknown_instruction_suggests_closest() {
+        for (input, suggestion) in [
+            ("-- @sqleibniz::expct", "`sqleibniz::expect`"),
+            (
+                "-- @sqleibniz::nod(Vacuum)",
+                "`sqleibniz::node(<node name>)`",
+            ),
+            (
+                "-- @sqleibniz::disabel:Quirk",
+                "`sqleibniz::disable:<rule name>`",
+            ),
+        ] {
+            let input = input.as_bytes().to_vec();
+            let mut l = lexer::Lexer::new(&input, "lexer_tests_instruction");
+            l.run();
+            assert_eq!(l.errors.len(), 1);
+            assert!(
+                l.errors[0].note.ends_with(suggestion),
+                "{} does not suggest {}",
+                l.errors[0].note,
+                suggestion
+            );
+        }
+    }
+
+    #[test]
+    fn columns_count_characters() {
+        let input = "имя 'é' x".as_bytes().to_vec();
+        let toks = lexer::Lexer::new(&input, "lexer_tests_offset").run();
+        assert_eq!(toks[0].ttype, Type::Ident(String::from("имя")));
+        assert_eq!(
+            toks.iter()
+                .map(|t| (t.start, t.end))
+                .collect::<Vec<(usize, usize)>>(),
+            vec![(0, 3), (4, 7), (8, 9)]
+        );
+    }
+
+    #[test]
+    fn unterminated_comment_points_at_start() {
+        let input = "VACUUM;\n  /* never\nclosed".as_bytes().to_vec();
+        let mut l = lexer::Lexer::new(&input, "lexer_tests_offset");
+        l.run();
+        assert_eq!(l.errors.len(), 1);
+        assert_eq!(
+            (l.errors[0].line, l.errors[0].start, l.errors[0].end),
+            (1, 2, 4)
+        );
+    }
+
+    #[test]
+    fn streamed_errors_are_offset() {
+        let input = "\n'unterminated".as_bytes().to_vec();
+        let mut streamed = vec![];
+        let mut l = lexer::Lexer::new(&input, "lexer_tests_offset")
+            .with_offset(10, 4)
+            .on_error(|e| streamed.push((e.line, e.start)));
+        l.run();
+        let collected = l
+            .errors
+            .iter()
+            .map(|e| (e.line, e.start))
+            .collect::<Vec<_>>();
+        drop(l);
+        assert_eq!(streamed, vec![(11, 0)]);
+        assert_eq!(streamed, collected);
+    }
+
+    #[test]
+    fn parser_errors_are_offset() {
+        let input = "VACUUM 5;".as_bytes().to_vec();
+        let mut l = lexer::Lexer::new(&input, "lexer_tests_offset").with_offset(10, 4);
+        let toks = l.run();
+        assert_eq!(l.errors.len(), 0);
+        let mut parser = Parser::new(toks, "lexer_tests_offset");
+        parser.parse();
+        assert_ne!(parser.errors.len(), 0);
+        assert!(parser.errors.iter().all(|e| e.line >= 10));
+        assert_eq!(parser.errors[0].start, 11);
+    }
+}
+
+#[cfg(test)]
+mod quoted_ident {
+    use crate::{
+        lexer,
+        types::{Type, rules::Rule},
+    };
+
+    /// returns the types of all tokens and the rules of all errors of input
+    fn lex(input: &str) -> (Vec<Type>, Vec<Rule>) {
+        let input = input.as_bytes().to_vec();
+        let mut l = lexer::Lexer::new(&input, "lexer_tests_quoted_ident");
+        let toks = l.run();
+        (
+            toks.into_iter().map(|t| t.ttype).collect(),
+            l.errors.into_iter().map(|e| e.rule).collect(),
+        )
+    }
+
+    #[test]
+    fn with_space() {
+        assert_eq!(
+            lex("\"my table\""),
+            (vec![Type::Ident("my table".into())], vec![Rule::Quirk])
+        );
+    }
+
+    #[test]
+    fn escaped_quote() {
+        assert_eq!(
+            lex("\"col\"\"name\""),
+            (vec![Type::Ident("col\"name".into())], vec![Rule::Quirk])
+        );
+    }
+
+    #[test]
+    fn keyword() {
+        assert_eq!(
+            lex("SELECT \"select\""),
+            (
+                vec![
+                    Type::Keyword(crate::types::Keyword::SELECT),
+                    Type::Ident("select".into())
+                ],
+                vec![Rule::Quirk]
+            )
+        );
+    }
+
+    #[test]
+    fn backtick() {
+        assert_eq!(
+            lex("`my_table`"),
+            (vec![Type::Ident("my_table".into())], vec![Rule::Quirk])
+        );
+        assert_eq!(
+            lex("`col`"),
+            (vec![Type::Ident("col".into())], vec![Rule::Quirk])
+        );
+    }
+
+    #[test]
+    fn backtick_escaped() {
+        assert_eq!(
+            lex("`a``b`"),
+            (vec![Type::Ident("a`b".into())], vec![Rule::Quirk])
+        );
+    }
+
+    #[test]
+    fn bracket() {
+        assert_eq!(
+            lex("[my table]"),
+            (vec![Type::Ident("my table".into())], vec![Rule::Quirk])
+        );
+        assert_eq!(
+            lex("[col]"),
+            (vec![Type::Ident("col".into())], vec![Rule::Quirk])
+        );
+    }
+
+    #[test]
+    fn bare_bracket() {
+        assert_eq!(lex("["), (vec![Type::BracketLeft], vec![]));
+        assert_eq!(
+            lex("[]"),
+            (vec![Type::BracketLeft, Type::BracketRight], vec![])
+        );
+        assert_eq!(
+            lex("[a\n]"),
+            (
+                vec![
+                    Type::BracketLeft,
+                    Type::Ident("a".into()),
+                    Type::BracketRight
+                ],
+                vec![]
+            )
+        );
+    }
+
+    #[test]
+    fn nested_bracket() {
+        assert_eq!(
+            lex("[[a]"),
+            (
+                vec![Type::BracketLeft, Type::Ident("a".into())],
+                vec![Rule::Quirk]
+            )
+        );
+    }
+
+    #[test]
+    fn bracket_position() {
+        let input = "a [b c] d".as_bytes().to_vec();
+        let toks = lexer::Lexer::new(&input, "lexer_tests_quoted_ident").run();
+        assert_eq!((toks[1].start, toks[1].end), (2, 6));
+        assert_eq!(toks[2].start, 8);
+    }
+
+    #[test]
+    fn position() {
+        let input = "a \"b\"\"c\" d".as_bytes().to_vec();
+        let toks = lexer::Lexer::new(&input, "lexer_tests_quoted_ident").run();
+        assert_eq!((toks[1].start, toks[1].end), (2, 7));
+        assert_eq!(toks[2].start, 9);
     }
 }
@@ -0,0 +1,54 @@
+//! sqleibniz lexes, parses and analyses sql, see [analyze] for analysing a source in one call or
+//! [Lexer] and [Parser] for the individual steps.
+//!
+//! ```
+//! let errors = sqleibniz::analyze(b"VACUUM 5;", "example.sql");
+//! assert_eq!(errors[0].rule, sqleibniz::types::rules::Rule::Syntax);
+//! ```
+#![allow(dead_code)]
+
+/// error does formatting and highlighting for errors
+pub mod error;
+/// highlight implements logic for highlighting tokens found in a string
+pub mod highlight;
+/// lev implements the levenshtein distance for all sql keywords, this is used to recommend a keyword based on a misspelled word or any
+/// unknown keyword at an arbitrary location in the source statement - mainly used at the start of a new statement
+pub mod lev;
+/// lexer converts the input into a stream of token for the parser
+pub mod lexer;
+/// lsp implements the language server protocol to provide diagnostics, suggestions and snippets for sql based on the sqleibniz tooling
+pub mod lsp;
+/// parser converts the token stream into an abstract syntax tree
+pub mod parser;
+/// style checks the formatting of the source text itself, such as the width of lines
+pub mod style;
+/// types holds all shared types between the above modules
+pub mod types;
+
+pub use error::Error;
+pub use lexer::Lexer;
+pub use parser::Parser;
+
+use types::ctx::Context;
+
+/// analyze lexes, parses and analyses source with the default configuration and returns all
+/// errors found, opt-in rules are not reported, name is used as the file name of the errors
+pub fn analyze(source: &[u8], name: &str) -> Vec<Error> {
+    let source = source.to_vec();
+    let mut lexer = Lexer::new(&source, name);
+    let toks = lexer.run();
+    let mut errors = std::mem::take(&mut lexer.errors);
+
+    if !toks.is_empty() {
+        let mut parser = Parser::new(toks, name);
+        let ast = parser.parse();
+        errors.append(&mut parser.errors);
+        errors.append(&mut parser::analyse::statements(
+            &ast,
+            &mut Context::new(name),
+        ));
+    }
+
+    errors.retain(|e| !e.rule.opt_in());
+    errors
+}
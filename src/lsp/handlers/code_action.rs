@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use lsp_server::{Connection, Message, RequestId, Response};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams, Position, Range, TextEdit,
+    Uri, WorkspaceEdit,
+};
+
+use crate::{
+    error::Error,
+    lsp::error::LspError,
+    types::{Keyword, Token, Type, rules::Rule},
+};
+
+/// replaces the contents of line from start to end with text
+fn edit(line: usize, start: usize, end: usize, text: &str) -> TextEdit {
+    TextEdit {
+        range: Range {
+            start: Position {
+                line: line as u32,
+                character: start as u32,
+            },
+            end: Position {
+                line: line as u32,
+                character: end as u32,
+            },
+        },
+        new_text: text.into(),
+    }
+}
+
+/// computes the quick fix for err, if there is one: inserting the missing semicolon of a
+/// [Rule::Semicolon] or replacing the identifier of a [Rule::UnknownKeyword] with the closest
+/// keyword
+fn quick_fix(uri: &Uri, tokens: &[Token], err: &Error) -> Option<CodeAction> {
+    let (title, edit) = match err.rule {
+        Rule::Semicolon => {
+            let improved = err.improved_line.as_ref()?;
+            (
+                "Insert missing semicolon".to_string(),
+                edit(err.line, improved.start, improved.start, improved.snippet),
+            )
+        }
+        Rule::UnknownKeyword => {
+            let name = tokens.iter().find_map(|t| match &t.ttype {
+                Type::Ident(name) if t.line == err.line && t.start == err.start => Some(name),
+                _ => None,
+            })?;
+            let suggestion = *Keyword::suggestions(name).first()?;
+            (
+                format!("Replace '{name}' with {suggestion}"),
+                edit(err.line, err.start, err.end, suggestion),
+            )
+        }
+        _ => return None,
+    };
+    Some(CodeAction {
+        title,
+        kind: Some(CodeActionKind::QUICKFIX),
+        diagnostics: Some(vec![err.clone().into()]),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(uri.clone(), vec![edit])])),
+            ..Default::default()
+        }),
+        is_preferred: Some(true),
+        ..Default::default()
+    })
+}
+
+/// returns the quick fixes for all errors on the lines of range
+pub fn code_actions(
+    uri: &Uri,
+    tokens: &[Token],
+    errors: &[Error],
+    range: Range,
+) -> Vec<CodeActionOrCommand> {
+    errors
+        .iter()
+        .filter(|e| (range.start.line as usize..=range.end.line as usize).contains(&e.line))
+        .filter_map(|e| quick_fix(uri, tokens, e))
+        .map(CodeActionOrCommand::CodeAction)
+        .collect()
+}
+
+pub fn handle(
+    connection: &Connection,
+    tokens: &[Token],
+    errors: &[Error],
+    id: RequestId,
+    params: CodeActionParams,
+) -> Result<(), LspError> {
+    eprintln!("got code action request #{id}");
+    let actions = code_actions(&params.text_document.uri, tokens, errors, params.range);
+    let result = serde_json::to_value(&actions).unwrap();
+    let resp = Response {
+        id,
+        result: Some(result),
+        error: None,
+    };
+    connection
+        .sender
+        .send(Message::Response(resp))
+        .map_err(|_| "failed to send code actions")?;
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use lsp_server::{Connection, Message, RequestId, Response};
+use lsp_types::{CompletionItem, CompletionItemKind, CompletionParams, Position};
+
+use crate::{
+    lsp::error::LspError,
+    types::{Keyword, Token, Type, ctx::Table, pragma},
+};
+
+/// what the token before the cursor allows to be completed
+enum Completing<'a> {
+    /// after PRAGMA or PRAGMA schema_name.
+    Pragma,
+    /// after table_name.
+    Columns(&'a str),
+    /// anything else
+    Any,
+}
+
+/// determines what to complete at position, the word the cursor is touching is ignored, since
+/// clients filter the completions by it themselves
+fn completing(tokens: &[Token], position: Position) -> Completing<'_> {
+    let (line, character) = (position.line as usize, position.character as usize);
+    let mut before: Vec<&Token> = tokens
+        .iter()
+        .take_while(|t| t.line < line || (t.line == line && t.start < character))
+        .collect();
+    if let Some(last) = before.last()
+        && matches!(last.ttype, Type::Ident(_) | Type::Keyword(_))
+        && last.line == line
+        && last.end >= character
+    {
+        before.pop();
+    }
+
+    match before.as_slice() {
+        [.., t] if t.ttype == Type::Keyword(Keyword::PRAGMA) => Completing::Pragma,
+        [.., p, s, d]
+            if p.ttype == Type::Keyword(Keyword::PRAGMA)
+                && matches!(s.ttype, Type::Ident(_))
+                && d.ttype == Type::Dot =>
+        {
+            Completing::Pragma
+        }
+        [
+            ..,
+            Token {
+                ttype: Type::Ident(table),
+                ..
+            },
+            d,
+        ] if d.ttype == Type::Dot => Completing::Columns(table),
+        _ => Completing::Any,
+    }
+}
+
+fn item(label: &str, kind: CompletionItemKind, detail: &str) -> CompletionItem {
+    CompletionItem {
+        label: label.to_string(),
+        kind: Some(kind),
+        detail: Some(detail.to_string()),
+        ..Default::default()
+    }
+}
+
+fn columns(table: &Table) -> impl Iterator<Item = CompletionItem> {
+    table.columns.iter().map(|c| {
+        item(
+            c,
+            CompletionItemKind::FIELD,
+            &format!("column of {}", table.name),
+        )
+    })
+}
+
+/// computes the completions at position: pragma names after PRAGMA, the columns of a table
+/// after its name and a dot, otherwise all keywords, tables and columns
+pub fn completions(tokens: &[Token], tables: &[Table], position: Position) -> Vec<CompletionItem> {
+    match completing(tokens, position) {
+        Completing::Pragma => pragma::PRAGMAS
+            .iter()
+            .map(|p| item(p, CompletionItemKind::PROPERTY, "pragma"))
+            .collect(),
+        Completing::Columns(name) => tables
+            .iter()
+            .filter(|t| t.name.eq_ignore_ascii_case(name))
+            .flat_map(columns)
+            .collect(),
+        Completing::Any => Keyword::all()
+            .iter()
+            .map(|k| item(k, CompletionItemKind::KEYWORD, "keyword"))
+            .chain(
+                tables
+                    .iter()
+                    .map(|t| item(&t.name, CompletionItemKind::MODULE, "table")),
+            )
+            .chain(tables.iter().flat_map(columns))
+            .collect(),
+    }
+}
+
+pub fn handle(
+    connection: &Connection,
+    tokens: &[Token],
+    tables: &[Table],
+    id: RequestId,
+    params: CompletionParams,
+) -> Result<(), LspError> {
+    eprintln!("got completion request #{id}");
+    let items = completions(tokens, tables, params.text_document_position.position);
+    let result = serde_json::to_value(&items).unwrap();
+    let resp = Response {
+        id,
+        result: Some(result),
+        error: None,
+    };
+    connection
+        .sender
+        .send(Message::Response(resp))
+        .map_err(|_| "failed to send completions")?;
+    Ok(())
+}
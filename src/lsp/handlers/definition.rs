@@ -0,0 +1,60 @@
+use lsp_server::{Connection, Message, RequestId, Response};
+use lsp_types::{GotoDefinitionParams, Location, Position, Range};
+
+use crate::{
+    lsp::{error::LspError, handlers::symbol_table::SymbolTable},
+    types::{Token, Type},
+};
+
+/// returns the token of the definition of the table name the cursor at position is on
+pub fn definition<'a>(
+    tokens: &[Token],
+    symbols: &'a SymbolTable,
+    position: Position,
+) -> Option<&'a Token> {
+    let (line, character) = (position.line as usize, position.character as usize);
+    let name = tokens.iter().find_map(|t| match &t.ttype {
+        Type::Ident(name) if t.line == line && t.start <= character && character <= t.end => {
+            Some(name)
+        }
+        _ => None,
+    })?;
+    symbols.lookup(name)
+}
+
+pub fn handle(
+    connection: &Connection,
+    tokens: &[Token],
+    symbols: &SymbolTable,
+    id: RequestId,
+    params: GotoDefinitionParams,
+) -> Result<(), LspError> {
+    eprintln!("got definition request #{id}");
+    let position = params.text_document_position_params;
+    let location = definition(tokens, symbols, position.position).map(|t| {
+        lsp_types::GotoDefinitionResponse::Scalar(Location {
+            uri: position.text_document.uri,
+            range: Range {
+                start: Position {
+                    line: t.line as u32,
+                    character: t.start as u32,
+                },
+                end: Position {
+                    line: t.line as u32,
+                    character: t.end as u32,
+                },
+            },
+        })
+    });
+    let result = serde_json::to_value(&location).unwrap();
+    let resp = Response {
+        id,
+        result: Some(result),
+        error: None,
+    };
+    connection
+        .sender
+        .send(Message::Response(resp))
+        .map_err(|_| "failed to send definition")?;
+    Ok(())
+}
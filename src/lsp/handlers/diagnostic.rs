@@ -1,7 +1,7 @@
 use lsp_server::{Connection, Message, RequestId, Response};
 use lsp_types::{Diagnostic, DiagnosticSeverity, DocumentDiagnosticParams, Position, Range};
 
-use crate::{error::Error, lsp::error::LspError};
+use crate::{error::Error, lsp::error::LspError, types::rules::Severity};
 
 impl From<Error> for Diagnostic {
     fn from(value: Error) -> Self {
@@ -16,7 +16,12 @@ impl From<Error> for Diagnostic {
                     character: value.end as u32,
                 },
             ),
-            severity: Some(DiagnosticSeverity::ERROR),
+            severity: Some(match value.severity {
+                Severity::Error => DiagnosticSeverity::ERROR,
+                Severity::Warning => DiagnosticSeverity::WARNING,
+                Severity::Info => DiagnosticSeverity::INFORMATION,
+                Severity::Hint => DiagnosticSeverity::HINT,
+            }),
             code: Some(lsp_types::NumberOrString::String(
                 value.rule.name().to_string(),
             )),
@@ -0,0 +1,73 @@
+use lsp_server::{Connection, Message, RequestId, Response};
+use lsp_types::{DocumentSymbol, DocumentSymbolParams, Position, Range, SymbolKind};
+
+use crate::{
+    lsp::{error::LspError, handlers::symbol_table::definitions},
+    types::{Keyword, Token, Type},
+};
+
+fn position(t: &Token, character: usize) -> Position {
+    Position {
+        line: t.line as u32,
+        character: character as u32,
+    }
+}
+
+/// returns a symbol for each table, index, view and trigger defined in tokens. The range of a
+/// symbol spans from CREATE to the end of the name, the selection range only the name. Symbols
+/// are computed from the tokens, since CREATE INDEX is not yet parsed into a node.
+pub fn symbols(tokens: &[Token]) -> Vec<DocumentSymbol> {
+    definitions(tokens)
+        .into_iter()
+        .filter_map(|d| {
+            let Type::Ident(name) = &d.name.ttype else {
+                return None;
+            };
+            let (kind, detail) = match d.kind {
+                Keyword::TABLE => (SymbolKind::STRUCT, "table"),
+                Keyword::INDEX => (SymbolKind::MODULE, "index"),
+                Keyword::VIEW => (SymbolKind::INTERFACE, "view"),
+                Keyword::TRIGGER => (SymbolKind::EVENT, "trigger"),
+                _ => return None,
+            };
+            #[allow(deprecated)]
+            Some(DocumentSymbol {
+                name: name.clone(),
+                detail: Some(detail.into()),
+                kind,
+                tags: None,
+                deprecated: None,
+                range: Range {
+                    start: position(d.create, d.create.start),
+                    end: position(d.name, d.name.end),
+                },
+                selection_range: Range {
+                    start: position(d.name, d.name.start),
+                    end: position(d.name, d.name.end),
+                },
+                children: None,
+            })
+        })
+        .collect()
+}
+
+pub fn handle(
+    connection: &Connection,
+    tokens: &[Token],
+    id: RequestId,
+    _params: DocumentSymbolParams,
+) -> Result<(), LspError> {
+    eprintln!("got document symbol request #{id}");
+    let response = lsp_types::DocumentSymbolResponse::Nested(symbols(tokens));
+    let result = serde_json::to_value(&response).unwrap();
+    let resp = Response {
+        id,
+        result: Some(result),
+        error: None,
+    };
+    connection
+        .sender
+        .send(Message::Response(resp))
+        .map_err(|_| "failed to send document symbols")?;
+    Ok(())
+}
@@ -1,7 +1,24 @@
 use lsp_server::{Connection, Message, RequestId, Response};
 use lsp_types::{HoverParams, Position};
 
-use crate::{lsp::error::LspError, parser::nodes::Node};
+use crate::{
+    lsp::error::LspError,
+    parser::{nodes::Node, visit::walk},
+};
+
+/// returns the innermost node starting on line before or at character
+pub fn node_at(ast: &[Box<dyn Node>], line: usize, character: usize) -> Option<&dyn Node> {
+    let mut found = None;
+    for node in ast {
+        walk(node.as_ref(), &mut |n| {
+            let tok = n.token();
+            if tok.line == line && tok.start <= character {
+                found = Some(n);
+            }
+        });
+    }
+    found
+}
 
 pub fn handle(
     connection: &Connection,
@@ -11,14 +28,7 @@ pub fn handle(
 ) -> Result<(), LspError> {
     eprintln!("got hover request #{id}");
     let Position { line, character } = params.text_document_position_params.position;
-    let text = match ast
-        .iter()
-        .filter(|n| {
-            let tok = n.token();
-            tok.line == line as usize && tok.start <= character as usize
-        })
-        .next_back()
-    {
+    let text = match node_at(ast, line as usize, character as usize) {
         Some(node) => {
             format!("# {}\n\n{}", node.name(), node.doc(),)
         }
@@ -1,2 +1,8 @@
+pub mod code_action;
+pub mod completion;
+pub mod definition;
 pub mod diagnostic;
+pub mod document_symbol;
 pub mod hover;
+pub mod signature_help;
+pub mod symbol_table;
@@ -0,0 +1,110 @@
+use lsp_server::{Connection, Message, RequestId, Response};
+use lsp_types::{
+    Documentation, ParameterInformation, ParameterLabel, Position, SignatureHelp,
+    SignatureHelpParams, SignatureInformation,
+};
+
+use crate::{
+    lsp::error::LspError,
+    types::{
+        Token, Type,
+        functions::{self, Function},
+    },
+};
+
+/// finds the innermost call of a built-in function enclosing position and the index of the
+/// argument position is at
+fn call_at(tokens: &[Token], position: Position) -> Option<(&'static Function, usize)> {
+    let (line, character) = (position.line as usize, position.character as usize);
+    // indexes of the unclosed Type::BraceLeft before position and the amount of commas in each
+    let mut braces: Vec<(usize, usize)> = vec![];
+    for (i, t) in tokens
+        .iter()
+        .enumerate()
+        .take_while(|(_, t)| t.line < line || (t.line == line && t.start < character))
+    {
+        match t.ttype {
+            Type::BraceLeft => braces.push((i, 0)),
+            Type::BraceRight => {
+                braces.pop();
+            }
+            Type::Comma => {
+                if let Some((_, commas)) = braces.last_mut() {
+                    *commas += 1;
+                }
+            }
+            Type::Semicolon => braces.clear(),
+            _ => {}
+        }
+    }
+
+    // parenthesized expressions inside of arguments are skipped, their enclosing call is used
+    braces.iter().rev().find_map(|&(i, commas)| {
+        let name: &str = match &tokens.get(i.checked_sub(1)?)?.ttype {
+            Type::Ident(name) => name,
+            Type::Keyword(keyword) => (*keyword).into(),
+            _ => return None,
+        };
+        Some((functions::lookup(name)?, commas))
+    })
+}
+
+/// computes the signature help for the built-in function call enclosing position
+pub fn signature_help(tokens: &[Token], position: Position) -> Option<SignatureHelp> {
+    let (function, argument) = call_at(tokens, position)?;
+
+    // parameter labels are offsets into the signature label, since parameter names such as X
+    // may occur multiple times in it
+    let mut label = format!("{}(", function.name);
+    let mut parameters = vec![];
+    for (i, param) in function.params.iter().enumerate() {
+        if i > 0 {
+            label.push_str(", ");
+        }
+        let start = label.len() as u32;
+        label.push_str(param.name);
+        parameters.push(ParameterInformation {
+            label: ParameterLabel::LabelOffsets([start, label.len() as u32]),
+            documentation: Some(Documentation::String(param.doc.into())),
+        });
+    }
+    if function.variadic {
+        label.push_str(", ...");
+    }
+    label.push(')');
+
+    // arguments past the last parameter of variadic functions map to the last parameter
+    let active_parameter = argument.min(function.params.len().saturating_sub(1)) as u32;
+
+    Some(SignatureHelp {
+        signatures: vec![SignatureInformation {
+            label,
+            documentation: Some(Documentation::String(function.doc.into())),
+            parameters: Some(parameters),
+            active_parameter: Some(active_parameter),
+        }],
+        active_signature: Some(0),
+        active_parameter: Some(active_parameter),
+    })
+}
+
+pub fn handle(
+    connection: &Connection,
+    tokens: &[Token],
+    id: RequestId,
+    params: SignatureHelpParams,
+) -> Result<(), LspError> {
+    eprintln!("got signature help request #{id}");
+    let help = signature_help(tokens, params.text_document_position_params.position);
+    let result = serde_json::to_value(&help).unwrap();
+    let resp = Response {
+        id,
+        result: Some(result),
+        error: None,
+    };
+    connection
+        .sender
+        .send(Message::Response(resp))
+        .map_err(|_| "failed to send signature help")?;
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use crate::types::{Keyword, Token, Type};
+
+/// SymbolTable maps the names of tables, views and virtual tables to the token of their name in
+/// the CREATE statement defining them. It is built from tokens instead of the ast, since the
+/// nodes only hold the names, not their tokens, and CREATE INDEX is not yet parsed into a node.
+#[derive(Default)]
+pub struct SymbolTable {
+    /// names are lowercased, since sqlite compares them case insensitively
+    definitions: HashMap<String, Token>,
+}
+
+/// a CREATE statement defining a named schema object
+pub struct Definition<'a> {
+    /// either TABLE, INDEX, VIEW or TRIGGER, virtual tables are TABLE
+    pub kind: Keyword,
+    /// the CREATE token starting the statement
+    pub create: &'a Token,
+    /// the token of the name of the defined object
+    pub name: &'a Token,
+}
+
+/// finds all CREATE [TEMP] [UNIQUE] [VIRTUAL] TABLE|INDEX|VIEW|TRIGGER [IF NOT EXISTS]
+/// [schema_name.]name in tokens, in source order
+pub fn definitions(tokens: &[Token]) -> Vec<Definition<'_>> {
+    let is = |i: usize, keyword: Keyword| {
+        tokens
+            .get(i)
+            .is_some_and(|t| t.ttype == Type::Keyword(keyword))
+    };
+    let mut definitions = vec![];
+    for (i, create) in tokens.iter().enumerate() {
+        if create.ttype != Type::Keyword(Keyword::CREATE) {
+            continue;
+        }
+        let mut j = i + 1;
+        if is(j, Keyword::TEMP) || is(j, Keyword::TEMPORARY) {
+            j += 1;
+        }
+        if is(j, Keyword::UNIQUE) || is(j, Keyword::VIRTUAL) {
+            j += 1;
+        }
+        let kind = match tokens.get(j).map(|t| &t.ttype) {
+            Some(Type::Keyword(
+                kind @ (Keyword::TABLE | Keyword::INDEX | Keyword::VIEW | Keyword::TRIGGER),
+            )) => *kind,
+            _ => continue,
+        };
+        j += 1;
+        if is(j, Keyword::IF) {
+            // IF NOT EXISTS
+            j += 3;
+        }
+        // skip schema_name.
+        if tokens.get(j + 1).is_some_and(|t| t.ttype == Type::Dot) {
+            j += 2;
+        }
+        if let Some(
+            name @ Token {
+                ttype: Type::Ident(_),
+                ..
+            },
+        ) = tokens.get(j)
+        {
+            definitions.push(Definition { kind, create, name });
+        }
+    }
+    definitions
+}
+
+impl SymbolTable {
+    /// indexes all tables, views and virtual tables defined in tokens, see [definitions], the
+    /// first definition of a name wins
+    pub fn build(tokens: &[Token]) -> Self {
+        let mut table = SymbolTable::default();
+        for definition in definitions(tokens) {
+            if let (Keyword::TABLE | Keyword::VIEW, Type::Ident(name)) =
+                (definition.kind, &definition.name.ttype)
+            {
+                table
+                    .definitions
+                    .entry(name.to_lowercase())
+                    .or_insert_with(|| definition.name.clone());
+            }
+        }
+        table
+    }
+
+    /// returns the token of the name of the definition of name
+    pub fn lookup(&self, name: &str) -> Option<&Token> {
+        self.definitions.get(&name.to_lowercase())
+    }
+}
@@ -1,18 +1,27 @@
 mod error;
 mod handlers;
+mod tests;
 
 use error::LspError;
+use handlers::symbol_table::SymbolTable;
 use lsp_server::{Connection, ExtractError, Message, Notification, Request, RequestId};
 use lsp_types::{
     DiagnosticOptions, InitializeParams, SaveOptions, ServerCapabilities, TextDocumentSyncKind,
     TextDocumentSyncOptions,
     notification::{DidChangeTextDocument, DidOpenTextDocument},
-    request::{DocumentDiagnosticRequest, HoverRequest},
+    request::{
+        CodeActionRequest, Completion, DocumentDiagnosticRequest, DocumentSymbolRequest,
+        GotoDefinition, HoverRequest, SignatureHelpRequest,
+    },
 };
 
 use crate::{
     lexer::Lexer,
-    parser::{Parser, nodes::Node},
+    parser::{Parser, analyse, nodes::Node},
+    types::{
+        Token,
+        ctx::{Context, Table},
+    },
 };
 
 macro_rules! lsp_log {
@@ -26,6 +35,18 @@ pub fn start() -> Result<(), LspError> {
     let (connection, threads) = Connection::stdio();
     let capabilities = serde_json::to_value(&ServerCapabilities {
         hover_provider: Some(lsp_types::HoverProviderCapability::Simple(true)),
+        definition_provider: Some(lsp_types::OneOf::Left(true)),
+        document_symbol_provider: Some(lsp_types::OneOf::Left(true)),
+        code_action_provider: Some(lsp_types::CodeActionProviderCapability::Simple(true)),
+        completion_provider: Some(lsp_types::CompletionOptions {
+            trigger_characters: Some(vec![".".into()]),
+            ..Default::default()
+        }),
+        signature_help_provider: Some(lsp_types::SignatureHelpOptions {
+            trigger_characters: Some(vec!["(".into(), ",".into()]),
+            retrigger_characters: None,
+            work_done_progress_options: Default::default(),
+        }),
         diagnostic_provider: Some(lsp_types::DiagnosticServerCapabilities::Options(
             DiagnosticOptions {
                 inter_file_dependencies: false,
@@ -75,7 +96,11 @@ fn event_loop(connection: Connection, params: serde_json::Value) -> Result<(), L
     let _params: InitializeParams = serde_json::from_value(params).unwrap();
     lsp_log!("starting event loop");
     let mut ast: Vec<Box<dyn Node>> = vec![];
+    let mut tokens: Vec<Token> = vec![];
     let mut errors: Vec<super::error::Error> = vec![];
+    // tables and columns known after analysing the document, used for completions
+    let mut tables: Vec<Table> = vec![];
+    let mut symbols = SymbolTable::default();
     for msg in &connection.receiver {
         eprintln!("got msg: {msg:?}");
         match msg {
@@ -98,6 +123,89 @@ fn event_loop(connection: Connection, params: serde_json::Value) -> Result<(), L
                             Err(err) => panic!("{err:?}"),
                         };
                     }
+                    "textDocument/definition" => {
+                        match cast::<GotoDefinition>(req) {
+                            Ok((id, params)) => {
+                                if let Err(e) = handlers::definition::handle(
+                                    &connection,
+                                    &tokens,
+                                    &symbols,
+                                    id,
+                                    params,
+                                ) {
+                                    eprintln!("[sqleibniz]: err: {}", e);
+                                }
+                                continue;
+                            }
+                            Err(err) => panic!("{err:?}"),
+                        };
+                    }
+                    "textDocument/codeAction" => {
+                        match cast::<CodeActionRequest>(req) {
+                            Ok((id, params)) => {
+                                if let Err(e) = handlers::code_action::handle(
+                                    &connection,
+                                    &tokens,
+                                    &errors,
+                                    id,
+                                    params,
+                                ) {
+                                    eprintln!("[sqleibniz]: err: {}", e);
+                                }
+                                continue;
+                            }
+                            Err(err) => panic!("{err:?}"),
+                        };
+                    }
+                    "textDocument/documentSymbol" => {
+                        match cast::<DocumentSymbolRequest>(req) {
+                            Ok((id, params)) => {
+                                if let Err(e) = handlers::document_symbol::handle(
+                                    &connection,
+                                    &tokens,
+                                    id,
+                                    params,
+                                ) {
+                                    eprintln!("[sqleibniz]: err: {}", e);
+                                }
+                                continue;
+                            }
+                            Err(err) => panic!("{err:?}"),
+                        };
+                    }
+                    "textDocument/completion" => {
+                        match cast::<Completion>(req) {
+                            Ok((id, params)) => {
+                                if let Err(e) = handlers::completion::handle(
+                                    &connection,
+                                    &tokens,
+                                    &tables,
+                                    id,
+                                    params,
+                                ) {
+                                    eprintln!("[sqleibniz]: err: {}", e);
+                                }
+                                continue;
+                            }
+                            Err(err) => panic!("{err:?}"),
+                        };
+                    }
+                    "textDocument/signatureHelp" => {
+                        match cast::<SignatureHelpRequest>(req) {
+                            Ok((id, params)) => {
+                                if let Err(e) = handlers::signature_help::handle(
+                                    &connection,
+                                    &tokens,
+                                    id,
+                                    params,
+                                ) {
+                                    eprintln!("[sqleibniz]: err: {}", e);
+                                }
+                                continue;
+                            }
+                            Err(err) => panic!("{err:?}"),
+                        };
+                    }
                     "textDocument/diagnostic" => {
                         match cast::<DocumentDiagnosticRequest>(req) {
                             Ok((id, params)) => {
@@ -129,11 +237,17 @@ fn event_loop(connection: Connection, params: serde_json::Value) -> Result<(), L
                             let formatted_path =
                                 params.text_document.uri.to_string().replace("file://", "");
                             let mut l = Lexer::new(text, &formatted_path);
-                            let tokens = l.run();
+                            tokens = l.run();
                             errors = l.errors;
-                            let mut p = Parser::new(tokens, &formatted_path);
+                            let mut p = Parser::new(tokens.clone(), &formatted_path);
                             ast = p.parse();
                             errors.append(&mut p.errors);
+                            symbols = SymbolTable::build(&tokens);
+                            let mut ctx = Context::new(&formatted_path);
+                            errors.append(&mut analyse::statements(&ast, &mut ctx));
+                            tables = ctx.tables;
+                            // the language server has no configuration, thus opt-in rules stay disabled
+                            errors.retain(|e| !e.rule.opt_in());
                         }
                         Err(err) => panic!("failed to cast notification: {err:?}"),
                     };
@@ -145,11 +259,17 @@ fn event_loop(connection: Connection, params: serde_json::Value) -> Result<(), L
                             let formatted_path =
                                 params.text_document.uri.to_string().replace("file://", "");
                             let mut l = Lexer::new(text, &formatted_path);
-                            let tokens = l.run();
+                            tokens = l.run();
                             errors = l.errors;
-                            let mut p = Parser::new(tokens, &formatted_path);
+                            let mut p = Parser::new(tokens.clone(), &formatted_path);
                             ast = p.parse();
                             errors.append(&mut p.errors);
+                            symbols = SymbolTable::build(&tokens);
+                            let mut ctx = Context::new(&formatted_path);
+                            errors.append(&mut analyse::statements(&ast, &mut ctx));
+                            tables = ctx.tables;
+                            // the language server has no configuration, thus opt-in rules stay disabled
+                            errors.retain(|e| !e.rule.opt_in());
                         }
                         Err(err) => panic!("failed to cast notification: {err:?}"),
                     };
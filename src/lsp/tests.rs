@@ -0,0 +1,465 @@
+#[cfg(test)]
+mod signature_help {
+    use lsp_types::{Documentation, ParameterLabel, Position};
+
+    use crate::{lexer, lsp::handlers::signature_help::signature_help};
+
+    /// computes the signature help at the position of `|` in input
+    fn help_at(input: &str) -> Option<lsp_types::SignatureHelp> {
+        let (line, column) = input
+            .lines()
+            .enumerate()
+            .find_map(|(i, l)| l.find('|').map(|c| (i, c)))
+            .expect("input has no cursor");
+        let source = input.replacen('|', "", 1).into_bytes();
+        let tokens = lexer::Lexer::new(&source, "lsp_test_signature_help").run();
+        signature_help(
+            &tokens,
+            Position {
+                line: line as u32,
+                character: column as u32,
+            },
+        )
+    }
+
+    #[test]
+    fn substr() {
+        let help = help_at("SELECT substr(|").expect("no signature help");
+        let signature = &help.signatures[0];
+        assert_eq!(signature.label, "substr(X, Y, Z)");
+        assert_eq!(help.active_parameter, Some(0));
+        let params = signature.parameters.as_ref().unwrap();
+        assert_eq!(params.len(), 3);
+        assert_eq!(params[0].label, ParameterLabel::LabelOffsets([7, 8]));
+        assert_eq!(
+            params[1].documentation,
+            Some(Documentation::String("start, 1 based".into()))
+        );
+    }
+
+    #[test]
+    fn active_parameter_follows_commas() {
+        let help = help_at("SELECT substr('abc', 1, |").unwrap();
+        assert_eq!(help.active_parameter, Some(2));
+    }
+
+    #[test]
+    fn variadic_clamps_to_last_parameter() {
+        let help = help_at("SELECT coalesce(a, b, c, |").unwrap();
+        assert_eq!(help.signatures[0].label, "coalesce(X, Y, ...)");
+        assert_eq!(help.active_parameter, Some(1));
+    }
+
+    #[test]
+    fn nested_calls_use_innermost() {
+        let help = help_at("SELECT length(upper(|)) FROM t;").unwrap();
+        assert_eq!(help.signatures[0].label, "upper(X)");
+    }
+
+    #[test]
+    fn grouping_parens_use_enclosing_call() {
+        let help = help_at("SELECT round((1 + 2) * 3, |").unwrap();
+        assert_eq!(help.signatures[0].label, "round(X, Y)");
+        assert_eq!(help.active_parameter, Some(1));
+    }
+
+    #[test]
+    fn keyword_named_function() {
+        let help = help_at("SELECT replace('a', |").unwrap();
+        assert_eq!(help.signatures[0].label, "replace(X, Y, Z)");
+    }
+
+    #[test]
+    fn outside_of_call() {
+        assert_eq!(help_at("SELECT substr('abc', 1)|;"), None);
+        assert_eq!(help_at("SELECT unknown_function(|"), None);
+    }
+}
+
+#[cfg(test)]
+mod hover {
+    use crate::{lexer, lsp::handlers::hover::node_at, parser::Parser};
+
+    /// returns the name of the node hovered at the position of `|` in input
+    fn hover_at(input: &str) -> Option<String> {
+        let column = input.find('|').expect("input has no cursor");
+        let source = input.replacen('|', "", 1).into_bytes();
+        let tokens = lexer::Lexer::new(&source, "lsp_test_hover").run();
+        let ast = Parser::new(tokens, "lsp_test_hover").parse();
+        node_at(&ast, 0, column).map(|n| n.name().to_string())
+    }
+
+    #[test]
+    fn statement() {
+        assert_eq!(hover_at("|VACUUM;"), Some("Vacuum".into()));
+    }
+
+    #[test]
+    fn innermost_node() {
+        assert_eq!(
+            hover_at("SELECT |lower(name) FROM t;"),
+            Some("FunctionCall".into())
+        );
+    }
+
+    #[test]
+    fn returning_expression() {
+        assert_eq!(
+            hover_at("INSERT INTO t (a) VALUES (1) RETURNING |lower(a);"),
+            Some("FunctionCall".into())
+        );
+        assert_eq!(
+            hover_at("DELETE FROM t RETURNING |lower(a);"),
+            Some("FunctionCall".into())
+        );
+    }
+}
+
+#[cfg(test)]
+mod completion {
+    use lsp_server::{Connection, Message, RequestId};
+    use lsp_types::{
+        CompletionItem, CompletionItemKind, CompletionParams, Position, TextDocumentIdentifier,
+        TextDocumentPositionParams, Uri,
+    };
+
+    use crate::{
+        lexer,
+        lsp::handlers::completion::{completions, handle},
+        parser::{Parser, analyse},
+        types::{
+            Token,
+            ctx::{Context, Table},
+        },
+    };
+
+    /// lexes, parses and analyses input without `|`, returns its tokens, its known tables and
+    /// the position of `|`
+    fn document(input: &str) -> (Vec<Token>, Vec<Table>, Position) {
+        let (line, column) = input
+            .lines()
+            .enumerate()
+            .find_map(|(i, l)| l.find('|').map(|c| (i, c)))
+            .expect("input has no cursor");
+        let source = input.replacen('|', "", 1).into_bytes();
+        let tokens = lexer::Lexer::new(&source, "lsp_test_completion").run();
+        let ast = Parser::new(tokens.clone(), "lsp_test_completion").parse();
+        let mut ctx = Context::new("lsp_test_completion");
+        analyse::statements(&ast, &mut ctx);
+        let position = Position {
+            line: line as u32,
+            character: column as u32,
+        };
+        (tokens, ctx.tables, position)
+    }
+
+    /// returns the label and kind of all completions at the position of `|` in input
+    fn complete_at(input: &str) -> Vec<(String, CompletionItemKind)> {
+        let (tokens, tables, position) = document(input);
+        completions(&tokens, &tables, position)
+            .into_iter()
+            .map(|i| (i.label, i.kind.unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn keywords() {
+        let items = complete_at("SEL|");
+        assert!(items.contains(&("SELECT".into(), CompletionItemKind::KEYWORD)));
+        assert!(items.iter().all(|(_, k)| *k == CompletionItemKind::KEYWORD));
+    }
+
+    #[test]
+    fn pragmas() {
+        let items = complete_at("PRAGMA fore|");
+        assert!(items.contains(&("foreign_keys".into(), CompletionItemKind::PROPERTY)));
+        assert!(
+            items
+                .iter()
+                .all(|(_, k)| *k == CompletionItemKind::PROPERTY)
+        );
+        assert_eq!(complete_at("PRAGMA main.|"), complete_at("PRAGMA |"));
+    }
+
+    #[test]
+    fn tables_and_columns() {
+        let items = complete_at(
+            "CREATE VIEW v(a, b) AS SELECT 1, 2;\nALTER TABLE t ADD COLUMN c TEXT;\nSELECT |",
+        );
+        assert!(items.contains(&("v".into(), CompletionItemKind::MODULE)));
+        assert!(items.contains(&("t".into(), CompletionItemKind::MODULE)));
+        assert!(items.contains(&("b".into(), CompletionItemKind::FIELD)));
+        assert!(items.contains(&("c".into(), CompletionItemKind::FIELD)));
+    }
+
+    #[test]
+    fn tables_and_columns_of_create_table() {
+        let items = complete_at(
+            "CREATE TABLE users (id INT, name TEXT NOT NULL, PRIMARY KEY (id));\nSELECT |",
+        );
+        assert!(items.contains(&("users".into(), CompletionItemKind::MODULE)));
+        assert!(items.contains(&("id".into(), CompletionItemKind::FIELD)));
+        assert!(items.contains(&("name".into(), CompletionItemKind::FIELD)));
+        assert_eq!(
+            complete_at(
+                "CREATE TABLE main.users (id INT, name TEXT);\nSELECT * FROM users WHERE users.|"
+            ),
+            vec![
+                ("id".into(), CompletionItemKind::FIELD),
+                ("name".into(), CompletionItemKind::FIELD)
+            ]
+        );
+    }
+
+    #[test]
+    fn columns_of_table() {
+        assert_eq!(
+            complete_at(
+                "CREATE VIEW v(a, b) AS SELECT 1, 2;\nALTER TABLE t ADD COLUMN c TEXT;\nSELECT V.|"
+            ),
+            vec![
+                ("a".into(), CompletionItemKind::FIELD),
+                ("b".into(), CompletionItemKind::FIELD)
+            ]
+        );
+    }
+
+    #[test]
+    fn responds_via_connection() {
+        let (server, client) = Connection::memory();
+        let (tokens, tables, position) = document("PRAGMA |");
+        let params = CompletionParams {
+            text_document_position: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier {
+                    uri: "file:///lsp_test_completion.sql".parse::<Uri>().unwrap(),
+                },
+                position,
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+            context: None,
+        };
+        handle(&server, &tokens, &tables, RequestId::from(1), params).unwrap();
+        let Message::Response(resp) = client.receiver.recv().unwrap() else {
+            panic!("expected a response");
+        };
+        assert_eq!(resp.id, RequestId::from(1));
+        let items: Vec<CompletionItem> = serde_json::from_value(resp.result.unwrap()).unwrap();
+        assert!(items.iter().any(|i| i.label == "journal_mode"));
+    }
+}
+
+#[cfg(test)]
+mod definition {
+    use lsp_types::Position;
+
+    use crate::{
+        lexer,
+        lsp::handlers::{definition::definition, symbol_table::SymbolTable},
+    };
+
+    /// returns the (line, start) of the definition of the table name at `|` in input
+    fn definition_at(input: &str) -> Option<(usize, usize)> {
+        let (line, column) = input
+            .lines()
+            .enumerate()
+            .find_map(|(i, l)| l.find('|').map(|c| (i, c)))
+            .expect("input has no cursor");
+        let source = input.replacen('|', "", 1).into_bytes();
+        let tokens = lexer::Lexer::new(&source, "lsp_test_definition").run();
+        let symbols = SymbolTable::build(&tokens);
+        definition(
+            &tokens,
+            &symbols,
+            Position {
+                line: line as u32,
+                character: column as u32,
+            },
+        )
+        .map(|t| (t.line, t.start))
+    }
+
+    #[test]
+    fn create_table() {
+        assert_eq!(
+            definition_at("CREATE TABLE users (id INTEGER);\nSELECT * FROM us|ers;"),
+            Some((0, 13))
+        );
+    }
+
+    #[test]
+    fn case_insensitive_with_schema_and_if_not_exists() {
+        assert_eq!(
+            definition_at(
+                "CREATE TEMP TABLE IF NOT EXISTS main.users (id INTEGER);\nDELETE FROM |USERS;"
+            ),
+            Some((0, 37))
+        );
+    }
+
+    #[test]
+    fn view_and_virtual_table() {
+        assert_eq!(
+            definition_at("CREATE VIEW v AS SELECT 1;\nSELECT * FROM |v;"),
+            Some((0, 12))
+        );
+        assert_eq!(
+            definition_at("CREATE VIRTUAL TABLE posts USING fts5(body);\nSELECT * FROM |posts;"),
+            Some((0, 21))
+        );
+    }
+
+    #[test]
+    fn first_definition_wins() {
+        assert_eq!(
+            definition_at(
+                "CREATE VIEW v AS SELECT 1;\nCREATE VIEW v AS SELECT 2;\nSELECT * FROM |v;"
+            ),
+            Some((0, 12))
+        );
+    }
+
+    #[test]
+    fn not_found() {
+        assert_eq!(definition_at("SELECT * FROM |users;"), None);
+        assert_eq!(
+            definition_at("CREATE VIEW v AS SELECT 1;\n|SELECT * FROM v;"),
+            None
+        );
+    }
+}
+
+#[cfg(test)]
+mod document_symbol {
+    use lsp_types::SymbolKind;
+
+    use crate::{lexer, lsp::handlers::document_symbol::symbols};
+
+    /// returns the (name, kind, selection line, selection start) of the symbols of input
+    fn symbols_of(input: &str) -> Vec<(String, SymbolKind, u32, u32)> {
+        let source = input.as_bytes().to_vec();
+        let tokens = lexer::Lexer::new(&source, "lsp_test_document_symbol").run();
+        symbols(&tokens)
+            .into_iter()
+            .map(|s| {
+                (
+                    s.name,
+                    s.kind,
+                    s.selection_range.start.line,
+                    s.selection_range.start.character,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn two_tables() {
+        assert_eq!(
+            symbols_of("CREATE TABLE users (id INTEGER);\nCREATE TABLE posts (id INTEGER);"),
+            vec![
+                ("users".into(), SymbolKind::STRUCT, 0, 13),
+                ("posts".into(), SymbolKind::STRUCT, 1, 13),
+            ]
+        );
+    }
+
+    #[test]
+    fn kinds() {
+        assert_eq!(
+            symbols_of(
+                "CREATE UNIQUE INDEX IF NOT EXISTS idx ON t (a);
+CREATE TEMP VIEW main.v AS SELECT 1;
+CREATE TRIGGER trig AFTER DELETE ON t BEGIN SELECT 1; END;
+CREATE VIRTUAL TABLE posts USING fts5(body);"
+            ),
+            vec![
+                ("idx".into(), SymbolKind::MODULE, 0, 34),
+                ("v".into(), SymbolKind::INTERFACE, 1, 22),
+                ("trig".into(), SymbolKind::EVENT, 2, 15),
+                ("posts".into(), SymbolKind::STRUCT, 3, 21),
+            ]
+        );
+    }
+
+    #[test]
+    fn range_spans_from_create_to_name() {
+        let source = b"VACUUM;\nCREATE VIEW v AS SELECT 1;".to_vec();
+        let tokens = lexer::Lexer::new(&source, "lsp_test_document_symbol").run();
+        let symbol = symbols(&tokens).remove(0);
+        assert_eq!(
+            (symbol.range.start.line, symbol.range.start.character),
+            (1, 0)
+        );
+        assert_eq!((symbol.range.end.line, symbol.range.end.character), (1, 13));
+    }
+}
+
+#[cfg(test)]
+mod code_action {
+    use lsp_types::{CodeActionOrCommand, Position, Range, TextEdit, Uri};
+
+    use crate::{lexer, lsp::handlers::code_action::code_actions, parser::Parser};
+
+    /// returns the title and edits of the code actions for all errors in input
+    fn actions(input: &str) -> Vec<(String, Vec<TextEdit>)> {
+        let source = input.as_bytes().to_vec();
+        let mut l = lexer::Lexer::new(&source, "lsp_test_code_action");
+        let tokens = l.run();
+        let mut p = Parser::new(tokens.clone(), "lsp_test_code_action");
+        p.parse();
+        let uri = "file:///lsp_test_code_action.sql".parse::<Uri>().unwrap();
+        let range = Range {
+            start: Position::new(0, 0),
+            end: Position::new(input.lines().count() as u32, 0),
+        };
+        code_actions(&uri, &tokens, &p.errors, range)
+            .into_iter()
+            .map(|a| match a {
+                CodeActionOrCommand::CodeAction(a) => {
+                    let (changed, edits) =
+                        a.edit.unwrap().changes.unwrap().into_iter().next().unwrap();
+                    assert_eq!(changed, uri);
+                    (a.title, edits)
+                }
+                CodeActionOrCommand::Command(_) => panic!("expected a code action"),
+            })
+            .collect()
+    }
+
+    fn edit(line: u32, start: u32, end: u32, text: &str) -> TextEdit {
+        TextEdit {
+            range: Range {
+                start: Position::new(line, start),
+                end: Position::new(line, end),
+            },
+            new_text: text.into(),
+        }
+    }
+
+    #[test]
+    fn missing_semicolon() {
+        assert_eq!(
+            actions("VACUUM;\nVACUUM"),
+            vec![(
+                "Insert missing semicolon".to_string(),
+                vec![edit(1, 6, 6, ";")]
+            )]
+        );
+    }
+
+    #[test]
+    fn misspelled_keyword() {
+        assert_eq!(
+            actions("VACUUM;\nVACUM;"),
+            vec![(
+                "Replace 'VACUM' with VACUUM".to_string(),
+                vec![edit(1, 0, 5, "VACUUM")]
+            )]
+        );
+    }
+
+    #[test]
+    fn no_action_for_other_rules() {
+        assert_eq!(actions("VACUUM 5;"), vec![]);
+    }
+}
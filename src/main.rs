@@ -1,32 +1,48 @@
 #![allow(dead_code)]
 #[cfg(feature = "trace")]
 use std::time::SystemTime;
-use std::{fs, process::exit, vec};
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process::exit,
+    sync::mpsc,
+    time::Duration,
+    vec,
+};
 
 use clap::Parser;
-use error::{print_str_colored, warn};
+use sqleibniz::{error, highlight, lexer, lsp, parser, style, types};
+
+use error::{Error, print_str_colored, warn};
 use highlight::builder;
 use lexer::Lexer;
-use types::config::Config;
-use types::rules::Rule;
-
-use crate::error::Error;
-
-/// error does formatting and highlighting for errors
-mod error;
-/// highlight implements logic for highlighting tokens found in a string
-mod highlight;
-/// lev implements the levenshtein distance for all sql keywords, this is used to recommend a keyword based on a misspelled word or any
-/// unknown keyword at an arbitrary location in the source statement - mainly used at the start of a new statement
-mod lev;
-/// lexer converts the input into a stream of token for the parser
-mod lexer;
-/// lsp implements the language server protocol to provide diagnostics, suggestions and snippets for sql based on the sqleibniz tooling
-mod lsp;
-/// parser converts the token stream into an abstract syntax tree
-mod parser;
-/// types holds all shared types between the above modules
-mod types;
+use types::config::{self, Config};
+use types::ctx::Context;
+use types::rules::{Rule, Severity};
+
+/// config_discovery finds the configuration applying to a file by walking up its directories
+mod config_discovery;
+/// gzip decompresses gzipped sql files before they are analysed
+mod gzip;
+/// ignore excludes files listed in a .sqleibnizignore from analysis
+mod ignore;
+/// output serializes errors into formats consumed by other tools
+mod output;
+/// watch reports changes of the analysed files for --watch
+mod watch;
+
+/// output format of errors and the summary
+#[derive(clap::ValueEnum, Clone, Copy, PartialEq, Default)]
+enum Format {
+    /// human readable and highlighted
+    #[default]
+    Text,
+    /// a json object per error and for the summary, each on its own line
+    Json,
+    /// a single SARIF 2.1.0 log containing the errors of all files
+    Sarif,
+}
 
 /// LSP and analysis cli for sql. Check for valid syntax, semantics and perform dynamic analysis.
 #[derive(clap::Parser)]
@@ -39,9 +55,30 @@ struct Cli {
     /// files to analyse
     paths: Vec<String>,
 
-    /// path to the configuration
-    #[arg(short = 'c', long, default_value = "leibniz.lua")]
-    config: String,
+    /// path to the configuration, used for all files instead of discovering the configuration of
+    /// each file
+    #[arg(short = 'c', long)]
+    config: Option<String>,
+
+    /// path to the ignore file, used for all files instead of discovering the .sqleibnizignore
+    /// of each file
+    #[arg(long)]
+    ignore_file: Option<String>,
+
+    /// path to a toml configuration, used instead of the lua configuration, its keys mirror the
+    /// `leibniz` table, except hooks
+    #[arg(long)]
+    config_toml: Option<String>,
+
+    /// write a commented default configuration to the path of --config, or leibniz.lua, and
+    /// exit, an existing configuration is not overwritten
+    #[arg(long)]
+    config_init: bool,
+
+    /// milliseconds the configuration may take to execute before sqleibniz falls back to the
+    /// default configuration
+    #[arg(long, default_value_t = config::DEFAULT_TIMEOUT_MS)]
+    config_timeout: u64,
 
     /// disable stdout/stderr output
     #[arg(short = 's', long)]
@@ -51,12 +88,49 @@ struct Cli {
     #[arg(short = 'k', long)]
     kiss: bool,
 
+    /// only print the total amount of errors across all files, useful for scripting
+    #[arg(long)]
+    count_only: bool,
+
+    /// stop reporting errors once this many were reported across all files, 0 reports all
+    /// errors, does not apply to --silent and --count-only
+    #[arg(long, default_value_t = 0)]
+    max_errors: usize,
+
+    /// stop after the first file with errors, later files are not analysed
+    #[arg(long)]
+    fail_fast: bool,
+
+    /// format of errors and the summary
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// lines shown before and after the line of each error, 0 only shows the line itself
+    #[arg(long, default_value_t = error::DEFAULT_CONTEXT_LINES)]
+    context_lines: usize,
+
     /// disable diagnostics by their rules, all are enabled by default - this may change in the
     /// future
     #[arg(short = 'D')]
     #[clap(value_enum)]
     disable: Option<Vec<Rule>>,
 
+    /// enable opt-in diagnostics by their rules, these are disabled by default
+    #[arg(short = 'E')]
+    #[clap(value_enum)]
+    enable: Option<Vec<Rule>>,
+
+    /// only report errors of at least this severity, overrides `min_severity` of the
+    /// configuration
+    #[arg(long)]
+    #[clap(value_enum)]
+    min_severity: Option<Severity>,
+
+    /// analyse the files as migrations, flags statements not belonging in a migration, such as
+    /// runtime-only pragmas
+    #[arg(long)]
+    migration: bool,
+
     /// dump the abstract syntax tree as pretty printed json
     #[arg(long)]
     ast_json: bool,
@@ -67,141 +141,138 @@ struct Cli {
     /// invoke sqleibniz as a language server
     #[arg(long)]
     lsp: bool,
-    // TODO: add a --doc <fuzzy ast node / ast name> to print node documentation
-}
 
-fn configuration(lua: &mlua::Lua, file_name: &str) -> Result<Config, String> {
-    let conf_str = fs::read_to_string(file_name).map_err(|err| {
-        format!(
-            "Issue trying to read configuration from '{}': [{}], falling back to default configuration",
-            file_name, err
-        )
-    })?;
-    let globals = lua.globals();
-    lua.load(conf_str)
-        .set_name(file_name)
-        .exec()
-        .map_err(|err| format!("{}: {}", file_name, err))?;
-    let raw_conf = globals
-        .get::<mlua::Value>("leibniz")
-        .map_err(|err| format!("{}: {}", file_name, err))?;
-    if raw_conf.is_nil() {
-        return Err(format!(
-            "{}: leibniz table is missing from configuration",
-            file_name
-        ));
-    }
-    let conf: Config = lua
-        .unpack(raw_conf)
-        .map_err(|err| format!("{}: {}", file_name, err))?;
-    Ok(conf)
+    /// analyse the files again whenever they change, until ctrl-c, changes of the configuration
+    /// require a restart
+    #[arg(long, conflicts_with_all = ["silent", "count_only", "format", "fail_fast"])]
+    watch: bool,
+
+    /// read the sql to analyse from stdin instead of files
+    #[arg(long, conflicts_with_all = ["paths", "watch"])]
+    stdin: bool,
+
+    /// name of the sql read via --stdin, used in errors and the summary and to discover the
+    /// configuration, relative to the current directory
+    #[arg(long, default_value = "<stdin>", requires = "stdin")]
+    stdin_filename: String,
+    // TODO: add a --doc <fuzzy ast node / ast name> to print node documentation
 }
 
 struct FileResult {
     name: String,
     errors: usize,
+    /// amount of reported errors per severity, indexed by [Severity] from hint to error
+    severities: [usize; 4],
     ignored_errors: usize,
+    /// not all errors were reported, because --max-errors was reached
+    truncated: bool,
 }
 
-fn main() {
-    let args = Cli::parse();
-
-    if args.lsp {
-        if let Err(e) = lsp::start() {
-            panic!("fatal error in language server: {}", e);
-        }
-        return;
-    }
-
-    let mut error_string_builder = builder::Builder::default();
-
-    if args.paths.is_empty() {
-        if !args.silent {
-            error::err(
-                &mut error_string_builder,
-                "no source file(s) provided, exiting",
-            );
-            print!("{}", error_string_builder.string())
+impl FileResult {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            errors: 0,
+            severities: [0; 4],
+            ignored_errors: 0,
+            truncated: false,
         }
-        exit(1);
     }
+}
 
-    let mut config = Config {
-        disabled_rules: vec![],
-        hooks: None,
-    };
+/// Session holds the state shared by the analysis of all files of an invocation
+struct Session<'a> {
+    args: &'a Cli,
+    silent: bool,
+    json: bool,
+    sarif: bool,
+    /// configuration passed via --config or --config-toml, used for all files
+    explicit_config: Option<PathBuf>,
+    /// configurations loaded so far, by their path, None being the default configuration
+    configs: Vec<(Option<PathBuf>, Config)>,
+    max_errors: usize,
+    /// errors reported across all files, for --max-errors
+    reported_errors: usize,
+    /// errors of all files, collected for --format sarif
+    sarif_errors: Vec<Error>,
+    /// buffered output, printed at the end of the invocation
+    out: builder::Builder,
+}
 
-    if !args.ignore_config {
-        // lua defined here because it would be dropped at the end of configuration(), in the
-        // future this will probably need to be moved one scope up to life long enough for analysis
-        let lua = mlua::Lua::new();
-        match configuration(&lua, &args.config) {
-            Ok(conf) => config = conf,
-            Err(err) => {
-                if !args.silent {
-                    error::warn(&mut error_string_builder, &err.to_string());
-                }
+impl Session<'_> {
+    /// analyse lexes, parses and analyses file with the configuration applying to it, reports the
+    /// errors found and records them in file
+    fn analyse(&mut self, file: &mut FileResult) -> Result<(), String> {
+        let config_path = if self.args.ignore_config {
+            None
+        } else if self.explicit_config.is_some() {
+            self.explicit_config.clone()
+        } else if self.args.stdin {
+            // the file named via --stdin-filename may not exist, thus it is not canonicalized
+            std::env::current_dir()
+                .ok()
+                .and_then(|dir| config_discovery::find_config(dir.join(&file.name).parent()?))
+        } else {
+            fs::canonicalize(&file.name)
+                .ok()
+                .and_then(|path| config_discovery::find_config(path.parent()?))
+        };
+        let config_index = match self
+            .configs
+            .iter()
+            .position(|(path, _)| *path == config_path)
+        {
+            Some(i) => i,
+            None => {
+                let config = configuration(
+                    config_path.as_deref(),
+                    self.args,
+                    self.silent,
+                    !self.silent && !self.args.kiss && !self.json && !self.sarif,
+                    &mut self.out,
+                );
+                self.configs.push((config_path, config));
+                self.configs.len() - 1
             }
-        }
-    }
-
-    if let Some(rules) = args.disable {
-        let mut p = rules.clone();
-        config.disabled_rules.append(&mut p);
-    }
-
-    if !config.disabled_rules.is_empty() && !args.silent && !args.kiss {
-        let mut ignore_buffer = builder::Builder::default();
-        warn(
-            &mut ignore_buffer,
-            "Ignoring the following diagnostics, as specified:",
-        );
-        for rule in &config.disabled_rules {
-            print_str_colored(&mut ignore_buffer, " -> ", error::Color::Blue);
-            ignore_buffer.write_str(rule.name());
-            ignore_buffer.write_char('\n');
-        }
-
-        print!("{}", ignore_buffer.string())
-    }
-
-    let mut files = args
-        .paths
-        .into_iter()
-        .map(|name| FileResult {
-            name,
-            errors: 0,
-            ignored_errors: 0,
-        })
-        .collect::<Vec<FileResult>>();
-
-    #[cfg(feature = "trace")]
-    let start = SystemTime::now();
+        };
+        let config = &self.configs[config_index].1;
+        let min_severity = config.min_severity.unwrap_or(Severity::Hint);
 
-    for file in &mut files {
         let mut errors: Vec<Error> = vec![];
-        let content = match fs::read(&file.name) {
-            Ok(c) => c,
-            Err(err) => {
-                if !args.silent {
-                    error::err(
-                        &mut error_string_builder,
-                        &format!("failed to read file '{}': {}", file.name, err),
-                    );
-                }
-                print!("{}", error_string_builder.string());
-                exit(1);
-            }
+        let content = if self.args.stdin {
+            let mut content = vec![];
+            io::stdin()
+                .read_to_end(&mut content)
+                .map_err(|err| format!("failed to read stdin: {}", err))?;
+            content
+        } else {
+            fs::read(&file.name)
+                .map_err(|err| format!("failed to read file '{}': {}", file.name, err))?
+        };
+        let content = if gzip::is_gzip(&file.name, &content) {
+            gzip::decompress(&content)
+                .map_err(|err| format!("failed to decompress file '{}': {}", file.name, err))?
+        } else {
+            content
         };
         let mut ignored_errors = 0;
         let mut lexer = Lexer::new(&content, file.name.as_str());
         let toks = lexer.run();
         errors.append(&mut lexer.errors);
+        errors.append(&mut style::line_length(
+            file.name.as_str(),
+            &content,
+            config
+                .max_line_length
+                .unwrap_or(style::DEFAULT_MAX_LINE_LENGTH),
+            config.tab_width.unwrap_or(style::DEFAULT_TAB_WIDTH),
+        ));
 
         if !toks.is_empty() {
             #[cfg(feature = "trace")]
             println!("{:=^72}", " CALLSTACK ");
             let mut parser = parser::Parser::new(toks.clone(), file.name.as_str());
+            parser.pragmas = config.pragmas.clone();
             let ast = parser.parse();
             #[cfg(feature = "trace")]
             {
@@ -211,7 +282,7 @@ fn main() {
                 }
             }
 
-            if args.ast_json {
+            if self.args.ast_json {
                 println!(
                     "{}",
                     serde_json::to_string_pretty(
@@ -223,17 +294,28 @@ fn main() {
                 );
             }
 
-            if args.ast {
+            if self.args.ast {
                 println!("{:#?}", &ast);
             }
 
             errors.append(&mut parser.errors);
+
+            let mut ctx = Context::new(file.name.as_str());
+            if let Some(max_joins) = config.max_joins {
+                ctx.max_joins = max_joins;
+            }
+            ctx.sqlite_version = config.sqlite_version;
+            ctx.migration = self.args.migration;
+            errors.append(&mut parser::analyse::statements(&ast, &mut ctx));
         }
 
         let mut processed_errors = errors
             .into_iter()
             .filter(|e| {
-                if config.disabled_rules.contains(&e.rule) {
+                if config.disabled_rules.contains(&e.rule)
+                    || (e.rule.opt_in() && !config.enabled_rules.contains(&e.rule))
+                    || e.severity < min_severity
+                {
                     ignored_errors += 1;
                     false
                 } else {
@@ -242,19 +324,39 @@ fn main() {
             })
             .collect::<Vec<error::Error>>();
 
-        if !processed_errors.is_empty() && !args.silent {
-            if !args.kiss {
+        file.errors = processed_errors.len();
+        for e in &processed_errors {
+            file.severities[e.severity as usize] += 1;
+        }
+        file.ignored_errors = ignored_errors;
+
+        if self.max_errors != 0 {
+            let remaining = self.max_errors.saturating_sub(self.reported_errors);
+            file.truncated = processed_errors.len() > remaining;
+            processed_errors.truncate(remaining);
+        }
+        self.reported_errors += processed_errors.len();
+
+        if self.json && !self.silent {
+            for e in &processed_errors {
+                println!("{}", e.as_json());
+            }
+        } else if self.sarif {
+            self.sarif_errors.extend(processed_errors.iter().cloned());
+        } else if !processed_errors.is_empty() && !self.silent {
+            if !self.args.kiss {
                 error::print_str_colored(
-                    &mut error_string_builder,
+                    &mut self.out,
                     &format!("{:=^72}\n", format!(" {} ", file.name)),
                     error::Color::Blue,
                 );
             }
             let error_count = processed_errors.len();
             for (i, e) in processed_errors.iter_mut().enumerate() {
-                if args.kiss {
+                if self.args.kiss {
                     println!(
-                        "{}: {}, {} at l:{}:{}-{}",
+                        "{}[{}]: {}, {} at l:{}:{}-{}",
+                        e.severity.name(),
                         e.rule.name(),
                         e.msg,
                         e.note,
@@ -263,21 +365,237 @@ fn main() {
                         e.end
                     );
                 } else {
-                    e.print(&mut error_string_builder, &content, &toks);
+                    e.print(&mut self.out, &content, &toks, self.args.context_lines);
                 }
 
                 if i + 1 != error_count {
-                    error_string_builder.write_char('\n');
+                    self.out.write_char('\n');
                 }
             }
         }
+        Ok(())
+    }
 
-        file.errors = processed_errors.len();
-        file.ignored_errors = ignored_errors;
+    /// summary writes the errors of each file and the amount of verified files to
+    /// [Session::out], clock is the time of the analysis in --watch mode, returns the amount of
+    /// verified files
+    fn summary(&mut self, files: &[FileResult], clock: Option<&str>) -> usize {
+        error::print_str_colored(
+            &mut self.out,
+            &format!(
+                "{:=^72}\n",
+                match clock {
+                    Some(clock) => format!(" Summary {clock} "),
+                    None => " Summary ".into(),
+                }
+            ),
+            error::Color::Blue,
+        );
+        for file in files {
+            error::print_str_colored(
+                &mut self.out,
+                &format!(
+                    "[{}]",
+                    match file.errors {
+                        0 => '+',
+                        _ => '-',
+                    }
+                ),
+                match file.errors {
+                    0 => error::Color::Green,
+                    _ => error::Color::Red,
+                },
+            );
+            self.out.write_char(' ');
+            self.out.write_str(&file.name);
+            self.out.write_char(':');
+            self.out.write_char('\n');
+            error::print_str_colored(
+                &mut self.out,
+                &format!("    {} Error(s) detected\n", file.errors),
+                match file.errors {
+                    0 => error::Color::Green,
+                    _ => error::Color::Red,
+                },
+            );
+            for severity in [
+                Severity::Error,
+                Severity::Warning,
+                Severity::Info,
+                Severity::Hint,
+            ] {
+                let count = file.severities[severity as usize];
+                if count != 0 {
+                    error::print_str_colored(
+                        &mut self.out,
+                        &format!("      {} {}(s)\n", count, severity.name()),
+                        severity.color(),
+                    );
+                }
+            }
+            error::print_str_colored(
+                &mut self.out,
+                &format!("    {} Error(s) ignored\n", file.ignored_errors),
+                match file.ignored_errors {
+                    0 => error::Color::Green,
+                    _ => error::Color::Yellow,
+                },
+            )
+        }
+        self.out.write_char('\n');
+        print_str_colored(&mut self.out, "=>", error::Color::Blue);
+        let verified = files.iter().filter(|f| f.errors == 0).count();
+        self.out.write_string(format!(
+            " {}/{} Files verified successfully, {} verification failed.\n",
+            verified,
+            files.len(),
+            files.len() - verified
+        ));
+        verified
+    }
+}
+
+fn main() {
+    let args = Cli::parse();
+    // --count-only prints nothing but the error count, thus everything else is silenced
+    let silent = args.silent || args.count_only;
+    let json = args.format == Format::Json;
+    let sarif = args.format == Format::Sarif;
+
+    if args.lsp {
+        if let Err(e) = lsp::start() {
+            panic!("fatal error in language server: {}", e);
+        }
+        return;
+    }
+
+    let mut error_string_builder = builder::Builder::default();
+
+    if args.config_init {
+        let path = args
+            .config
+            .as_deref()
+            .unwrap_or(config_discovery::CONFIG_NAMES[0]);
+        if let Err(err) = config::init(path) {
+            if !silent {
+                error::err(&mut error_string_builder, &err);
+                print!("{}", error_string_builder.string())
+            }
+            exit(1);
+        }
+        if !silent {
+            println!("Wrote default configuration to '{}'", path);
+        }
+        return;
+    }
+
+    if args.paths.is_empty() && !args.stdin {
+        if !silent {
+            error::err(
+                &mut error_string_builder,
+                "no source file(s) provided, exiting",
+            );
+            print!("{}", error_string_builder.string())
+        }
+        exit(1);
     }
+
+    // ignore files loaded so far, by their path, None if loading failed
+    let mut ignores: Vec<(PathBuf, Option<ignore::Ignore>)> = vec![];
+    let mut files = if args.stdin {
+        vec![FileResult::new(args.stdin_filename.clone())]
+    } else {
+        args.paths
+            .iter()
+            .filter(|name| {
+                let Ok(path) = fs::canonicalize(name) else {
+                    // missing files are reported when analysed
+                    return true;
+                };
+                let Some(ignore_path) = args
+                    .ignore_file
+                    .as_ref()
+                    .map(PathBuf::from)
+                    .or_else(|| config_discovery::find_ignore_file(path.parent()?))
+                else {
+                    return true;
+                };
+                let index = match ignores.iter().position(|(p, _)| *p == ignore_path) {
+                    Some(i) => i,
+                    None => {
+                        let ignore = match ignore::Ignore::load(&ignore_path) {
+                            Ok(ignore) => Some(ignore),
+                            Err(err) => {
+                                if !silent {
+                                    error::warn(&mut error_string_builder, &err);
+                                }
+                                None
+                            }
+                        };
+                        ignores.push((ignore_path, ignore));
+                        ignores.len() - 1
+                    }
+                };
+                !ignores[index]
+                    .1
+                    .as_ref()
+                    .is_some_and(|i| i.is_ignored(&path))
+            })
+            .map(|name| FileResult::new(name.clone()))
+            .collect::<Vec<FileResult>>()
+    };
+
+    let mut session = Session {
+        args: &args,
+        silent,
+        json,
+        sarif,
+        // an explicitly passed configuration applies to all files
+        explicit_config: args
+            .config_toml
+            .as_ref()
+            .or(args.config.as_ref())
+            .map(PathBuf::from),
+        configs: vec![],
+        // only the exit code matters if silent, thus all errors are counted
+        max_errors: if silent { 0 } else { args.max_errors },
+        reported_errors: 0,
+        sarif_errors: vec![],
+        out: error_string_builder,
+    };
+
+    #[cfg(feature = "trace")]
+    let start = SystemTime::now();
+
+    // files analysed, less than all files if --fail-fast stopped early
+    let mut analysed = files.len();
+    for (index, file) in files.iter_mut().enumerate() {
+        if let Err(err) = session.analyse(file) {
+            if !silent {
+                error::err(&mut session.out, &err);
+            }
+            print!("{}", std::mem::take(&mut session.out).string());
+            exit(1);
+        }
+
+        if args.fail_fast && file.errors != 0 {
+            analysed = index + 1;
+            break;
+        }
+    }
+    // the summary only holds the analysed files
+    files.truncate(analysed);
     #[cfg(feature = "trace")]
     let took = SystemTime::now().duration_since(start).unwrap();
 
+    if args.count_only {
+        println!("{}", files.iter().map(|f| f.errors).sum::<usize>());
+        if files.iter().any(|f| f.errors != 0) {
+            exit(1);
+        }
+        return;
+    }
+
     if args.silent {
         let verified = files.iter().filter(|f| f.errors == 0).count();
         if verified != files.len() {
@@ -286,64 +604,182 @@ fn main() {
         return;
     }
 
-    error::print_str_colored(
-        &mut error_string_builder,
-        &format!("{:=^72}\n", " Summary "),
-        error::Color::Blue,
-    );
-    for file in &files {
-        error::print_str_colored(
-            &mut error_string_builder,
-            &format!(
-                "[{}]",
-                match file.errors {
-                    0 => '+',
-                    _ => '-',
-                }
-            ),
-            match file.errors {
-                0 => error::Color::Green,
-                _ => error::Color::Red,
-            },
+    if sarif {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&output::sarif::document(&session.sarif_errors))
+                .unwrap_or_default()
         );
-        error_string_builder.write_char(' ');
-        error_string_builder.write_str(&file.name);
-        error_string_builder.write_char(':');
-        error_string_builder.write_char('\n');
-        error::print_str_colored(
-            &mut error_string_builder,
-            &format!("    {} Error(s) detected\n", file.errors),
-            match file.errors {
-                0 => error::Color::Green,
-                _ => error::Color::Red,
-            },
+        if files.iter().any(|f| f.errors != 0) {
+            exit(1);
+        }
+        return;
+    }
+
+    let truncated = files.iter().any(|f| f.truncated);
+
+    if json {
+        let verified = files.iter().filter(|f| f.errors == 0).count();
+        println!(
+            "{}",
+            serde_json::json!({
+                "files": files
+                    .iter()
+                    .map(|f| serde_json::json!({
+                        "file": f.name,
+                        "errors": f.errors,
+                        "ignored_errors": f.ignored_errors,
+                        "truncated": f.truncated,
+                    }))
+                    .collect::<Vec<_>>(),
+                "truncated": truncated,
+                "verified": verified,
+                "failed": files.len() - verified,
+            })
         );
-        error::print_str_colored(
-            &mut error_string_builder,
-            &format!("    {} Error(s) ignored\n", file.ignored_errors),
-            match file.ignored_errors {
-                0 => error::Color::Green,
-                _ => error::Color::Yellow,
-            },
-        )
+        if verified != files.len() {
+            exit(1);
+        }
+        return;
     }
-    error_string_builder.write_char('\n');
-    print_str_colored(&mut error_string_builder, "=>", error::Color::Blue);
-    let verified = files.iter().filter(|f| f.errors == 0).count();
+
+    if truncated {
+        let notice = format!(
+            "stopped reporting errors after {}, the summary counts all errors, see --max-errors",
+            session.max_errors
+        );
+        if args.kiss {
+            println!("{notice}");
+        } else {
+            warn(&mut session.out, &notice);
+        }
+    }
+
+    let verified = session.summary(&files, args.watch.then(watch::clock).as_deref());
     #[cfg(feature = "trace")]
     println!("took: [{:?}]", took);
-    error_string_builder.write_string(format!(
-        " {}/{} Files verified successfully, {} verification failed.\n",
-        verified,
-        files.len(),
-        files.len() - verified
-    ));
 
+    let out = std::mem::take(&mut session.out).string();
     if !args.silent && !args.kiss {
-        print!("{}", error_string_builder.string());
+        print!("{out}");
+    }
+
+    if args.watch {
+        watch_files(&mut session, &mut files);
+        return;
     }
 
     if verified != files.len() {
         exit(1);
     }
 }
+
+/// analyses each file of files again once it changes and prints its errors and the summary, until
+/// ctrl-c is pressed, see --watch
+fn watch_files(session: &mut Session, files: &mut [FileResult]) {
+    let paths = files
+        .iter()
+        .map(|f| PathBuf::from(&f.name))
+        .collect::<Vec<PathBuf>>();
+    let (sender, events) = mpsc::channel();
+    let stop = sender.clone();
+    if let Err(err) = ctrlc::set_handler(move || {
+        let _ = stop.send(watch::Event::Stop);
+    }) {
+        error::warn(&mut session.out, &format!("failed to handle ctrl-c: {err}"));
+    }
+    // events are only sent as long as the watcher lives
+    let _watcher = match watch::watch(&paths, sender) {
+        Ok(watcher) => watcher,
+        Err(err) => {
+            error::err(&mut session.out, &format!("failed to watch files: {err}"));
+            print!("{}", std::mem::take(&mut session.out).string());
+            exit(1);
+        }
+    };
+    print!("{}", std::mem::take(&mut session.out).string());
+
+    watch::run(&paths, &events, |index| {
+        session.reported_errors = 0;
+        files[index] = FileResult::new(files[index].name.clone());
+        if let Err(err) = session.analyse(&mut files[index]) {
+            // the file may be replaced by an editor right now, the next change analyses it again
+            error::err(&mut session.out, &err);
+        }
+        session.summary(files, Some(&watch::clock()));
+        let out = std::mem::take(&mut session.out).string();
+        if !session.args.kiss {
+            print!("{out}");
+        }
+    });
+}
+
+/// loads the configuration at path, lua or toml depending on its extension, or the default
+/// configuration if path is None or fails to load, and applies the rule and severity flags
+fn configuration(
+    path: Option<&Path>,
+    args: &Cli,
+    silent: bool,
+    verbose: bool,
+    error_string_builder: &mut builder::Builder,
+) -> Config {
+    let mut config = Config {
+        disabled_rules: vec![],
+        enabled_rules: vec![],
+        min_severity: None,
+        hooks: None,
+        max_joins: None,
+        sqlite_version: None,
+        max_line_length: None,
+        tab_width: None,
+        pragmas: vec![],
+    };
+
+    if let Some(path) = path {
+        let name = path.to_string_lossy();
+        let loaded = if path.extension().is_some_and(|ext| ext == "toml") {
+            config::load_toml(&name)
+        } else {
+            // lua defined here because it would be dropped at the end of configuration(), in the
+            // future this will probably need to be moved one scope up to life long enough for analysis
+            let lua = mlua::Lua::new();
+            config::load(&lua, &name, Duration::from_millis(args.config_timeout))
+        };
+        match loaded {
+            Ok(conf) => config = conf,
+            Err(err) => {
+                if !silent {
+                    error::warn(error_string_builder, &err);
+                }
+            }
+        }
+    }
+
+    if let Some(rules) = &args.disable {
+        config.disabled_rules.extend(rules.iter().cloned());
+    }
+
+    if let Some(rules) = &args.enable {
+        config.enabled_rules.extend(rules.iter().cloned());
+    }
+
+    if args.min_severity.is_some() {
+        config.min_severity = args.min_severity;
+    }
+
+    if !config.disabled_rules.is_empty() && verbose {
+        let mut ignore_buffer = builder::Builder::default();
+        warn(
+            &mut ignore_buffer,
+            "Ignoring the following diagnostics, as specified:",
+        );
+        for rule in &config.disabled_rules {
+            print_str_colored(&mut ignore_buffer, " -> ", error::Color::Blue);
+            ignore_buffer.write_str(rule.name());
+            ignore_buffer.write_char('\n');
+        }
+
+        print!("{}", ignore_buffer.string())
+    }
+    config
+}
@@ -0,0 +1,2 @@
+/// sarif serializes errors as a SARIF 2.1.0 log, see: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+pub mod sarif;
@@ -0,0 +1,143 @@
+use crate::{
+    error::Error,
+    types::rules::{Rule, Severity},
+};
+
+/// the SARIF level of results of the severity, SARIF has no distinct level for hints
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info | Severity::Hint => "note",
+    }
+}
+
+fn result(err: &Error) -> serde_json::Value {
+    serde_json::json!({
+        "ruleId": err.rule.name(),
+        "level": level(err.severity),
+        "message": { "text": format!("{}: {}", err.msg, err.note) },
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": { "uri": err.file },
+                // sarif lines and columns are one based, the end column is exclusive
+                "region": {
+                    "startLine": err.line + 1,
+                    "startColumn": err.start + 1,
+                    "endColumn": err.end.max(err.start) + 1,
+                },
+            },
+        }],
+    })
+}
+
+/// builds a SARIF log with a single run containing a result for each error, the rules of the
+/// tool driver are the distinct rules of errors
+pub fn document(errors: &[Error]) -> serde_json::Value {
+    let mut rules: Vec<&Rule> = vec![];
+    for err in errors {
+        if !rules.contains(&&err.rule) {
+            rules.push(&err.rule);
+        }
+    }
+    serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules
+                        .iter()
+                        .map(|r| serde_json::json!({
+                            "id": r.name(),
+                            "shortDescription": { "text": r.description() },
+                        }))
+                        .collect::<Vec<_>>(),
+                },
+            },
+            "results": errors.iter().map(result).collect::<Vec<_>>(),
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::document;
+    use crate::{error::Error, types::rules::Rule};
+
+    fn error(rule: Rule, line: usize, start: usize, end: usize) -> Error {
+        Error {
+            file: "sarif_test.sql".into(),
+            line,
+            severity: rule.severity(),
+            rule,
+            note: "note".into(),
+            msg: "msg".into(),
+            start,
+            end,
+            improved_line: None,
+            doc_url: None,
+        }
+    }
+
+    #[test]
+    fn empty_run() {
+        let doc = document(&[]);
+        assert_eq!(doc["version"], "2.1.0");
+        assert_eq!(doc["runs"][0]["tool"]["driver"]["name"], "sqleibniz");
+        assert_eq!(doc["runs"][0]["results"], serde_json::json!([]));
+        assert_eq!(
+            doc["runs"][0]["tool"]["driver"]["rules"],
+            serde_json::json!([])
+        );
+    }
+
+    #[test]
+    fn results_and_distinct_rules() {
+        let doc = document(&[
+            error(Rule::Syntax, 0, 7, 8),
+            error(Rule::Semicolon, 1, 0, 6),
+            error(Rule::Syntax, 2, 3, 4),
+        ]);
+        let run = &doc["runs"][0];
+        assert_eq!(
+            run["tool"]["driver"]["rules"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|r| r["id"].as_str().unwrap())
+                .collect::<Vec<_>>(),
+            vec!["Syntax", "Semicolon"]
+        );
+        let result = &run["results"][0];
+        assert_eq!(result["ruleId"], "Syntax");
+        assert_eq!(result["level"], "error");
+        assert_eq!(result["message"]["text"], "msg: note");
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "sarif_test.sql");
+        assert_eq!(
+            location["region"],
+            serde_json::json!({ "startLine": 1, "startColumn": 8, "endColumn": 9 })
+        );
+        assert_eq!(run["results"].as_array().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn levels_follow_severity() {
+        let doc = document(&[
+            error(Rule::Quirk, 0, 0, 1),
+            error(Rule::ImplicitInsertColumns, 0, 0, 1),
+            error(Rule::LineLength, 0, 0, 1),
+        ]);
+        let levels = doc["runs"][0]["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["level"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(levels, vec!["warning", "note", "note"]);
+    }
+}
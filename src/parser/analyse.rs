@@ -0,0 +1,807 @@
+use crate::{
+    error::Error,
+    parser::nodes::*,
+    types::{
+        Keyword, Token, Type,
+        ctx::{Context, SavePoint, Transaction},
+        pragma,
+        rules::Rule,
+        storage::SqliteStorageClass,
+        version::Version,
+    },
+};
+
+/// impl FieldAnalyse for $tt as a noop, for types not holding any nodes
+macro_rules! impl_field_analyse_noop {
+    ($($tt:tt),*) => {
+        $(
+            impl FieldAnalyse for $tt {
+                fn field_analyse(&self, _ctx: &mut Context) -> Vec<Error> {
+                    vec![]
+                }
+            }
+        )*
+    };
+}
+
+/// FieldAnalyse is implemented for all types used as fields of nodes, the node! macro calls
+/// [FieldAnalyse::field_analyse] for each field of a node in [Node::analyse]
+pub trait FieldAnalyse {
+    fn field_analyse(&self, ctx: &mut Context) -> Vec<Error>;
+}
+
+impl_field_analyse_noop!(
+    String,
+    bool,
+    usize,
+    Keyword,
+    Token,
+    SqliteStorageClass,
+    SchemaTableContainer,
+    Type,
+    PragmaInvocation,
+    TriggerTiming,
+    TriggerEvent,
+    JoinOperator,
+    CompoundOperator
+);
+
+impl<T: Node + ?Sized> FieldAnalyse for Box<T> {
+    fn field_analyse(&self, ctx: &mut Context) -> Vec<Error> {
+        self.as_ref().analyse(ctx)
+    }
+}
+
+impl<T: FieldAnalyse> FieldAnalyse for Option<T> {
+    fn field_analyse(&self, ctx: &mut Context) -> Vec<Error> {
+        match self {
+            Some(n) => n.field_analyse(ctx),
+            None => vec![],
+        }
+    }
+}
+
+impl<T: FieldAnalyse> FieldAnalyse for Vec<T> {
+    fn field_analyse(&self, ctx: &mut Context) -> Vec<Error> {
+        self.iter().flat_map(|n| n.field_analyse(ctx)).collect()
+    }
+}
+
+impl<A: FieldAnalyse, B: FieldAnalyse> FieldAnalyse for (A, B) {
+    fn field_analyse(&self, ctx: &mut Context) -> Vec<Error> {
+        let mut errors = self.0.field_analyse(ctx);
+        errors.append(&mut self.1.field_analyse(ctx));
+        errors
+    }
+}
+
+impl FieldAnalyse for ColumnConstraint {
+    fn field_analyse(&self, ctx: &mut Context) -> Vec<Error> {
+        match self {
+            // CHECK constraints are additionally checked by the statement defining the column, see
+            // [check_constraints]
+            ColumnConstraint::Check(expr) => expr.analyse(ctx),
+            ColumnConstraint::Default { expr, literal, .. } => {
+                let mut errors = expr.field_analyse(ctx);
+                if let Some(literal) = literal {
+                    errors.append(&mut literal.analyse(ctx));
+                }
+                errors
+            }
+            ColumnConstraint::Generated { expr, .. } | ColumnConstraint::As { expr, .. } => {
+                expr.analyse(ctx)
+            }
+            ColumnConstraint::PrimaryKey { .. }
+            | ColumnConstraint::NotNull { .. }
+            | ColumnConstraint::Unique { .. }
+            | ColumnConstraint::Collate(_)
+            | ColumnConstraint::ForeignKey(_) => vec![],
+        }
+    }
+}
+
+impl FieldAnalyse for NamedColumnConstraint {
+    fn field_analyse(&self, ctx: &mut Context) -> Vec<Error> {
+        self.constraint.field_analyse(ctx)
+    }
+}
+
+impl FieldAnalyse for TableConstraint {
+    fn field_analyse(&self, ctx: &mut Context) -> Vec<Error> {
+        match self {
+            TableConstraint::PrimaryKey { columns, .. }
+            | TableConstraint::Unique { columns, .. } => columns.field_analyse(ctx),
+            // CHECK constraints are additionally checked by [create_table]
+            TableConstraint::Check(expr) => expr.analyse(ctx),
+            TableConstraint::ForeignKey { .. } => vec![],
+        }
+    }
+}
+
+impl FieldAnalyse for NamedTableConstraint {
+    fn field_analyse(&self, ctx: &mut Context) -> Vec<Error> {
+        self.constraint.field_analyse(ctx)
+    }
+}
+
+impl FieldAnalyse for InBody {
+    fn field_analyse(&self, ctx: &mut Context) -> Vec<Error> {
+        match self {
+            InBody::List(list) => list.field_analyse(ctx),
+            InBody::Subquery(subquery) => subquery.analyse(ctx),
+            InBody::Table(_) => vec![],
+        }
+    }
+}
+
+impl FieldAnalyse for FrameBound {
+    fn field_analyse(&self, ctx: &mut Context) -> Vec<Error> {
+        match self {
+            FrameBound::Preceding(expr) | FrameBound::Following(expr) => expr.analyse(ctx),
+            FrameBound::UnboundedPreceding
+            | FrameBound::CurrentRow
+            | FrameBound::UnboundedFollowing => vec![],
+        }
+    }
+}
+
+impl FieldAnalyse for ResultColumn {
+    fn field_analyse(&self, ctx: &mut Context) -> Vec<Error> {
+        match self {
+            ResultColumn::Expr { expr, .. } => expr.analyse(ctx),
+            ResultColumn::Star | ResultColumn::TableStar(_) => vec![],
+        }
+    }
+}
+
+impl FieldAnalyse for TableOrSubquery {
+    fn field_analyse(&self, ctx: &mut Context) -> Vec<Error> {
+        match self {
+            TableOrSubquery::Subquery { select, .. } => select.analyse(ctx),
+            TableOrSubquery::Table { .. } => vec![],
+        }
+    }
+}
+
+impl FieldAnalyse for InsertSource {
+    fn field_analyse(&self, ctx: &mut Context) -> Vec<Error> {
+        match self {
+            InsertSource::Values(rows) => rows.field_analyse(ctx),
+            InsertSource::Select(select) => select.analyse(ctx),
+            InsertSource::DefaultValues => vec![],
+        }
+    }
+}
+
+impl FieldAnalyse for UpsertAction {
+    fn field_analyse(&self, ctx: &mut Context) -> Vec<Error> {
+        match self {
+            UpsertAction::Update { set, where_clause } => {
+                let mut errors = set.field_analyse(ctx);
+                errors.append(&mut where_clause.field_analyse(ctx));
+                errors
+            }
+            UpsertAction::Nothing => vec![],
+        }
+    }
+}
+
+impl FieldAnalyse for JoinConstraint {
+    fn field_analyse(&self, ctx: &mut Context) -> Vec<Error> {
+        match self {
+            JoinConstraint::On(expr) => expr.analyse(ctx),
+            JoinConstraint::Using(_) => vec![],
+        }
+    }
+}
+
+/// emits [Rule::TooManyJoins] for join clauses with more than [Context::max_joins] joins and
+/// [Rule::SqliteUnsupported] for join clauses exceeding the table limit of sqlite
+pub fn join_clause(node: &JoinClause, ctx: &mut Context) -> Vec<Error> {
+    // the first table of the clause is not part of node.joins
+    let tables = node.joins.len() + 1;
+    if tables > Context::SQLITE_MAX_JOIN_TABLES {
+        let mut err = ctx.err(
+            "Too many tables in join",
+            &format!(
+                "sqlite joins at most {} tables, this join clause joins {tables}",
+                Context::SQLITE_MAX_JOIN_TABLES
+            ),
+            &node.joins[Context::SQLITE_MAX_JOIN_TABLES - 1].t,
+            Rule::SqliteUnsupported,
+        );
+        err.doc_url = Some("https://www.sqlite.org/limits.html#max_join");
+        vec![err]
+    } else if node.joins.len() > ctx.max_joins {
+        vec![ctx.err(
+            "Too many joins",
+            &format!(
+                "join clause contains {} joins, the configured maximum is {}, consider splitting the query or using views",
+                node.joins.len(),
+                ctx.max_joins
+            ),
+            &node.joins[ctx.max_joins].t,
+            Rule::TooManyJoins,
+        )]
+    } else {
+        vec![]
+    }
+}
+
+/// emits [Rule::Quirk] for an empty IN list, sqlite accepts it, while the SQL standard and most
+/// other databases reject it
+pub fn in_expr(node: &InExpr, ctx: &mut Context) -> Vec<Error> {
+    match &node.body {
+        InBody::List(list) if list.is_empty() => {
+            let mut err = ctx.err(
+                "Empty IN list",
+                &format!(
+                    "sqlite evaluates {} () to {}, most other databases and the SQL standard reject an empty list",
+                    if node.negated { "NOT IN" } else { "IN" },
+                    node.negated
+                ),
+                &node.t,
+                Rule::Quirk,
+            );
+            err.doc_url = Some("https://www.sqlite.org/lang_expr.html#the_in_and_not_in_operators");
+            vec![err]
+        }
+        _ => vec![],
+    }
+}
+
+/// emits [Rule::SqliteUnsupported] for operators newer than [Context::sqlite_version]
+pub fn binary_expr(node: &BinaryExpr, ctx: &mut Context) -> Vec<Error> {
+    let operator = match node.op.ttype {
+        Type::Arrow => "->",
+        Type::DoubleArrow => "->>",
+        _ => return vec![],
+    };
+    requires_version(
+        ctx,
+        Version::new(3, 38, 0),
+        "Operator requires a newer sqlite version",
+        operator,
+        &node.op,
+        "https://www.sqlite.org/json1.html#jptr",
+    )
+}
+
+/// emits [Rule::SqliteUnsupported] for [NOT] MATERIALIZED hints if the configured sqlite version
+/// predates them
+pub fn common_table_expression(node: &CommonTableExpression, ctx: &mut Context) -> Vec<Error> {
+    let hint = match node.materialized {
+        Some(true) => "MATERIALIZED",
+        Some(false) => "NOT MATERIALIZED",
+        None => return vec![],
+    };
+    requires_version(
+        ctx,
+        Version::new(3, 35, 0),
+        "Materialization hint requires a newer sqlite version",
+        hint,
+        &node.t,
+        "https://www.sqlite.org/lang_with.html#materialization_hints",
+    )
+}
+
+/// emits [Rule::SqliteUnsupported] for IS [NOT] DISTINCT FROM if the configured sqlite version
+/// predates it
+pub fn is_expr(node: &IsExpr, ctx: &mut Context) -> Vec<Error> {
+    if !node.distinct {
+        return vec![];
+    }
+    requires_version(
+        ctx,
+        Version::new(3, 39, 0),
+        "Operator requires a newer sqlite version",
+        if node.negated {
+            "IS NOT DISTINCT FROM"
+        } else {
+            "IS DISTINCT FROM"
+        },
+        &node.t,
+        "https://www.sqlite.org/lang_expr.html#the_is_and_is_not_operators",
+    )
+}
+
+/// emits [Rule::SqliteUnsupported] for feature if [Context::sqlite_version] is set to a version
+/// before since, the version introducing feature
+fn requires_version(
+    ctx: &Context,
+    since: Version,
+    msg: &str,
+    feature: &str,
+    t: &Token,
+    doc: &'static str,
+) -> Vec<Error> {
+    match ctx.sqlite_version {
+        Some(target) if target < since => {
+            let mut err = ctx.err(
+                msg,
+                &format!(
+                    "{feature} requires sqlite {since} or later, the configured sqlite_version is {target}"
+                ),
+                t,
+                Rule::SqliteUnsupported,
+            );
+            err.doc_url = Some(doc);
+            vec![err]
+        }
+        _ => vec![],
+    }
+}
+
+/// emits [Rule::Quirk] for pragmas only affecting the current connection in migrations
+pub fn pragma(node: &Pragma, ctx: &mut Context) -> Vec<Error> {
+    let name = node.name.table();
+    if !ctx.migration || !pragma::is_runtime(name) {
+        return vec![];
+    }
+    let mut err = ctx.err(
+        "Runtime pragma in migration",
+        &format!(
+            "PRAGMA {name} only affects or inspects the current connection, it does not change the schema and does not belong in a migration"
+        ),
+        &node.t,
+        Rule::Quirk,
+    );
+    err.doc_url = Some("https://www.sqlite.org/pragma.html");
+    vec![err]
+}
+
+/// emits [Rule::ReservedTableName] and [Rule::Quirk] for tables renamed to a reserved or already
+/// existing name, keeps [Context::tables] up to date
+pub fn alter(node: &Alter, ctx: &mut Context) -> Vec<Error> {
+    let target = node.target.table();
+    let Some(new_name) = &node.rename_to else {
+        let errors = node
+            .add_column
+            .as_ref()
+            .map(|column| check_constraints(column, target, ctx))
+            .unwrap_or_default();
+        // ALTER TABLE requires the table to exist
+        let table = ctx.add_table(target);
+        if let Some(column) = &node.add_column {
+            table.columns.push(column.name.clone());
+            if is_required(column) {
+                table.required.push(column.name.clone());
+            }
+        } else if let (Some(old), Some(new)) = (&node.rename_column_target, &node.new_column_name) {
+            table.columns.retain(|c| !c.eq_ignore_ascii_case(old));
+            table.columns.push(new.clone());
+            if table.required.iter().any(|c| c.eq_ignore_ascii_case(old)) {
+                table.required.retain(|c| !c.eq_ignore_ascii_case(old));
+                table.required.push(new.clone());
+            }
+        } else if let Some(column) = &node.drop_column {
+            table.columns.retain(|c| !c.eq_ignore_ascii_case(column));
+            table.required.retain(|c| !c.eq_ignore_ascii_case(column));
+        }
+        return errors;
+    };
+    let mut errors = vec![];
+    if new_name
+        .get(..7)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("sqlite_"))
+    {
+        let mut err = ctx.err(
+            "Reserved table name",
+            &format!(
+                "table names starting with sqlite_ are reserved for sqlites internal tables, rename {new_name}"
+            ),
+            &node.t,
+            Rule::ReservedTableName,
+        );
+        err.doc_url = Some("https://www.sqlite.org/fileformat2.html#intschema");
+        errors.push(err);
+    }
+    if !new_name.eq_ignore_ascii_case(target) && ctx.table(new_name).is_some() {
+        let mut err = ctx.err(
+            "Rename to existing table",
+            &format!("a table or view named {new_name} already exists, sqlite rejects renaming {target} to it"),
+            &node.t,
+            Rule::Quirk,
+        );
+        err.doc_url = Some("https://www.sqlite.org/lang_altertable.html#alter_table_rename");
+        errors.push(err);
+    }
+    let (columns, required) = ctx
+        .table(target)
+        .map(|t| (t.columns.clone(), t.required.clone()))
+        .unwrap_or_default();
+    ctx.remove_table(target);
+    let table = ctx.add_table(new_name);
+    table.columns = columns;
+    table.required = required;
+    errors
+}
+
+/// emits [Rule::SqliteUnsupported] for subqueries and references to tables other than table in
+/// the CHECK constraints of column, these may only reference the columns of the current row, see:
+/// https://www.sqlite.org/lang_createtable.html#ckconst
+fn check_constraints(column: &ColumnDef, table: &str, ctx: &Context) -> Vec<Error> {
+    let mut errors = vec![];
+    for constraint in &column.constraints {
+        if let ColumnConstraint::Check(expr) = &constraint.constraint {
+            check_constraint(expr, table, ctx, &mut errors);
+        }
+    }
+    errors
+}
+
+/// walks node for [check_constraints], subqueries are reported as a whole and not descended into
+fn check_constraint(node: &dyn Node, table: &str, ctx: &Context, errors: &mut Vec<Error>) {
+    let subquery = |t: &Token| {
+        let mut err = ctx.err(
+            "Subquery in CHECK constraint",
+            "CHECK constraints may not contain subqueries, only the columns of the current row are available",
+            t,
+            Rule::SqliteUnsupported,
+        );
+        err.doc_url = Some("https://www.sqlite.org/lang_createtable.html#ckconst");
+        err
+    };
+    let reference = |other: String, t: &Token| {
+        let mut err = ctx.err(
+            "Table reference in CHECK constraint",
+            &format!("CHECK constraints may not reference other tables, such as {other}"),
+            t,
+            Rule::SqliteUnsupported,
+        );
+        err.doc_url = Some("https://www.sqlite.org/lang_createtable.html#ckconst");
+        err
+    };
+
+    let any = node.as_any();
+    if any.is::<SubqueryExpr>() || any.is::<ExistsExpr>() {
+        errors.push(subquery(node.token()));
+        return;
+    }
+    if let Some(in_expr) = any.downcast_ref::<InExpr>() {
+        match &in_expr.body {
+            InBody::Subquery(_) => errors.push(subquery(&in_expr.t)),
+            InBody::Table(other) => errors.push(reference(
+                match other {
+                    SchemaTableContainer::Table(table) => table.clone(),
+                    SchemaTableContainer::SchemaAndTable { schema, table } => {
+                        format!("{schema}.{table}")
+                    }
+                },
+                &in_expr.t,
+            )),
+            InBody::List(list) => {
+                for expr in list {
+                    check_constraint(expr, table, ctx, errors);
+                }
+            }
+        }
+        check_constraint(in_expr.operand.as_ref(), table, ctx, errors);
+        return;
+    }
+    // the columns of the current row may be qualified by the name of its table
+    if let Some(expr) = any.downcast_ref::<Expr>()
+        && let Some(other) = &expr.table
+        && !other.eq_ignore_ascii_case(table)
+    {
+        errors.push(reference(
+            match &expr.schema {
+                Some(schema) => format!("{schema}.{other}"),
+                None => other.clone(),
+            },
+            &expr.t,
+        ));
+    }
+    for child in node.children() {
+        check_constraint(child, table, ctx, errors);
+    }
+}
+
+/// a column declared NOT NULL without a DEFAULT requires a value on each insert, generated
+/// columns compute their value and are thus never required, neither are INTEGER PRIMARY KEY
+/// columns, sqlite assigns these as aliases of the rowid, see:
+/// https://www.sqlite.org/lang_createtable.html#rowid
+fn is_required(column: &ColumnDef) -> bool {
+    let mut not_null = false;
+    for constraint in &column.constraints {
+        match constraint.constraint {
+            ColumnConstraint::NotNull { .. } => not_null = true,
+            ColumnConstraint::Default { .. }
+            | ColumnConstraint::Generated { .. }
+            | ColumnConstraint::As { .. } => return false,
+            ColumnConstraint::PrimaryKey { asc_desc, .. }
+                if matches!(column.type_name, Some(SqliteStorageClass::Integer))
+                    && asc_desc != Some(Keyword::DESC) =>
+            {
+                return false;
+            }
+            _ => (),
+        }
+    }
+    not_null
+}
+
+/// emits [Rule::Quirk] for INSERT ... DEFAULT VALUES into tables with columns requiring a value,
+/// see [is_required]
+pub fn insert(node: &Insert, ctx: &mut Context) -> Vec<Error> {
+    if !matches!(node.source, InsertSource::DefaultValues) {
+        return vec![];
+    }
+    let Some(table) = ctx.table(node.table.table()) else {
+        return vec![];
+    };
+    if table.required.is_empty() {
+        return vec![];
+    }
+    let mut err = ctx.err(
+        "DEFAULT VALUES without defaults",
+        &format!(
+            "{} declares {} NOT NULL without a DEFAULT, inserting DEFAULT VALUES fails with a NOT NULL constraint violation",
+            table.name,
+            table.required.join(", ")
+        ),
+        &node.t,
+        Rule::Quirk,
+    );
+    err.doc_url = Some("https://www.sqlite.org/lang_insert.html");
+    vec![err]
+}
+
+/// registers the view in [Context::tables]
+pub fn create_view(node: &CreateView, ctx: &mut Context) -> Vec<Error> {
+    ctx.add_table(node.name.table()).columns = node.columns.clone();
+    vec![]
+}
+
+/// registers the table, its columns and the columns requiring a value (see [is_required]) in
+/// [Context::tables], emits [Rule::SqliteUnsupported] for CHECK constraints of the table and its
+/// columns, see [check_constraints]
+pub fn create_table(node: &CreateTable, ctx: &mut Context) -> Vec<Error> {
+    let target = node.name.table();
+    let mut errors: Vec<Error> = node
+        .columns
+        .iter()
+        .flat_map(|column| check_constraints(column, target, ctx))
+        .collect();
+    // a PRIMARY KEY table constraint on a single INTEGER column aliases the rowid, just like the
+    // column constraint
+    let mut rowid_alias = None;
+    for constraint in &node.constraints {
+        match &constraint.constraint {
+            TableConstraint::Check(expr) => {
+                check_constraint(expr.as_ref(), target, ctx, &mut errors)
+            }
+            TableConstraint::PrimaryKey { columns, .. } if columns.len() == 1 => {
+                rowid_alias = columns[0].expr.column.clone();
+            }
+            _ => (),
+        }
+    }
+
+    let table = ctx.add_table(target);
+    table.columns = node.columns.iter().map(|c| c.name.clone()).collect();
+    table.required = node
+        .columns
+        .iter()
+        .filter(|c| {
+            is_required(c)
+                && !(matches!(c.type_name, Some(SqliteStorageClass::Integer))
+                    && rowid_alias
+                        .as_ref()
+                        .is_some_and(|alias| alias.eq_ignore_ascii_case(&c.name)))
+        })
+        .map(|c| c.name.clone())
+        .collect();
+    errors
+}
+
+/// registers the virtual table in [Context::tables]
+pub fn create_virtual_table(node: &CreateVirtualTable, ctx: &mut Context) -> Vec<Error> {
+    ctx.add_table(node.name.table());
+    vec![]
+}
+
+/// removes dropped tables and views from [Context::tables]
+pub fn drop(node: &Drop, ctx: &mut Context) -> Vec<Error> {
+    if matches!(node.ttype, Keyword::TABLE | Keyword::VIEW) {
+        ctx.remove_table(node.argument.table());
+    }
+    vec![]
+}
+
+/// collects the tokens of the window names referenced by the window definitions nested in node,
+/// without descending into nested selects, since these define their own windows
+fn window_references<'a>(node: &'a dyn Node, references: &mut Vec<&'a Token>) {
+    for child in node.children() {
+        match child.name() {
+            "Select" => {}
+            "WindowDefn" => {
+                // a window definition starts at the name of the window it references, if any
+                if let Type::Ident(_) = child.token().ttype {
+                    references.push(child.token());
+                }
+                window_references(child, references);
+            }
+            _ => window_references(child, references),
+        }
+    }
+}
+
+/// emits [Rule::Syntax] for windows referenced via OVER window_name or as a base window, that are
+/// not defined in the WINDOW clause of the select
+pub fn select(node: &Select, ctx: &mut Context) -> Vec<Error> {
+    let mut references = vec![];
+    window_references(node, &mut references);
+    references
+        .into_iter()
+        .filter_map(|t| match &t.ttype {
+            Type::Ident(name)
+                if !node
+                    .windows
+                    .iter()
+                    .any(|w| w.name.eq_ignore_ascii_case(name)) =>
+            {
+                let mut err = ctx.err(
+                    "Unknown window",
+                    &format!("window {name} is not defined in the WINDOW clause of this SELECT"),
+                    t,
+                    Rule::Syntax,
+                );
+                err.doc_url = Some("https://www.sqlite.org/lang_select.html#the_window_clause");
+                Some(err)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// marks the transaction as started
+pub fn begin(node: &Begin, ctx: &mut Context) -> Vec<Error> {
+    ctx.transaction = Transaction::Open(node.t.clone());
+    vec![]
+}
+
+/// marks the transaction as ended, emits [Rule::Quirk] if savepoints are still open, COMMIT
+/// releases them without a RELEASE
+pub fn commit(node: &Commit, ctx: &mut Context) -> Vec<Error> {
+    let mut errors = vec![];
+    if !ctx.save_points.is_empty() {
+        let mut err = ctx.err(
+            "COMMIT with open savepoints",
+            &format!(
+                "the transaction stack is {}, COMMIT releases all savepoints and commits the whole transaction, RELEASE the savepoints before the COMMIT",
+                transaction_stack(ctx)
+            ),
+            &node.t,
+            Rule::Quirk,
+        );
+        err.doc_url = Some("https://www.sqlite.org/lang_savepoint.html");
+        errors.push(err);
+    }
+    ctx.save_points.clear();
+    ctx.transaction = Transaction::Ended(node.t.clone());
+    errors
+}
+
+/// marks the transaction as ended, ROLLBACK TO a savepoint keeps the transaction and the
+/// savepoint open, but cancels all savepoints opened after it
+pub fn rollback(node: &Rollback, ctx: &mut Context) -> Vec<Error> {
+    match &node.save_point {
+        None => {
+            ctx.save_points.clear();
+            ctx.transaction = Transaction::Ended(node.t.clone());
+        }
+        Some(name) => {
+            if let Some(i) = ctx
+                .save_points
+                .iter()
+                .rposition(|s| s.name.eq_ignore_ascii_case(name))
+            {
+                ctx.save_points.truncate(i + 1);
+            }
+        }
+    }
+    vec![]
+}
+
+/// pushes the savepoint onto the transaction stack
+pub fn savepoint(node: &Savepoint, ctx: &mut Context) -> Vec<Error> {
+    ctx.save_points.push(SavePoint {
+        name: node.savepoint_name.clone(),
+        t: node.t.clone(),
+    });
+    vec![]
+}
+
+/// removes the innermost savepoint named like the released one and all savepoints opened after
+/// it, emits [Rule::Quirk] if no such savepoint is open, sqlite rejects the RELEASE
+pub fn release(node: &Release, ctx: &mut Context) -> Vec<Error> {
+    let name = &node.savepoint_name;
+    if let Some(i) = ctx
+        .save_points
+        .iter()
+        .rposition(|s| s.name.eq_ignore_ascii_case(name))
+    {
+        ctx.save_points.truncate(i);
+        return vec![];
+    }
+    let note = match (&ctx.transaction, ctx.save_points.is_empty()) {
+        (Transaction::Open(begin), true) => format!(
+            "the top of the transaction stack is the BEGIN on line {}, not a savepoint named {name}, a transaction started by BEGIN is ended by COMMIT",
+            begin.line + 1
+        ),
+        (_, true) => {
+            format!("the transaction stack is empty, no savepoint named {name} was opened")
+        }
+        (_, false) => format!(
+            "no savepoint named {name} is open, the transaction stack is {}",
+            transaction_stack(ctx)
+        ),
+    };
+    let mut err = ctx.err("RELEASE of unknown savepoint", &note, &node.t, Rule::Quirk);
+    err.doc_url = Some("https://www.sqlite.org/lang_savepoint.html");
+    vec![err]
+}
+
+/// describes the open transaction and savepoints from the outermost to the innermost, for the
+/// notes of errors concerning them
+fn transaction_stack(ctx: &Context) -> String {
+    let mut stack = vec![];
+    if let Transaction::Open(begin) = &ctx.transaction {
+        stack.push(format!("BEGIN on line {}", begin.line + 1));
+    }
+    stack.extend(
+        ctx.save_points
+            .iter()
+            .map(|s| format!("SAVEPOINT {} on line {}", s.name, s.t.line + 1)),
+    );
+    stack.join(" > ")
+}
+
+/// emits [Rule::Quirk] for statements modifying the database or its schema between the end of a
+/// transaction and the start of the next, these run in their own implicit transaction
+fn stranded(node: &dyn Node, ctx: &Context) -> Option<Error> {
+    let Transaction::Ended(end) = &ctx.transaction else {
+        return None;
+    };
+    if !matches!(
+        node.name(),
+        "Insert"
+            | "Update"
+            | "Delete"
+            | "Drop"
+            | "Alter"
+            | "CreateTable"
+            | "CreateView"
+            | "CreateVirtualTable"
+            | "CreateTrigger"
+    ) {
+        return None;
+    }
+    let ended_by = match &end.ttype {
+        Type::Keyword(keyword) => (*keyword).into(),
+        _ => "COMMIT",
+    };
+    let mut err = ctx.err(
+        "Statement outside of transaction",
+        &format!(
+            "the transaction was ended by {ended_by} on line {}, this statement runs in its own implicit transaction, move it before the {ended_by} or start a new transaction with BEGIN",
+            end.line + 1
+        ),
+        node.token(),
+        Rule::Quirk,
+    );
+    err.doc_url = Some("https://www.sqlite.org/lang_transaction.html");
+    Some(err)
+}
+
+/// analyses the statements of a file in order, in addition to [Node::analyse] of each statement,
+/// checks concerning the sequence of statements are performed
+pub fn statements(ast: &[Box<dyn Node>], ctx: &mut Context) -> Vec<Error> {
+    let mut errors = vec![];
+    for node in ast {
+        errors.extend(stranded(node.as_ref(), ctx));
+        errors.append(&mut node.analyse(ctx));
+    }
+    errors
+}
@@ -27,7 +27,11 @@ impl_field_serializable_with_serde_to_value!(
     SqliteStorageClass,
     SchemaTableContainer,
     Type,
-    PragmaInvocation
+    PragmaInvocation,
+    TriggerTiming,
+    TriggerEvent,
+    JoinOperator,
+    CompoundOperator
 );
 
 impl FieldSerializable for ColumnConstraint {
@@ -71,7 +75,11 @@ impl FieldSerializable for ColumnConstraint {
             ColumnConstraint::Check(expr) => serde_json::json!({
                 "expr": expr.as_serializable(),
             }),
-            ColumnConstraint::Default { expr, literal } => {
+            ColumnConstraint::Default {
+                expr,
+                literal,
+                parenthesized,
+            } => {
                 serde_json::json!({
                     "expr": match expr {
                         Some(e) => e.as_serializable(),
@@ -81,6 +89,7 @@ impl FieldSerializable for ColumnConstraint {
                         Some(e) => e.as_serializable(),
                         None => serde_json::Value::Null,
                     },
+                    "parenthesized": parenthesized,
                 })
             }
             ColumnConstraint::Generated {
@@ -101,15 +110,168 @@ impl FieldSerializable for ColumnConstraint {
     }
 }
 
+impl FieldSerializable for NamedColumnConstraint {
+    fn field_as_serializable(&self) -> serde_json::Value {
+        serde_json::json!({
+            "name": self.name,
+            "constraint": self.constraint.field_as_serializable(),
+        })
+    }
+}
+
+impl FieldSerializable for TableConstraint {
+    fn field_as_serializable(&self) -> serde_json::Value {
+        match self {
+            TableConstraint::PrimaryKey {
+                columns,
+                on_conflict,
+            } => serde_json::json!({
+                "primary_key": {
+                    "columns": columns.field_as_serializable(),
+                    "on_conflict": on_conflict,
+                }
+            }),
+            TableConstraint::Unique {
+                columns,
+                on_conflict,
+            } => serde_json::json!({
+                "unique": {
+                    "columns": columns.field_as_serializable(),
+                    "on_conflict": on_conflict,
+                }
+            }),
+            TableConstraint::Check(expr) => serde_json::json!({
+                "check": {
+                    "expr": expr.as_serializable(),
+                }
+            }),
+            TableConstraint::ForeignKey { columns, clause } => serde_json::json!({
+                "foreign_key": {
+                    "columns": columns,
+                    "foreign_key_clause": clause,
+                }
+            }),
+        }
+    }
+}
+
+impl FieldSerializable for NamedTableConstraint {
+    fn field_as_serializable(&self) -> serde_json::Value {
+        serde_json::json!({
+            "name": self.name,
+            "constraint": self.constraint.field_as_serializable(),
+        })
+    }
+}
+
+impl FieldSerializable for InBody {
+    fn field_as_serializable(&self) -> serde_json::Value {
+        match self {
+            InBody::List(list) => serde_json::json!({ "list": list.field_as_serializable() }),
+            InBody::Subquery(subquery) => serde_json::json!({
+                "subquery": subquery.field_as_serializable()
+            }),
+            InBody::Table(table) => serde_json::json!({ "table": table }),
+        }
+    }
+}
+
+impl FieldSerializable for FrameBound {
+    fn field_as_serializable(&self) -> serde_json::Value {
+        match self {
+            FrameBound::UnboundedPreceding => serde_json::json!("unbounded_preceding"),
+            FrameBound::Preceding(expr) => serde_json::json!({
+                "preceding": expr.as_serializable()
+            }),
+            FrameBound::CurrentRow => serde_json::json!("current_row"),
+            FrameBound::Following(expr) => serde_json::json!({
+                "following": expr.as_serializable()
+            }),
+            FrameBound::UnboundedFollowing => serde_json::json!("unbounded_following"),
+        }
+    }
+}
+
+impl FieldSerializable for ResultColumn {
+    fn field_as_serializable(&self) -> serde_json::Value {
+        match self {
+            ResultColumn::Star => serde_json::json!("*"),
+            ResultColumn::TableStar(table) => serde_json::json!({ "table_star": table }),
+            ResultColumn::Expr { expr, alias } => serde_json::json!({
+                "expr": expr.as_serializable(),
+                "alias": alias,
+            }),
+        }
+    }
+}
+
+impl FieldSerializable for TableOrSubquery {
+    fn field_as_serializable(&self) -> serde_json::Value {
+        match self {
+            TableOrSubquery::Table { name, alias } => serde_json::json!({
+                "table": name,
+                "alias": alias,
+            }),
+            TableOrSubquery::Subquery { select, alias } => serde_json::json!({
+                "subquery": select.as_serializable(),
+                "alias": alias,
+            }),
+        }
+    }
+}
+
+impl FieldSerializable for InsertSource {
+    fn field_as_serializable(&self) -> serde_json::Value {
+        match self {
+            InsertSource::Values(rows) => {
+                serde_json::json!({ "values": rows.field_as_serializable() })
+            }
+            InsertSource::Select(select) => {
+                serde_json::json!({ "select": select.as_serializable() })
+            }
+            InsertSource::DefaultValues => serde_json::json!("default_values"),
+        }
+    }
+}
+
+impl FieldSerializable for UpsertAction {
+    fn field_as_serializable(&self) -> serde_json::Value {
+        match self {
+            UpsertAction::Nothing => serde_json::json!("nothing"),
+            UpsertAction::Update { set, where_clause } => serde_json::json!({
+                "update": {
+                    "set": set.field_as_serializable(),
+                    "where_clause": where_clause.field_as_serializable(),
+                }
+            }),
+        }
+    }
+}
+
+impl FieldSerializable for JoinConstraint {
+    fn field_as_serializable(&self) -> serde_json::Value {
+        match self {
+            JoinConstraint::On(expr) => serde_json::json!({ "on": expr.as_serializable() }),
+            JoinConstraint::Using(columns) => serde_json::json!({ "using": columns }),
+        }
+    }
+}
+
+impl FieldSerializable for usize {
+    fn field_as_serializable(&self) -> serde_json::Value {
+        serde_json::json!(self)
+    }
+}
+
 impl FieldSerializable for Token {
     fn field_as_serializable(&self) -> serde_json::Value {
         serde_json::to_value(&self.ttype).unwrap()
     }
 }
 
-impl FieldSerializable for Box<dyn Node> {
+impl<T: Node + ?Sized> FieldSerializable for Box<T> {
     fn field_as_serializable(&self) -> serde_json::Value {
-        self.as_serializable()
+        self.as_ref().as_serializable()
     }
 }
 
@@ -127,3 +289,12 @@ impl<T: FieldSerializable> FieldSerializable for Vec<T> {
         serde_json::Value::Array(self.iter().map(|n| n.field_as_serializable()).collect())
     }
 }
+
+impl<A: FieldSerializable, B: FieldSerializable> FieldSerializable for (A, B) {
+    fn field_as_serializable(&self) -> serde_json::Value {
+        serde_json::json!([
+            self.0.field_as_serializable(),
+            self.1.field_as_serializable()
+        ])
+    }
+}
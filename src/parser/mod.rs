@@ -4,18 +4,29 @@ use nodes::{BindParameter, SchemaTableContainer};
 use proc::trace;
 
 use crate::{
-    error::{Error, ImprovedLine},
+    error::{Error, ErrorSink, ImprovedLine},
     parser::nodes::{
         ColumnConstraint, ForeignKeyAction, ForeignKeyClause, ForeignKeyMatch, Pragma,
+        TriggerEvent, TriggerTiming,
+    },
+    types::{
+        Keyword, Token, Type,
+        dialect::{self, Position},
+        functions, pragma,
+        rules::Rule,
+        storage::SqliteStorageClass,
     },
-    types::{Keyword, Token, Type, rules::Rule, storage::SqliteStorageClass},
 };
 
+/// semantic analysis of all nodes and contained types, performed after parsing
+pub mod analyse;
 /// implement serialisation manually for all nodes and contained types
 pub mod debug;
 /// nodes holds all abstract syntax tree nodes, the node! macro, the lua preparation for the plugin execution and the sqleibniz analysis
 pub mod nodes;
 mod tests;
+/// traversal of all nodes and contained types
+pub mod visit;
 
 // this sucks but is necessary to track the call depth for indentation when printing the parser
 // stack
@@ -27,8 +38,24 @@ thread_local! {
 pub struct Parser<'a> {
     pos: usize,
     tokens: Vec<Token>,
+    /// zero width token directly after the last token, returned by [Parser::cur] once all tokens
+    /// are consumed, so errors at the end of the input point to the end of the input
+    eof: Token,
     name: &'a str,
+    /// called for each error as soon as it is found, see [Parser::on_error]
+    on_error: Option<ErrorSink<'a>>,
     pub errors: Vec<Error>,
+    /// maximum nesting depth of expressions, subqueries and CASE, exceeding it results in a
+    /// Rule::Syntax error instead of a stack overflow, defaults to [Parser::MAX_EXPR_DEPTH]
+    pub max_expr_depth: usize,
+    /// current nesting depth of expressions, subqueries and CASE, see [Parser::nested]
+    expr_depth: usize,
+    /// rules disabled via `@sqleibniz::disable:<rule>` for the current statement, errors of these
+    /// rules are not emitted
+    disabled: Vec<Rule>,
+    /// pragma names known in addition to the pragmas of sqlite, see
+    /// [crate::types::config::Config::pragmas]
+    pub pragmas: Vec<String>,
 }
 
 /// wrap argument in Some(Box::new(_))
@@ -45,26 +72,64 @@ macro_rules! some_box {
 /// - https://www.sqlite.org/lang.html
 /// - https://www.sqlite.org/lang_expr.html
 impl<'a> Parser<'a> {
+    /// well below SQLITE_MAX_EXPR_DEPTH of 1000, see:
+    /// https://www.sqlite.org/limits.html#max_expr_depth, since the analysis of the resulting
+    /// tree recurses on the regular stack, a single level of nesting may span several counted
+    /// levels, such as a subquery and the expressions inside of it
+    pub const MAX_EXPR_DEPTH: usize = 200;
+    /// remaining stack below which [Parser::nested] switches to a new stack segment, a single
+    /// level of nesting takes up to ~60KiB in unoptimized builds
+    const STACK_RED_ZONE: usize = 256 * 1024;
+    /// size of stack segments allocated by [Parser::nested]
+    const STACK_SEGMENT: usize = 4 * 1024 * 1024;
+
     pub fn new(tokens: Vec<Token>, name: &'a str) -> Parser<'a> {
+        // single character tokens start and end at the same column, the end of input is directly
+        // after them
+        let (line, end) = tokens.last().map_or((0, 0), |last| {
+            (last.line, last.end + usize::from(last.start == last.end))
+        });
+        let eof = Token {
+            ttype: Type::Eof,
+            start: end,
+            end,
+            line,
+        };
         Parser {
             pos: 0,
             name,
             tokens,
+            eof,
+            on_error: None,
             errors: vec![],
+            max_expr_depth: Self::MAX_EXPR_DEPTH,
+            expr_depth: 0,
+            disabled: vec![],
+            pragmas: vec![],
         }
     }
 
-    fn cur(&self) -> &Token {
-        if let Some(tok) = self.tokens.get(self.pos) {
-            tok
-        } else {
-            &Token {
-                ttype: Type::Eof,
-                start: 0,
-                end: 0,
-                line: 0,
-            }
+    /// registers f to be called with each error as soon as the parser finds it, before it is
+    /// appended to [Parser::errors]
+    pub fn on_error(mut self, f: impl FnMut(&Error) + 'a) -> Self {
+        self.on_error = Some(Box::new(f));
+        self
+    }
+
+    /// passes err to [Parser::on_error] and appends it to [Parser::errors], unless its rule is
+    /// disabled for the current statement
+    fn emit(&mut self, err: Error) {
+        if self.disabled.contains(&err.rule) {
+            return;
+        }
+        if let Some(f) = &mut self.on_error {
+            f(&err);
         }
+        self.errors.push(err);
+    }
+
+    fn cur(&self) -> &Token {
+        self.tokens.get(self.pos).unwrap_or(&self.eof)
     }
 
     fn err(&self, msg: impl Into<String>, note: &str, start: &Token, rule: Rule) -> Error {
@@ -72,6 +137,7 @@ impl<'a> Parser<'a> {
             improved_line: None,
             file: self.name.to_string(),
             line: start.line,
+            severity: rule.severity(),
             rule,
             note: note.into(),
             msg: msg.into(),
@@ -83,7 +149,7 @@ impl<'a> Parser<'a> {
 
     fn push_err(&mut self, msg: impl Into<String>, note: &str, start: &Token, rule: Rule) {
         let err = self.err(msg, note, start, rule);
-        self.errors.push(err);
+        self.emit(err);
     }
 
     fn is_eof(&self) -> bool {
@@ -128,13 +194,14 @@ impl<'a> Parser<'a> {
                 err.msg = "Missing semicolon".into();
                 err.note.push_str(", terminate statements with ';'");
                 err.rule = Rule::Semicolon;
+                err.severity = Rule::Semicolon.severity();
                 err.improved_line = Some(ImprovedLine {
                     snippet: ";",
                     start: self.cur().end,
                 });
             }
             err.doc_url = Some("https://www.sqlite.org/syntax/sql-stmt.html");
-            self.errors.push(err);
+            self.emit(err);
         }
         self.advance(); // we advance either way to keep the parser error resistant
     }
@@ -151,7 +218,19 @@ impl<'a> Parser<'a> {
 
     /// checks if current token is semicolon, if not pushes Rule::Syntax
     fn expect_end(&mut self, doc: &'static str) -> Option<()> {
-        if !self.is(Type::Semicolon) {
+        if self.foreign_dialect(Position::TableOption) {
+            self.skip_until_semicolon_or_eof();
+        } else if self.is_keyword(Keyword::RETURNING) {
+            let mut err = self.err(
+                "Unsupported RETURNING",
+                "sqlite only supports a RETURNING clause on INSERT, UPDATE and DELETE statements",
+                &self.cur().clone(),
+                Rule::SqliteUnsupported,
+            );
+            err.doc_url = Some("https://www.sqlite.org/lang_returning.html");
+            self.emit(err);
+            self.skip_until_semicolon_or_eof();
+        } else if !self.is(Type::Semicolon) {
             let cur = self.cur().clone();
             let mut err = self.err(
                 "Unexpected Statement Continuation",
@@ -162,12 +241,41 @@ impl<'a> Parser<'a> {
             if !doc.is_empty() {
                 err.doc_url = Some(doc);
             }
-            self.errors.push(err);
+            self.emit(err);
             self.advance();
         }
         None
     }
 
+    /// checks if the current token is an ident naming a construct of another sql dialect expected
+    /// at position, pushes a Rule::SqliteUnsupported error with the sqlite equivalent if so,
+    /// does not advance
+    fn foreign_dialect(&mut self, position: Position) -> bool {
+        let Type::Ident(name) = &self.cur().ttype else {
+            return false;
+        };
+        let Some(foreign) = dialect::lookup(name, position) else {
+            return false;
+        };
+        // table options are always assignments, ENGINE=InnoDB, this keeps columns named engine
+        // from being flagged
+        if position == Position::TableOption && !self.next_is(Type::Equal) {
+            return false;
+        }
+        let mut err = self.err(
+            format!("{} is {} syntax", foreign.name, foreign.dialect),
+            &format!(
+                "sqlite does not support {} {}: {}",
+                foreign.dialect, foreign.name, foreign.note
+            ),
+            self.cur(),
+            Rule::SqliteUnsupported,
+        );
+        err.doc_url = Some(foreign.doc);
+        self.emit(err);
+        true
+    }
+
     fn consume_ident(
         &mut self,
         doc: &'static str,
@@ -189,7 +297,7 @@ impl<'a> Parser<'a> {
                 Rule::Syntax,
             );
             err.doc_url = Some(doc);
-            self.errors.push(err);
+            self.emit(err);
             self.advance();
             None
         }
@@ -205,6 +313,13 @@ impl<'a> Parser<'a> {
     fn sql_stmt_list(&mut self) -> Vec<Box<dyn nodes::Node>> {
         let mut r = vec![];
         while !self.is_eof() {
+            // disables accumulate until the statement following them ends
+            if let Type::InstructionDisable(rule) = &self.cur().ttype {
+                let rule = rule.clone();
+                self.disabled.push(rule);
+                self.advance();
+                continue;
+            }
             if let Token {
                 ttype: Type::InstructionExpect,
                 ..
@@ -217,17 +332,63 @@ impl<'a> Parser<'a> {
                 if !self.is_eof() {
                     // skip ';'
                     self.consume(Type::Semicolon);
+                    self.disabled.clear();
                     continue;
                 }
             }
+            if let Type::InstructionNode(expected) = &self.cur().ttype {
+                let expected = expected.clone();
+                let instruction = self.cur().clone();
+                self.advance();
+                self.instruction_node(&instruction, &expected, &mut r);
+                self.disabled.clear();
+                continue;
+            }
             if let Some(stmt) = self.sql_stmt_prefix() {
                 r.push(stmt);
             }
             self.consume(Type::Semicolon);
+            self.disabled.clear();
         }
         r
     }
 
+    /// parses the statement following a `@sqleibniz::node(<expected>)` instruction and emits
+    /// [Rule::BadSqleibnizInstruction] if it is not parsed to a node named expected, used to
+    /// assert the parser output in sql fixtures
+    fn instruction_node(
+        &mut self,
+        instruction: &Token,
+        expected: &str,
+        r: &mut Vec<Box<dyn nodes::Node>>,
+    ) {
+        if self.is_eof() {
+            self.push_err(
+                "Missing statement",
+                &format!("`@sqleibniz::node({expected})` has to be followed by a statement"),
+                instruction,
+                Rule::BadSqleibnizInstruction,
+            );
+            return;
+        }
+        // statements failing to parse already produced an error
+        if let Some(stmt) = self.sql_stmt_prefix() {
+            if stmt.name() != expected {
+                self.push_err(
+                    "Unexpected node",
+                    &format!(
+                        "`@sqleibniz::node({expected})` expects the following statement to be a {expected}, got {}",
+                        stmt.name()
+                    ),
+                    instruction,
+                    Rule::BadSqleibnizInstruction,
+                );
+            }
+            r.push(stmt);
+        }
+        self.consume(Type::Semicolon);
+    }
+
     #[cfg_attr(feature = "trace", trace)]
     fn sql_stmt_prefix(&mut self) -> Option<Box<dyn nodes::Node>> {
         let r: Option<Box<dyn nodes::Node>> = match self.cur().ttype {
@@ -237,7 +398,8 @@ impl<'a> Parser<'a> {
                 self.advance();
 
                 // path for EXPLAIN->QUERY->PLAN
-                if self.is(Type::Keyword(Keyword::QUERY)) {
+                let query_plan = self.is(Type::Keyword(Keyword::QUERY));
+                if query_plan {
                     self.advance();
                     self.consume(Type::Keyword(Keyword::PLAN));
                 }
@@ -245,9 +407,15 @@ impl<'a> Parser<'a> {
                 // else path is EXPLAIN->*_stmt
                 some_box!(nodes::Explain {
                     t,
-                    child: self.sql_stmt()?,
+                    query_plan,
+                    child: if self.is_keyword(Keyword::WITH) {
+                        self.with_stmt()?
+                    } else {
+                        self.sql_stmt()?
+                    },
                 })
             }
+            Type::Keyword(Keyword::WITH) => self.with_stmt(),
             _ => self.sql_stmt(),
         };
 
@@ -272,6 +440,12 @@ impl<'a> Parser<'a> {
             Type::Keyword(Keyword::COMMIT) | Type::Keyword(Keyword::END) => self.commit_stmt(),
             Type::Keyword(Keyword::BEGIN) => self.begin_stmt(),
             Type::Keyword(Keyword::VACUUM) => self.vacuum_stmt(),
+            Type::Keyword(Keyword::CREATE) => self.create_stmt(),
+            Type::Keyword(Keyword::SELECT) => self.select_stmt(),
+            Type::Keyword(Keyword::INSERT) | Type::Keyword(Keyword::REPLACE) => self.insert_stmt(),
+            Type::Keyword(Keyword::DELETE) => self.delete_stmt(),
+            Type::Keyword(Keyword::UPDATE) => self.update_stmt(),
+            Type::Keyword(Keyword::VALUES) => self.values_stmt(),
 
             // statement should not start with a semicolon 󰚌
             Type::Semicolon => {
@@ -288,7 +462,8 @@ impl<'a> Parser<'a> {
             // explicitly disallowing literals at this point: results in clearer and more
             // understandable error messages
             Type::String(_)
-            | Type::Number(_)
+            | Type::Integer(_)
+            | Type::Float(_)
             | Type::Blob(_)
             | Type::Keyword(Keyword::NULL)
             | Type::Boolean(_)
@@ -302,7 +477,7 @@ impl<'a> Parser<'a> {
                     Rule::Syntax,
                 );
                 err.doc_url = Some("https://www.sqlite.org/syntax/sql-stmt.html");
-                self.errors.push(err);
+                self.emit(err);
                 self.advance();
                 None
             }
@@ -320,7 +495,7 @@ impl<'a> Parser<'a> {
                         Rule::UnknownKeyword,
                     );
                     err.doc_url = Some("https://sqlite.org/lang_keywords.html");
-                    self.errors.push(err);
+                    self.emit(err);
                 } else {
                     self.push_err(
                         "Unknown Keyword",
@@ -367,789 +542,3602 @@ impl<'a> Parser<'a> {
     //
     // }
 
-    /// https://www.sqlite.org/lang_createindex.html
+    /// https://www.sqlite.org/lang_select.html
+    /// https://www.sqlite.org/lang_delete.html
     #[cfg_attr(feature = "trace", trace)]
-    fn create_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
-        todo!("Parser::create_stmt");
+    fn delete_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
+        let t = self.cur().clone();
+        // skip DELETE
+        self.advance();
+
+        if !self.is_keyword(Keyword::FROM) {
+            let mut err = self.err(
+                "Missing FROM",
+                &format!(
+                    "DELETE requires FROM before the table to delete from, as in DELETE FROM <table>, got {:?}",
+                    self.cur().ttype
+                ),
+                self.cur(),
+                Rule::Syntax,
+            );
+            err.doc_url = Some("https://www.sqlite.org/lang_delete.html");
+            self.emit(err);
+            // DELETE table is a common mistake, recover by treating it as DELETE FROM table
+            if !matches!(self.cur().ttype, Type::Ident(_) | Type::String(_)) {
+                self.advance();
+                return None;
+            }
+        } else {
+            self.advance();
+        }
+
+        let table = self.schema_table_container(None)?;
+
+        let mut where_clause = None;
+        if self.is_keyword(Keyword::WHERE) {
+            self.advance();
+            where_clause = Some(self.expr()?);
+        }
+
+        let returning = self.returning_clause()?;
+
+        self.expect_end("https://www.sqlite.org/lang_delete.html");
+
+        some_box!(nodes::Delete {
+            t,
+            table,
+            where_clause,
+            returning,
+        })
     }
 
-    /// https://www.sqlite.org/pragma.html
+    /// https://www.sqlite.org/lang_update.html
     #[cfg_attr(feature = "trace", trace)]
-    fn pragma_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
+    fn update_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
         let t = self.cur().clone();
-
-        // skip PRAGMA
+        // skip UPDATE
         self.advance();
 
-        // PRAGMA needs a target name
-        let Some(schema_and_pragma) = self.schema_table_container(Some("pragma")) else {
-            return None;
-        };
-
-        let pragma = if self.is(Type::Semicolon) {
-            Pragma {
-                t,
-                name: schema_and_pragma,
-                invocation: nodes::PragmaInvocation::Query,
-            }
-        } else if self.is(Type::Equal) {
+        let mut or = None;
+        if self.is_keyword(Keyword::OR) {
             self.advance();
             match self.cur().ttype {
-                Type::String(_) | Type::Number(_) | Type::Ident(_) | Type::Keyword(_) => {}
-                _ => {
-                    let cur = self.cur().clone();
-                    self.push_err("Bad pragma value", &format!("A pragmas assignment value has to be either String, Number, Ident or a Keyword, got {:?} instead", cur.ttype), &cur, Rule::Syntax,);
+                Type::Keyword(
+                    keyword @ (Keyword::ROLLBACK
+                    | Keyword::ABORT
+                    | Keyword::FAIL
+                    | Keyword::IGNORE
+                    | Keyword::REPLACE),
+                ) => {
+                    or = Some(keyword);
                     self.advance();
                 }
-            }
-            let p = Pragma {
-                t,
-                name: schema_and_pragma,
-                invocation: nodes::PragmaInvocation::Assign {
-                    value: self.cur().clone(),
-                },
-            };
-            self.advance();
-            p
-        } else if self.is(Type::BraceLeft) {
-            self.advance();
-            match self.cur().ttype {
-                Type::String(_) | Type::Number(_) | Type::Ident(_) | Type::Keyword(_) => {}
                 _ => {
-                    let cur = self.cur().clone();
-                    self.push_err("Bad pragma value", &format!("A pragmas call value has to be either String, Number, Ident or a Keyword, got {:?} instead", cur.ttype), &cur, Rule::Syntax,);
+                    let mut err = self.err(
+                        "Unexpected Token",
+                        &format!(
+                            "Wanted either ROLLBACK, ABORT, FAIL, IGNORE or REPLACE after UPDATE OR, got {:?}.",
+                            self.cur().ttype
+                        ),
+                        self.cur(),
+                        Rule::Syntax,
+                    );
+                    err.doc_url = Some("https://www.sqlite.org/lang_update.html");
+                    self.emit(err);
                     self.advance();
                 }
             }
-            let p = Pragma {
-                t,
-                name: schema_and_pragma,
-                invocation: nodes::PragmaInvocation::Call {
-                    value: self.cur().clone(),
-                },
-            };
+        }
+
+        let table = self.schema_table_container(None)?;
+
+        let mut alias = None;
+        if self.is_keyword(Keyword::AS) {
             self.advance();
-            self.consume(Type::BraceRight);
-            p
-        } else {
-            let cur = self.cur().clone();
-            self.push_err(
-                "Bad pragma value",
-                &format!(
-                    "A pragmas rhs value has to be either an assignment via '=', a call via '(<arg>)' or simply be a query, got {:?} instead",
-                    cur.ttype
-                ),
-                &cur,
-                Rule::Syntax,
-            );
+            alias = Some(self.consume_ident("https://www.sqlite.org/lang_update.html", "alias")?);
+        }
+
+        let set = self.set_clause("https://www.sqlite.org/lang_update.html")?;
+
+        let mut from = None;
+        if self.is_keyword(Keyword::FROM) {
             self.advance();
-            return None;
-        };
+            from = Some(self.join_clause()?);
+        }
 
-        self.expect_end("https://www.sqlite.org/pragma.html");
+        let mut where_clause = None;
+        if self.is_keyword(Keyword::WHERE) {
+            self.advance();
+            where_clause = Some(self.expr()?);
+        }
 
-        some_box!(pragma)
+        let returning = self.returning_clause()?;
+
+        self.expect_end("https://www.sqlite.org/lang_update.html");
+
+        some_box!(nodes::Update {
+            t,
+            or,
+            table,
+            alias,
+            set,
+            from,
+            where_clause,
+            returning,
+        })
     }
 
-    /// https://www.sqlite.org/lang_altertable.html
+    /// parses the optional RETURNING clause of INSERT, UPDATE and DELETE, see:
+    /// https://www.sqlite.org/lang_returning.html
     #[cfg_attr(feature = "trace", trace)]
-    fn alter_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
-        let mut a = nodes::Alter {
-            t: self.cur().clone(),
-            target: SchemaTableContainer::Table(String::new()),
-            rename_to: None,
-            rename_column_target: None,
-            new_column_name: None,
-            add_column: None,
-            drop_column: None,
-        };
-
+    fn returning_clause(&mut self) -> Option<Option<Vec<nodes::ResultColumn>>> {
+        if !self.is_keyword(Keyword::RETURNING) {
+            return Some(None);
+        }
         self.advance();
-        self.consume(Type::Keyword(Keyword::TABLE));
+        let mut columns = vec![self.result_column()?];
+        while self.is(Type::Comma) {
+            self.advance();
+            columns.push(self.result_column()?);
+        }
+        Some(Some(columns))
+    }
 
-        a.target = self.schema_table_container(None)?;
+    /// https://www.sqlite.org/lang_select.html#the_values_clause
+    #[cfg_attr(feature = "trace", trace)]
+    fn values_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
+        let values = self.values()?;
+        self.expect_end("https://www.sqlite.org/lang_select.html#the_values_clause");
+        some_box!(values)
+    }
 
-        match self.cur().ttype {
-            Type::Keyword(Keyword::RENAME) => {
+    /// parses VALUES (expr, ...), ... as a select-stmt, recording the row lengths if they differ
+    #[cfg_attr(feature = "trace", trace)]
+    fn values(&mut self) -> Option<nodes::Values> {
+        let t = self.cur().clone();
+        let rows = self.values_rows()?;
+
+        let mut arities: Vec<usize> = vec![];
+        for row in &rows {
+            if !arities.contains(&row.len()) {
+                arities.push(row.len());
+            }
+        }
+        if arities.len() == 1 {
+            arities.clear();
+        }
+
+        Some(nodes::Values { t, rows, arities })
+    }
+
+    /// parses VALUES (expr, ...), ... into its rows
+    #[cfg_attr(feature = "trace", trace)]
+    fn values_rows(&mut self) -> Option<Vec<Vec<nodes::Expr>>> {
+        self.consume_keyword(Keyword::VALUES);
+        let mut rows = vec![];
+        loop {
+            self.consume(Type::BraceLeft);
+            let mut row = vec![self.expr()?];
+            while self.is(Type::Comma) {
                 self.advance();
-                if self.is(Type::Keyword(Keyword::TO)) {
-                    // RENAME TO <new_table_name>
-                    self.advance();
-                    let new_table_name = self.consume_ident(
-                        "https://www.sqlite.org/lang_altertable.html",
-                        "new_table_name",
-                    )?;
-                    a.rename_to = Some(new_table_name);
-                } else {
-                    if self.is(Type::Keyword(Keyword::COLUMN)) {
-                        self.advance();
-                    }
+                row.push(self.expr()?);
+            }
+            self.consume(Type::BraceRight);
+            rows.push(row);
 
-                    a.rename_column_target = self.consume_ident(
-                        "https://www.sqlite.org/lang_altertable.html",
-                        "column_name",
-                    );
-                    self.consume(Type::Keyword(Keyword::TO));
-                    a.new_column_name = self.consume_ident(
-                        "https://www.sqlite.org/lang_altertable.html",
-                        "column_name",
-                    );
-                }
+            if !self.is(Type::Comma) {
+                break;
             }
-            Type::Keyword(Keyword::ADD) => {
+            let comma = self.cur().clone();
+            self.advance();
+            if !self.is(Type::BraceLeft) {
+                let mut err = self.err(
+                    "Trailing comma",
+                    "A comma after a row of VALUES has to be followed by another row, remove the trailing comma",
+                    &comma,
+                    Rule::Syntax,
+                );
+                err.doc_url = Some("https://www.sqlite.org/lang_select.html#the_values_clause");
+                self.emit(err);
+                return None;
+            }
+        }
+        Some(rows)
+    }
+
+    /// https://www.sqlite.org/lang_insert.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn insert_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
+        let t = self.cur().clone();
+        let mut or = None;
+
+        if self.is_keyword(Keyword::REPLACE) {
+            // REPLACE is an alias for INSERT OR REPLACE
+            or = Some(Keyword::REPLACE);
+            self.advance();
+        } else {
+            // skip INSERT
+            self.advance();
+            if self.is_keyword(Keyword::OR) {
                 self.advance();
-                if self.is(Type::Keyword(Keyword::COLUMN)) {
-                    self.advance();
+                match self.cur().ttype {
+                    Type::Keyword(
+                        keyword @ (Keyword::ROLLBACK
+                        | Keyword::ABORT
+                        | Keyword::FAIL
+                        | Keyword::IGNORE
+                        | Keyword::REPLACE),
+                    ) => {
+                        or = Some(keyword);
+                        self.advance();
+                    }
+                    _ => {
+                        let mut err = self.err(
+                            "Unexpected Token",
+                            &format!(
+                                "Wanted either ROLLBACK, ABORT, FAIL, IGNORE or REPLACE after INSERT OR, got {:?}.",
+                                self.cur().ttype
+                            ),
+                            self.cur(),
+                            Rule::Syntax,
+                        );
+                        err.doc_url = Some("https://www.sqlite.org/lang_insert.html");
+                        self.emit(err);
+                        self.advance();
+                    }
                 }
+            }
+        }
 
-                a.add_column = self.column_def();
+        self.consume_keyword(Keyword::INTO);
+        let table = self.schema_table_container(None)?;
+
+        let mut alias = None;
+        if self.is_keyword(Keyword::AS) {
+            self.advance();
+            alias = Some(self.consume_ident("https://www.sqlite.org/lang_insert.html", "alias")?);
+        }
+
+        let mut columns = None;
+        if self.is(Type::BraceLeft) {
+            self.advance();
+            let mut names =
+                vec![self.consume_ident("https://www.sqlite.org/lang_insert.html", "column_name")?];
+            while self.is(Type::Comma) {
+                self.advance();
+                names.push(
+                    self.consume_ident("https://www.sqlite.org/lang_insert.html", "column_name")?,
+                );
             }
-            Type::Keyword(Keyword::DROP) => {
+            self.consume(Type::BraceRight);
+            columns = Some(names);
+        }
+
+        let source = match self.cur().ttype {
+            Type::Keyword(Keyword::VALUES) => nodes::InsertSource::Values(self.values_rows()?),
+            Type::Keyword(Keyword::SELECT) => nodes::InsertSource::Select(Box::new(self.select()?)),
+            Type::Keyword(Keyword::DEFAULT) => {
                 self.advance();
-                if self.is(Type::Keyword(Keyword::COLUMN)) {
-                    self.advance();
-                }
-                a.drop_column = self
-                    .consume_ident("https://www.sqlite.org/lang_altertable.html", "column_name");
+                self.consume_keyword(Keyword::VALUES);
+                nodes::InsertSource::DefaultValues
             }
             _ => {
                 let mut err = self.err(
                     "Unexpected Token",
                     &format!(
-                        "ALTER requires either RENAME, ADD or DROP at this point, got {:?}",
+                        "INSERT requires VALUES, a SELECT or DEFAULT VALUES, got {:?}",
                         self.cur().ttype
                     ),
                     self.cur(),
                     Rule::Syntax,
                 );
-                err.doc_url = Some("https://www.sqlite.org/lang_altertable.html");
-                self.errors.push(err);
+                err.doc_url = Some("https://www.sqlite.org/lang_insert.html");
+                self.emit(err);
                 self.advance();
                 return None;
             }
+        };
+
+        if columns.is_none() && !matches!(source, nodes::InsertSource::DefaultValues) {
+            let mut err = self.err(
+                "Implicit INSERT columns",
+                "INSERT without a column list depends on the order and amount of the tables columns, consider listing the columns explicitly, since schema changes silently change its meaning",
+                &t,
+                Rule::ImplicitInsertColumns,
+            );
+            err.doc_url = Some("https://www.sqlite.org/lang_insert.html");
+            self.emit(err);
         }
 
-        self.expect_end("https://www.sqlite.org/lang_altertable.html");
+        let upsert = self.upsert_clause()?;
+        let returning = self.returning_clause()?;
 
-        some_box!(a)
+        self.expect_end("https://www.sqlite.org/lang_insert.html");
+
+        some_box!(nodes::Insert {
+            t,
+            or,
+            table,
+            alias,
+            columns,
+            source,
+            upsert,
+            returning,
+        })
     }
 
-    /// https://www.sqlite.org/syntax/reindex-stmt.html
+    /// parses the optional upsert clause of INSERT, see:
+    /// https://www.sqlite.org/syntax/upsert-clause.html
     #[cfg_attr(feature = "trace", trace)]
-    fn reindex_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
-        let mut r = nodes::Reindex {
-            t: self.cur().clone(),
-            target: None,
-        };
+    fn upsert_clause(&mut self) -> Option<Option<nodes::Upsert>> {
+        if !self.is_keyword(Keyword::ON) {
+            return Some(None);
+        }
+        let t = self.cur().clone();
+        // skip ON
         self.advance();
+        self.consume_keyword(Keyword::CONFLICT);
 
-        // REINDEX has a path with no further nodes
-        if self.is(Type::Semicolon) {
-            return some_box!(r);
+        let mut target = vec![];
+        let mut target_where = None;
+        if self.is(Type::BraceLeft) {
+            self.advance();
+            target.push(self.ordering_term()?);
+            while self.is(Type::Comma) {
+                self.advance();
+                target.push(self.ordering_term()?);
+            }
+            self.consume(Type::BraceRight);
+            if self.is_keyword(Keyword::WHERE) {
+                self.advance();
+                target_where = Some(self.expr()?);
+            }
         }
 
-        r.target = self.schema_table_container(None);
+        self.consume_keyword(Keyword::DO);
+        let action = match self.cur().ttype {
+            Type::Keyword(Keyword::NOTHING) => {
+                self.advance();
+                nodes::UpsertAction::Nothing
+            }
+            Type::Keyword(Keyword::UPDATE) => {
+                self.advance();
+                let set = self.set_clause("https://www.sqlite.org/lang_upsert.html")?;
+                let mut where_clause = None;
+                if self.is_keyword(Keyword::WHERE) {
+                    self.advance();
+                    where_clause = Some(Box::new(self.expr()?));
+                }
+                nodes::UpsertAction::Update { set, where_clause }
+            }
+            _ => {
+                let mut err = self.err(
+                    "Unexpected Token",
+                    &format!(
+                        "Wanted either NOTHING or UPDATE after ON CONFLICT DO, got {:?}",
+                        self.cur().ttype
+                    ),
+                    self.cur(),
+                    Rule::Syntax,
+                );
+                err.doc_url = Some("https://www.sqlite.org/lang_upsert.html");
+                self.emit(err);
+                self.advance();
+                return None;
+            }
+        };
 
-        self.expect_end("https://www.sqlite.org/syntax/reindex-stmt.html");
+        Some(Some(nodes::Upsert {
+            t,
+            target,
+            target_where,
+            action,
+        }))
+    }
 
-        some_box!(r)
+    /// parses SET column-name = expr, (column-name, ...) = expr, ... of UPDATE and the upsert DO
+    /// UPDATE
+    #[cfg_attr(feature = "trace", trace)]
+    fn set_clause(&mut self, doc: &'static str) -> Option<Vec<nodes::Assignment>> {
+        if !self.is_keyword(Keyword::SET) {
+            let mut err = self.err(
+                "Missing SET",
+                &format!(
+                    "UPDATE requires SET followed by at least one column assignment, got {:?}",
+                    self.cur().ttype
+                ),
+                self.cur(),
+                Rule::Syntax,
+            );
+            err.doc_url = Some(doc);
+            self.emit(err);
+            self.skip_until_semicolon_or_eof();
+            return None;
+        }
+        // skip SET
+        self.advance();
+
+        let mut assignments = vec![self.assignment(doc)?];
+        while self.is(Type::Comma) {
+            self.advance();
+            assignments.push(self.assignment(doc)?);
+        }
+        Some(assignments)
     }
 
-    /// https://www.sqlite.org/syntax/attach-stmt.html
     #[cfg_attr(feature = "trace", trace)]
-    fn attach_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
+    fn assignment(&mut self, doc: &'static str) -> Option<nodes::Assignment> {
         let t = self.cur().clone();
-        // skipping ATTACH
-        self.advance();
-        // skipping optional DATABASE
-        if self.is(Type::Keyword(Keyword::DATABASE)) {
+        let mut columns = vec![];
+        if self.is(Type::BraceLeft) {
             self.advance();
+            columns.push(self.consume_ident(doc, "column_name")?);
+            while self.is(Type::Comma) {
+                self.advance();
+                columns.push(self.consume_ident(doc, "column_name")?);
+            }
+            self.consume(Type::BraceRight);
+        } else {
+            columns.push(self.consume_ident(doc, "column_name")?);
         }
+        self.consume(Type::Equal);
+        let expr = self.expr()?;
+        Some(nodes::Assignment { t, columns, expr })
+    }
 
-        let mut a = nodes::Attach {
-            t,
-            schema_name: String::new(),
-            expr: self.expr()?,
-        };
+    #[cfg_attr(feature = "trace", trace)]
+    fn select_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
+        let select = self.select()?;
+        self.expect_end("https://www.sqlite.org/lang_select.html");
+        some_box!(select)
+    }
 
-        self.consume(Type::Keyword(Keyword::AS));
+    /// parses a select without checking for the end of the statement, so it can be used for
+    /// subqueries: https://www.sqlite.org/syntax/select-stmt.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn select(&mut self) -> Option<nodes::Select> {
+        self.nested(Self::select_inner)
+    }
 
-        a.schema_name =
-            self.consume_ident("https://www.sqlite.org/lang_attach.html", "schema_name")?;
+    fn select_inner(&mut self) -> Option<nodes::Select> {
+        let mut s = self.select_core()?;
 
-        self.expect_end("https://www.sqlite.org/lang_attach.html");
+        // https://www.sqlite.org/syntax/compound-operator.html
+        loop {
+            let t = self.cur().clone();
+            let operator = match t.ttype {
+                Type::Keyword(Keyword::UNION) => {
+                    self.advance();
+                    if self.is_keyword(Keyword::ALL) {
+                        self.advance();
+                        nodes::CompoundOperator::UnionAll
+                    } else {
+                        nodes::CompoundOperator::Union
+                    }
+                }
+                Type::Keyword(Keyword::INTERSECT) => {
+                    self.advance();
+                    nodes::CompoundOperator::Intersect
+                }
+                Type::Keyword(Keyword::EXCEPT) => {
+                    self.advance();
+                    nodes::CompoundOperator::Except
+                }
+                _ => break,
+            };
+            let select = self.select_core()?;
+            s.compound.push(nodes::CompoundSelect {
+                t,
+                operator,
+                select,
+            });
+        }
 
-        some_box!(a)
-    }
+        if self.is_keyword(Keyword::ORDER) {
+            s.order_by = self.order_by_clause()?;
+            self.order_by_ordinals(&mut s);
+        }
 
-    /// https://www.sqlite.org/syntax/release-stmt.html
-    #[cfg_attr(feature = "trace", trace)]
-    fn release_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
-        let mut r = nodes::Release {
-            t: self.cur().clone(),
-            savepoint_name: String::new(),
-        };
-        self.advance();
+        if self.is_keyword(Keyword::LIMIT) {
+            let (limit, offset) = self.limit_clause()?;
+            s.limit = Some(limit);
+            s.offset = offset;
+        }
 
-        if self.is(Type::Keyword(Keyword::SAVEPOINT)) {
+        if self.is_keyword(Keyword::NULLS) {
+            let mut err = self.err(
+                "Misplaced NULLS",
+                "NULLS FIRST and NULLS LAST are only valid directly after an ORDER BY term",
+                self.cur(),
+                Rule::Syntax,
+            );
+            err.doc_url = Some("https://www.sqlite.org/syntax/ordering-term.html");
+            self.emit(err);
             self.advance();
+            return None;
         }
 
-        r.savepoint_name = self.consume_ident(
-            "https://www.sqlite.org/syntax/release-stmt.html",
-            "savepoint_name",
-        )?;
+        Some(s)
+    }
 
-        self.expect_end("https://www.sqlite.org/syntax/release-stmt.html");
+    /// parses ORDER BY ordering-term, ..., see: https://www.sqlite.org/syntax/ordering-term.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn order_by_clause(&mut self) -> Option<Vec<nodes::OrderingTerm>> {
+        self.consume_keyword(Keyword::ORDER);
+        self.consume_keyword(Keyword::BY);
+        let mut terms = vec![self.ordering_term()?];
+        while self.is(Type::Comma) {
+            self.advance();
+            terms.push(self.ordering_term()?);
+        }
+        Some(terms)
+    }
 
-        some_box!(r)
+    /// parses LIMIT expr [OFFSET expr] and LIMIT expr, expr into the limit and the offset, the
+    /// comma form lists the offset first: LIMIT 5, 10 is LIMIT 10 OFFSET 5, see:
+    /// https://www.sqlite.org/lang_select.html#the_limit_clause
+    #[cfg_attr(feature = "trace", trace)]
+    fn limit_clause(&mut self) -> Option<(nodes::Expr, Option<nodes::Expr>)> {
+        self.consume_keyword(Keyword::LIMIT);
+        let limit = self.expr()?;
+        match self.cur().ttype {
+            Type::Keyword(Keyword::OFFSET) => {
+                self.advance();
+                Some((limit, Some(self.expr()?)))
+            }
+            Type::Comma => {
+                self.advance();
+                Some((self.expr()?, Some(limit)))
+            }
+            _ => Some((limit, None)),
+        }
     }
 
-    /// https://www.sqlite.org/syntax/savepoint-stmt.html
+    /// parses a select without compound operators, ORDER BY and LIMIT, see:
+    /// https://www.sqlite.org/syntax/select-core.html
     #[cfg_attr(feature = "trace", trace)]
-    fn savepoint_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
-        let mut s = nodes::Savepoint {
+    fn select_core(&mut self) -> Option<nodes::Select> {
+        let mut s = nodes::Select {
             t: self.cur().clone(),
-            savepoint_name: String::new(),
+            distinct: false,
+            columns: vec![],
+            from: None,
+            where_clause: None,
+            group_by: vec![],
+            having: None,
+            windows: vec![],
+            compound: vec![],
+            order_by: vec![],
+            limit: None,
+            offset: None,
         };
-        self.advance();
-        s.savepoint_name = self.consume_ident(
-            "https://www.sqlite.org/syntax/savepoint-stmt.html",
-            "savepoint_name",
-        )?;
-        self.expect_end("https://www.sqlite.org/lang_savepoint.html");
+        self.consume_keyword(Keyword::SELECT);
 
-        some_box!(s)
+        match self.cur().ttype {
+            Type::Keyword(Keyword::DISTINCT) => {
+                self.advance();
+                s.distinct = true;
+            }
+            Type::Keyword(Keyword::ALL) => self.advance(),
+            _ => {}
+        }
+
+        s.columns.push(self.result_column()?);
+        while self.is(Type::Comma) {
+            self.advance();
+            s.columns.push(self.result_column()?);
+        }
+
+        if self.is_keyword(Keyword::FROM) {
+            self.advance();
+            s.from = Some(self.join_clause()?);
+        }
+
+        if self.is_keyword(Keyword::WHERE) {
+            self.advance();
+            s.where_clause = Some(self.expr()?);
+        }
+
+        (s.group_by, s.having) = self.group_by_clause()?;
+
+        if self.is_keyword(Keyword::WINDOW) {
+            self.advance();
+            s.windows.push(self.named_window()?);
+            while self.is(Type::Comma) {
+                self.advance();
+                s.windows.push(self.named_window()?);
+            }
+        }
+
+        Some(s)
     }
 
-    /// https://www.sqlite.org/lang_dropindex.html
-    /// https://www.sqlite.org/lang_droptable.html
-    /// https://www.sqlite.org/lang_droptrigger.html
-    /// https://www.sqlite.org/lang_dropview.html
+    /// parses window-name AS (window-defn) of a WINDOW clause, see:
+    /// https://www.sqlite.org/lang_select.html#the_window_clause
     #[cfg_attr(feature = "trace", trace)]
-    fn drop_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
+    fn named_window(&mut self) -> Option<nodes::NamedWindow> {
         let t = self.cur().clone();
-        self.advance();
+        let name = self.consume_ident(
+            "https://www.sqlite.org/lang_select.html#the_window_clause",
+            "window_name",
+        )?;
+        self.consume_keyword(Keyword::AS);
+        self.consume(Type::BraceLeft);
+        let window = self.window_defn()?;
+        self.consume(Type::BraceRight);
+        Some(nodes::NamedWindow { t, name, window })
+    }
 
-        match self.cur().ttype {
-            Type::Keyword(Keyword::INDEX) => (),
-            Type::Keyword(Keyword::TABLE) => (),
-            Type::Keyword(Keyword::TRIGGER) => (),
-            Type::Keyword(Keyword::VIEW) => (),
-            _ => {
+    /// parses the optional GROUP BY expr, ... [HAVING expr] of a select, HAVING is allowed without
+    /// GROUP BY, in which case the whole result is a single group, see:
+    /// https://www.sqlite.org/lang_select.html#generation_of_the_set_of_result_rows
+    #[cfg_attr(feature = "trace", trace)]
+    fn group_by_clause(&mut self) -> Option<(Vec<nodes::Expr>, Option<nodes::Expr>)> {
+        let mut group_by = vec![];
+        if self.is_keyword(Keyword::GROUP) {
+            self.advance();
+            self.consume_keyword(Keyword::BY);
+            if let Type::Semicolon
+            | Type::Eof
+            | Type::BraceRight
+            | Type::Keyword(
+                Keyword::HAVING
+                | Keyword::WINDOW
+                | Keyword::ORDER
+                | Keyword::LIMIT
+                | Keyword::UNION
+                | Keyword::INTERSECT
+                | Keyword::EXCEPT,
+            ) = self.cur().ttype
+            {
                 let mut err = self.err(
-                        "Unexpected Token",
-                        &format!(
-                            "DROP requires either TRIGGER, TABLE, TRIGGER or VIEW at this point, got {:?}",
-                            self.cur().ttype
-                        ),
-                        self.cur(),
-                        Rule::Syntax,
-                    );
-                err.doc_url = Some("https://www.sqlite.org/lang.html");
-                self.errors.push(err);
-                self.advance();
+                    "Empty GROUP BY",
+                    "GROUP BY requires at least one expression to group the rows by",
+                    self.cur(),
+                    Rule::Syntax,
+                );
+                err.doc_url = Some("https://www.sqlite.org/syntax/select-core.html");
+                self.emit(err);
+                self.skip_until_semicolon_or_eof();
                 return None;
             }
+            group_by.push(self.expr()?);
+            while self.is(Type::Comma) {
+                self.advance();
+                group_by.push(self.expr()?);
+            }
         }
 
-        let ttype = {
-            let Type::Keyword(keyword) = &self.cur().ttype else {
-                unreachable!("self.cur() in (in the set theory kind) {{INDEX,TABLE,TRIGGER,VIEW}}")
-            };
-            *keyword
-        };
-
-        // skip either INDEX;TABLE;TRIGGER or VIEW
-        self.advance();
-
-        let if_exists = if self.is(Type::Keyword(Keyword::IF)) {
+        let having = if self.is_keyword(Keyword::HAVING) {
             self.advance();
-            self.consume(Type::Keyword(Keyword::EXISTS));
-            true
+            Some(self.expr()?)
         } else {
-            false
+            None
         };
 
-        let argument = self.schema_table_container(None)?;
+        Some((group_by, having))
+    }
 
-        some_box!(nodes::Drop {
+    /// parses WITH [RECURSIVE] common-table-expression, ... and the statement it prefixes, see:
+    /// https://www.sqlite.org/lang_with.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn with_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
+        let t = self.cur().clone();
+        let (recursive, ctes) = self.with_clause()?;
+
+        let child = match self.cur().ttype {
+            Type::Keyword(
+                Keyword::SELECT
+                | Keyword::INSERT
+                | Keyword::REPLACE
+                | Keyword::UPDATE
+                | Keyword::DELETE,
+            ) => self.sql_stmt()?,
+            _ => {
+                let mut err = self.err(
+                    "Unexpected Token",
+                    &format!(
+                        "A WITH clause has to be followed by SELECT, INSERT, REPLACE, UPDATE or DELETE, got {:?}",
+                        self.cur().ttype
+                    ),
+                    self.cur(),
+                    Rule::Syntax,
+                );
+                err.doc_url = Some("https://www.sqlite.org/lang_with.html");
+                self.emit(err);
+                self.advance();
+                return None;
+            }
+        };
+
+        some_box!(nodes::WithClause {
             t,
-            ttype,
-            if_exists,
-            argument,
+            recursive,
+            ctes,
+            child,
         })
     }
 
-    /// https://www.sqlite.org/syntax/analyze-stmt.html
+    /// parses WITH [RECURSIVE] common-table-expression, ..., see:
+    /// https://www.sqlite.org/syntax/with-clause.html
     #[cfg_attr(feature = "trace", trace)]
-    fn analyse_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
-        let mut a = nodes::Analyze {
-            t: self.cur().clone(),
-            target: None,
-        };
+    fn with_clause(&mut self) -> Option<(bool, Vec<nodes::CommonTableExpression>)> {
+        self.consume_keyword(Keyword::WITH);
+        let recursive = self.is_keyword(Keyword::RECURSIVE);
+        if recursive {
+            self.advance();
+        }
 
-        self.advance();
+        let mut ctes = vec![self.common_table_expression()?];
+        while self.is(Type::Comma) {
+            self.advance();
+            ctes.push(self.common_table_expression()?);
+        }
 
-        // inlined Parser::schema_table_container
-        a.target = match self.cur().ttype.clone() {
-            Type::Ident(schema) if self.next_is(Type::Dot) => {
-                self.advance();
-                self.advance();
-                if let Type::Ident(table) = &self.cur().ttype {
-                    let table = table.clone();
-                    self.advance();
-                    Some(SchemaTableContainer::SchemaAndTable { schema, table })
-                } else if let Type::String(table) = &self.cur().ttype {
-                    let table = table.clone();
-                    self.advance();
-                    Some(SchemaTableContainer::SchemaAndTable { schema, table })
-                } else {
-                    let cur = self.cur().clone();
-                    self.errors.push(match cur.ttype {
-                        Type::Keyword(keyword) => {
-                            let as_str: &str = keyword.into();
-                            self.err(
-                            "Malformed table name",
-                            &format!("`{as_str}` is a keyword, if you want to use it as a table or column name, quote it: '{as_str}'"),
-                            &cur, Rule::Syntax)
-                        }
-                        _ => self.err(
-                            "Malformed table name",
-                            &format!(
-                                "expected a table name after <schema_name>. - got {:?}",
-                                cur.ttype
-                            ),
-                            &cur,
-                            Rule::Syntax,
-                        ),
-                    });
+        Some((recursive, ctes))
+    }
 
-                    // skip wrong token, should I skip to the next statement via
-                    // self.skip_until_semicolon_or_eof?
-                    self.advance();
-                    None
-                }
+    /// https://www.sqlite.org/syntax/common-table-expression.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn common_table_expression(&mut self) -> Option<nodes::CommonTableExpression> {
+        let t = self.cur().clone();
+        let name = self.consume_ident(
+            "https://www.sqlite.org/syntax/common-table-expression.html",
+            "table_name",
+        )?;
+
+        let mut columns = vec![];
+        if self.is(Type::BraceLeft) {
+            self.advance();
+            if self.is(Type::BraceRight) {
+                let mut err = self.err(
+                    "Empty column list",
+                    &format!(
+                        "The column list of the common table expression `{name}` has to contain at least one column, omit the parenthesis to use the columns of its select"
+                    ),
+                    self.cur(),
+                    Rule::Syntax,
+                );
+                err.doc_url = Some("https://www.sqlite.org/syntax/common-table-expression.html");
+                self.emit(err);
+                self.skip_until_semicolon_or_eof();
+                return None;
             }
-            Type::Ident(table_name) | Type::String(table_name) => {
-                // skip table_name
+            columns.push(self.consume_ident(
+                "https://www.sqlite.org/syntax/common-table-expression.html",
+                "column_name",
+            )?);
+            while self.is(Type::Comma) {
                 self.advance();
-                Some(SchemaTableContainer::Table(table_name))
+                columns.push(self.consume_ident(
+                    "https://www.sqlite.org/syntax/common-table-expression.html",
+                    "column_name",
+                )?);
             }
-            _ => None,
-        };
+            self.consume(Type::BraceRight);
+        }
 
-        self.expect_end("https://www.sqlite.org/lang_analyze.html");
+        self.consume_keyword(Keyword::AS);
 
-        some_box!(a)
+        // AS [NOT] MATERIALIZED (select-stmt), sqlite 3.35+, see
+        // [crate::parser::analyse::common_table_expression]
+        let mut materialized = None;
+        if self.is_keyword(Keyword::NOT) {
+            self.advance();
+            self.consume_keyword(Keyword::MATERIALIZED);
+            materialized = Some(false);
+        } else if self.is_keyword(Keyword::MATERIALIZED) {
+            self.advance();
+            materialized = Some(true);
+        }
+
+        if !self.is(Type::BraceLeft) {
+            let mut err = self.err(
+                "Unexpected Token",
+                &format!(
+                    "The body of the common table expression `{name}` has to be a select-stmt in parenthesis, got {:?}",
+                    self.cur().ttype
+                ),
+                self.cur(),
+                Rule::Syntax,
+            );
+            err.doc_url = Some("https://www.sqlite.org/syntax/common-table-expression.html");
+            self.emit(err);
+            self.skip_until_semicolon_or_eof();
+            return None;
+        }
+        self.advance();
+        // postgres allows data-modifying statements as the body, sqlite does not
+        if let Type::Keyword(
+            keyword @ (Keyword::INSERT | Keyword::REPLACE | Keyword::UPDATE | Keyword::DELETE),
+        ) = self.cur().ttype
+        {
+            let keyword: &str = keyword.into();
+            let mut err = self.err(
+                "Data-modifying common table expression",
+                &format!(
+                    "The body of the common table expression `{name}` is a {keyword}, sqlite only allows SELECT or VALUES here, run the {keyword} ... RETURNING as a statement of its own instead"
+                ),
+                self.cur(),
+                Rule::SqliteUnsupported,
+            );
+            err.doc_url = Some("https://www.sqlite.org/lang_with.html");
+            self.emit(err);
+            self.skip_until_semicolon_or_eof();
+            return None;
+        }
+        let body = Box::new(self.select()?);
+        self.consume(Type::BraceRight);
+
+        Some(nodes::CommonTableExpression {
+            t,
+            name,
+            columns,
+            materialized,
+            body,
+        })
     }
 
-    /// https://www.sqlite.org/syntax/detach-stmt.html
+    /// integer literals in ORDER BY refer to the result column at their position, starting at 1,
+    /// see: https://www.sqlite.org/lang_select.html#the_order_by_clause
+    fn order_by_ordinals(&mut self, s: &mut nodes::Select) {
+        // * and table.* expand to an unknown amount of columns
+        let column_count = s
+            .columns
+            .iter()
+            .all(|c| matches!(c, nodes::ResultColumn::Expr { .. }))
+            .then_some(s.columns.len());
+
+        for (i, term) in s.order_by.iter_mut().enumerate() {
+            let Some(Token {
+                ttype: Type::Integer(number),
+                ..
+            }) = &term.expr.literal
+            else {
+                continue;
+            };
+            let ordinal = *number as usize;
+            term.ordinal = Some(ordinal);
+
+            let t = term.expr.t.clone();
+            if column_count.is_some_and(|count| ordinal < 1 || ordinal > count) || *number < 1 {
+                let mut err = self.err(
+                    "ORDER BY term out of range",
+                    &format!(
+                        "{}. ORDER BY term refers to result column {}, ordinals should be {}",
+                        i + 1,
+                        number,
+                        match column_count {
+                            Some(count) => format!("between 1 and {count}"),
+                            None => "at least 1".into(),
+                        }
+                    ),
+                    &t,
+                    Rule::Syntax,
+                );
+                err.doc_url = Some("https://www.sqlite.org/lang_select.html#the_order_by_clause");
+                self.emit(err);
+            } else {
+                let mut err = self.err(
+                    "ORDER BY ordinal",
+                    &format!(
+                        "ORDER BY {ordinal} sorts by the {ordinal}. result column, consider naming the column explicitly, since ordinals silently change meaning if result columns are reordered"
+                    ),
+                    &t,
+                    Rule::Quirk,
+                );
+                err.doc_url = Some("https://www.sqlite.org/lang_select.html#the_order_by_clause");
+                self.emit(err);
+            }
+        }
+    }
+
+    /// https://www.sqlite.org/syntax/result-column.html
     #[cfg_attr(feature = "trace", trace)]
-    fn detach_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
-        let t = self.cur().clone();
+    fn result_column(&mut self) -> Option<nodes::ResultColumn> {
+        match &self.cur().ttype {
+            Type::Asterisk => {
+                self.advance();
+                Some(nodes::ResultColumn::Star)
+            }
+            Type::Ident(table)
+                if self.next_is(Type::Dot)
+                    && self
+                        .tokens
+                        .get(self.pos + 2)
+                        .is_some_and(|t| t.ttype == Type::Asterisk) =>
+            {
+                let table = table.clone();
+                // skip table_name, Type::Dot and Type::Asterisk
+                self.advance();
+                self.advance();
+                self.advance();
+                Some(nodes::ResultColumn::TableStar(table))
+            }
+            _ => {
+                let expr = self.expr()?;
+                let alias = self.alias("https://www.sqlite.org/syntax/result-column.html")?;
+                Some(nodes::ResultColumn::Expr {
+                    expr: Box::new(expr),
+                    alias,
+                })
+            }
+        }
+    }
+
+    /// parses an optional [AS] alias, AS requires an alias to follow
+    fn alias(&mut self, doc: &'static str) -> Option<Option<String>> {
+        match &self.cur().ttype {
+            Type::Keyword(Keyword::AS) => {
+                self.advance();
+                match &self.cur().ttype {
+                    Type::String(alias) => {
+                        let alias = alias.clone();
+                        self.advance();
+                        Some(Some(alias))
+                    }
+                    _ => Some(Some(self.consume_ident(doc, "alias")?)),
+                }
+            }
+            Type::Ident(alias) | Type::String(alias) => {
+                let alias = alias.clone();
+                self.advance();
+                Some(Some(alias))
+            }
+            _ => Some(None),
+        }
+    }
+
+    /// https://www.sqlite.org/lang_expr.html#the_case_expression
+    #[cfg_attr(feature = "trace", trace)]
+    fn case_expr(&mut self) -> Option<nodes::Expr> {
+        self.nested(Self::case_expr_inner)
+    }
+
+    fn case_expr_inner(&mut self) -> Option<nodes::Expr> {
+        let mut case = nodes::CaseExpr {
+            t: self.cur().clone(),
+            operand: None,
+            when_clauses: vec![],
+            else_clause: None,
+        };
+        // skip CASE
         self.advance();
 
-        // skip optional DATABASE path
-        if self.is(Type::Keyword(Keyword::DATABASE)) {
+        if !self.is_keyword(Keyword::WHEN) {
+            case.operand = Some(self.expr()?);
+        }
+
+        while self.is_keyword(Keyword::WHEN) {
             self.advance();
+            let when = self.expr()?;
+            self.consume_keyword(Keyword::THEN);
+            let then = self.expr()?;
+            case.when_clauses.push((when, then));
         }
 
-        let schema_name =
-            self.consume_ident("https://www.sqlite.org/lang_detach.html", "schema_name")?;
+        if case.when_clauses.is_empty() {
+            let mut err = self.err(
+                "Missing WHEN",
+                &format!(
+                    "CASE requires at least one WHEN expr THEN expr, got {:?}",
+                    self.cur().ttype
+                ),
+                self.cur(),
+                Rule::Syntax,
+            );
+            err.doc_url = Some("https://www.sqlite.org/lang_expr.html#the_case_expression");
+            self.emit(err);
+            self.skip_until_semicolon_or_eof();
+            return None;
+        }
 
-        let d = nodes::Detach { t, schema_name };
+        if self.is_keyword(Keyword::ELSE) {
+            self.advance();
+            case.else_clause = Some(self.expr()?);
+        }
 
-        self.expect_end("https://www.sqlite.org/lang_detach.html");
+        if !self.is_keyword(Keyword::END) {
+            let mut err = self.err(
+                "Missing END",
+                &format!("CASE must be terminated by END, got {:?}", self.cur().ttype),
+                self.cur(),
+                Rule::Syntax,
+            );
+            err.doc_url = Some("https://www.sqlite.org/lang_expr.html#the_case_expression");
+            self.emit(err);
+            self.skip_until_semicolon_or_eof();
+            return None;
+        }
+        // skip END
+        self.advance();
 
-        some_box!(d)
+        Some(Self::compound_expr(case.t.clone(), Box::new(case)))
     }
 
-    /// https://www.sqlite.org/syntax/rollback-stmt.html
+    /// https://www.sqlite.org/syntax/join-clause.html
     #[cfg_attr(feature = "trace", trace)]
-    fn rollback_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
-        let mut rollback = nodes::Rollback {
+    fn join_clause(&mut self) -> Option<nodes::JoinClause> {
+        let mut clause = nodes::JoinClause {
             t: self.cur().clone(),
-            save_point: None,
+            table: self.table_or_subquery()?,
+            joins: vec![],
         };
-        self.advance();
 
-        match self.cur().ttype {
-            Type::Keyword(Keyword::TRANSACTION) | Type::Keyword(Keyword::TO) | Type::Semicolon => {}
-            _ => {
+        while let Some((t, natural, operator)) = self.join_operator() {
+            // a join operator requires a table or subquery to join with
+            if !matches!(
+                self.cur().ttype,
+                Type::Ident(_) | Type::String(_) | Type::BraceLeft
+            ) {
                 let mut err = self.err(
-                    "Unexpected Token",
+                    "Missing table or subquery to join",
                     &format!(
-                        "ROLLBACK requires TRANSACTION, TO or to end at this point, got {:?}",
+                        "{}{operator} requires a table or subquery to join with, got {:?}",
+                        if natural { "NATURAL " } else { "" },
                         self.cur().ttype
                     ),
                     self.cur(),
                     Rule::Syntax,
                 );
-                err.doc_url = Some("https://www.sqlite.org/lang_transaction.html");
-                self.errors.push(err);
-            }
-        }
-
-        // optional TRANSACTION
-        if self.is(Type::Keyword(Keyword::TRANSACTION)) {
-            self.advance();
-        }
-
-        // optional TO
-        if self.is(Type::Keyword(Keyword::TO)) {
-            self.advance();
-
-            // optional SAVEPOINT
-            if self.is(Type::Keyword(Keyword::SAVEPOINT)) {
-                self.advance();
+                err.doc_url = Some("https://www.sqlite.org/syntax/join-clause.html");
+                self.emit(err);
+                self.skip_until_semicolon_or_eof();
+                return None;
             }
+            let table = self.table_or_subquery()?;
 
-            match self.cur().ttype {
-                Type::Keyword(Keyword::SAVEPOINT) | Type::Ident(_) | Type::Semicolon => {}
-                _ => {
-                    let mut err = self.err(
-                        "Unexpected Token",
-                        &format!(
-                            "ROLLBACK requires SAVEPOINT, Ident or to end at this point, got {:?}",
-                            self.cur().ttype
-                        ),
-                        self.cur(),
-                        Rule::Syntax,
-                    );
-                    err.doc_url = Some("https://www.sqlite.org/lang_transaction.html");
-                    self.errors.push(err);
+            // https://www.sqlite.org/syntax/join-constraint.html
+            let constraint_t = self.cur().clone();
+            let constraint = match self.cur().ttype {
+                Type::Keyword(Keyword::ON) => {
                     self.advance();
+                    Some(nodes::JoinConstraint::On(Box::new(self.expr()?)))
                 }
-            }
-
-            if let Type::Ident(str) = &self.cur().ttype {
-                rollback.save_point = Some(String::from(str));
-            } else {
+                Type::Keyword(Keyword::USING) => {
+                    self.advance();
+                    self.consume(Type::BraceLeft);
+                    let mut columns = vec![self.consume_ident(
+                        "https://www.sqlite.org/syntax/join-constraint.html",
+                        "column_name",
+                    )?];
+                    while self.is(Type::Comma) {
+                        self.advance();
+                        columns.push(self.consume_ident(
+                            "https://www.sqlite.org/syntax/join-constraint.html",
+                            "column_name",
+                        )?);
+                    }
+                    self.consume(Type::BraceRight);
+                    Some(nodes::JoinConstraint::Using(columns))
+                }
+                _ => None,
+            };
+            // NATURAL already joins on all columns both sides have in common
+            if let (true, Some(c)) = (natural, &constraint) {
                 let mut err = self.err(
-                    "Unexpected Token",
+                    "Constraint on NATURAL join",
                     &format!(
-                        "ROLLBACK wants Ident as <savepoint-name>, got {:?}",
-                        self.cur().ttype
+                        "a NATURAL join may not have an ON or USING clause, remove NATURAL or the {} clause",
+                        match c {
+                            nodes::JoinConstraint::On(_) => "ON",
+                            nodes::JoinConstraint::Using(_) => "USING",
+                        }
                     ),
-                    self.cur(),
+                    &constraint_t,
                     Rule::Syntax,
                 );
-                err.doc_url = Some("https://www.sqlite.org/lang_transaction.html");
-                self.errors.push(err);
+                err.doc_url = Some("https://www.sqlite.org/syntax/join-constraint.html");
+                self.emit(err);
             }
-            self.advance();
-        }
 
-        self.expect_end("https://www.sqlite.org/lang_transaction.html");
+            clause.joins.push(nodes::Join {
+                t,
+                natural,
+                operator,
+                table,
+                constraint,
+            });
+        }
 
-        some_box!(rollback)
+        Some(clause)
     }
 
-    /// https://www.sqlite.org/syntax/commit-stmt.html
-    #[cfg_attr(feature = "trace", trace)]
-    fn commit_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
-        let commit: Option<Box<dyn nodes::Node>> = some_box!(nodes::Commit {
-            t: self.cur().clone(),
-        });
+    /// parses a join operator, returns None if the current token does not start one, see:
+    /// https://www.sqlite.org/syntax/join-operator.html
+    fn join_operator(&mut self) -> Option<(Token, bool, nodes::JoinOperator)> {
+        let t = self.cur().clone();
+        if self.is(Type::Comma) {
+            self.advance();
+            return Some((t, false, nodes::JoinOperator::Comma));
+        }
 
-        // skip either COMMIT or END
-        self.advance();
+        let natural = self.is_keyword(Keyword::NATURAL);
+        if natural {
+            self.advance();
+        }
 
-        match self.cur().ttype {
-            // expected end 1
-            Type::Semicolon => (),
-            // expected end 2, optional
-            Type::Keyword(Keyword::TRANSACTION) => self.advance(),
-            _ => {
+        let operator = match self.cur().ttype {
+            Type::Keyword(Keyword::JOIN) => nodes::JoinOperator::Join,
+            Type::Keyword(Keyword::CROSS) => nodes::JoinOperator::Cross,
+            Type::Keyword(Keyword::INNER) => nodes::JoinOperator::Inner,
+            Type::Keyword(Keyword::LEFT) => nodes::JoinOperator::Left,
+            Type::Keyword(Keyword::RIGHT) => nodes::JoinOperator::Right,
+            Type::Keyword(Keyword::FULL) => nodes::JoinOperator::Full,
+            _ if natural => {
                 let mut err = self.err(
                     "Unexpected Token",
                     &format!(
-                        "Wanted Keyword(TRANSACTION) or Semicolon, got {:?}",
+                        "NATURAL requires a join operator (JOIN, INNER, LEFT, RIGHT or FULL) to follow, got {:?}",
                         self.cur().ttype
                     ),
                     self.cur(),
                     Rule::Syntax,
                 );
-                err.doc_url = Some("https://www.sqlite.org/lang_transaction.html");
-                self.errors.push(err);
+                err.doc_url = Some("https://www.sqlite.org/syntax/join-operator.html");
+                self.emit(err);
+                return None;
+            }
+            _ => return None,
+        };
+
+        if operator != nodes::JoinOperator::Join {
+            // skip CROSS, INNER, LEFT, RIGHT or FULL
+            self.advance();
+            if matches!(
+                operator,
+                nodes::JoinOperator::Left | nodes::JoinOperator::Right | nodes::JoinOperator::Full
+            ) && self.is_keyword(Keyword::OUTER)
+            {
                 self.advance();
             }
         }
+        self.consume_keyword(Keyword::JOIN);
 
-        self.expect_end("https://www.sqlite.org/lang_transaction.html");
-
-        commit
+        Some((t, natural, operator))
     }
 
-    /// https://www.sqlite.org/syntax/begin-stmt.html
+    /// https://www.sqlite.org/syntax/table-or-subquery.html
     #[cfg_attr(feature = "trace", trace)]
-    fn begin_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
-        let mut begin: nodes::Begin = nodes::Begin {
-            t: self.cur().clone(),
-            transaction_kind: None,
-        };
-
-        // skip BEGIN
-        self.advance();
-
-        // skip modifiers
-        match self.cur().ttype {
-            // BEGIN;
-            Type::Semicolon => return some_box!(begin),
-            Type::Keyword(Keyword::DEFERRED)
-            | Type::Keyword(Keyword::IMMEDIATE)
-            | Type::Keyword(Keyword::EXCLUSIVE) => {
-                begin.transaction_kind = if let Type::Keyword(word) = &self.cur().ttype {
-                    Some(*word)
-                } else {
-                    None
-                };
-                self.advance()
+    fn table_or_subquery(&mut self) -> Option<nodes::TableOrSubquery> {
+        if self.is(Type::BraceLeft) {
+            // skip (
+            self.advance();
+            if !self.is_keyword(Keyword::SELECT) {
+                let cur = self.cur().clone();
+                let mut err = self.err(
+                    "Unimplemented",
+                    &format!(
+                        "sqleibniz can only analyse subqueries in FROM, got {:?}",
+                        cur.ttype
+                    ),
+                    &cur,
+                    Rule::Unimplemented,
+                );
+                err.doc_url = Some("https://www.sqlite.org/syntax/table-or-subquery.html");
+                self.emit(err);
+                self.skip_until_semicolon_or_eof();
+                return None;
             }
-            _ => {}
+            let select = self.select()?;
+            self.consume(Type::BraceRight);
+            let alias = self.alias("https://www.sqlite.org/syntax/table-or-subquery.html")?;
+            return Some(nodes::TableOrSubquery::Subquery {
+                select: Box::new(select),
+                alias,
+            });
         }
 
+        let name = self.schema_table_container(None)?;
+        let alias = self.alias("https://www.sqlite.org/syntax/table-or-subquery.html")?;
+        Some(nodes::TableOrSubquery::Table { name, alias })
+    }
+
+    /// https://www.sqlite.org/lang_createindex.html
+    /// https://www.sqlite.org/lang_createtable.html
+    /// https://www.sqlite.org/lang_createtrigger.html
+    /// https://www.sqlite.org/lang_createview.html
+    /// https://www.sqlite.org/lang_createvtab.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn create_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
+        let t = self.cur().clone();
+        // skip CREATE
+        self.advance();
+
+        let temporary = if let Type::Keyword(Keyword::TEMP | Keyword::TEMPORARY) = self.cur().ttype
+        {
+            self.advance();
+            true
+        } else {
+            false
+        };
+
         match self.cur().ttype {
-            Type::Semicolon => return some_box!(begin),
-            // ending
-            Type::Keyword(Keyword::TRANSACTION) => self.advance(),
-            Type::Keyword(Keyword::DEFERRED)
-            | Type::Keyword(Keyword::IMMEDIATE)
-            | Type::Keyword(Keyword::EXCLUSIVE) => {
-                let mut err = self.err(
-                    "Unexpected Token",
-                    "BEGIN does not allow multiple transaction behaviour modifiers",
-                    self.cur(),
-                    Rule::Syntax,
+            Type::Keyword(Keyword::TRIGGER) => self.create_trigger_stmt(t, temporary),
+            Type::Keyword(Keyword::VIEW) => self.create_view_stmt(t, temporary),
+            Type::Keyword(Keyword::VIRTUAL) => self.create_virtual_table_stmt(t, temporary),
+            Type::Keyword(Keyword::TABLE) => self.create_table_stmt(t, temporary),
+            Type::Keyword(Keyword::INDEX) | Type::Keyword(Keyword::UNIQUE) => {
+                let cur = self.cur().clone();
+                self.push_err(
+                    "Unimplemented",
+                    &format!(
+                        "sqleibniz can not yet analyse CREATE {:?}, skipping ahead to next statement",
+                        cur.ttype,
+                    ),
+                    &cur,
+                    Rule::Unimplemented,
                 );
-                err.doc_url = Some("https://www.sqlite.org/lang_transaction.html");
-                self.errors.push(err);
-                // TODO: think about if this is smart at this point, skipping to the next ; could
-                // be skipping too many tokens
                 self.skip_until_semicolon_or_eof();
+                None
             }
             _ => {
                 let mut err = self.err(
                     "Unexpected Token",
                     &format!(
-                        "Wanted any of TRANSACTION, DEFERRED, IMMEDIATE or EXCLUSIVE before this point, got {:?}",
+                        "CREATE requires either TABLE, INDEX, TRIGGER, VIEW or VIRTUAL at this point, got {:?}",
                         self.cur().ttype
                     ),
                     self.cur(),
                     Rule::Syntax,
                 );
-                err.doc_url = Some("https://www.sqlite.org/lang_transaction.html");
-                self.errors.push(err);
+                err.doc_url = Some("https://www.sqlite.org/lang.html");
+                self.emit(err);
+                self.advance();
+                None
             }
         }
-
-        self.expect_end("https://www.sqlite.org/lang_transaction.html");
-
-        some_box!(begin)
     }
 
-    /// https://www.sqlite.org/lang_vacuum.html
+    /// https://www.sqlite.org/lang_createtable.html
     #[cfg_attr(feature = "trace", trace)]
-    fn vacuum_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
-        let mut v = nodes::Vacuum {
-            t: self.cur().clone(),
-            schema_name: None,
-            filename: None,
+    fn create_table_stmt(&mut self, t: Token, temporary: bool) -> Option<Box<dyn nodes::Node>> {
+        // skip TABLE
+        self.advance();
+
+        let if_not_exists = if self.is_keyword(Keyword::IF) {
+            self.advance();
+            self.consume_keyword(Keyword::NOT);
+            self.consume_keyword(Keyword::EXISTS);
+            true
+        } else {
+            false
         };
-        self.consume(Type::Keyword(Keyword::VACUUM));
 
-        match self.cur().ttype {
-            Type::Semicolon | Type::Ident(_) | Type::Keyword(Keyword::INTO) => {}
-            _ => {
+        let name = self.schema_table_container(None)?;
+
+        let mut table = nodes::CreateTable {
+            t,
+            temporary,
+            if_not_exists,
+            name,
+            columns: vec![],
+            constraints: vec![],
+            without_rowid: false,
+            strict: false,
+            select: None,
+        };
+
+        if self.is_keyword(Keyword::AS) {
+            self.advance();
+            table.select = Some(self.create_select_body(
+                "CREATE TABLE ... AS",
+                "https://www.sqlite.org/lang_createtable.html",
+            )?);
+            self.expect_end("https://www.sqlite.org/lang_createtable.html");
+            return some_box!(table);
+        }
+
+        if !self.is(Type::BraceLeft) {
+            let mut err = self.err(
+                "Unexpected Token",
+                &format!(
+                    "CREATE TABLE requires either a parenthesized list of column definitions or AS followed by a select-stmt, got {:?}",
+                    self.cur().ttype
+                ),
+                self.cur(),
+                Rule::Syntax,
+            );
+            err.doc_url = Some("https://www.sqlite.org/lang_createtable.html");
+            self.emit(err);
+            self.skip_until_semicolon_or_eof();
+            return None;
+        }
+        // skip (
+        self.advance();
+
+        loop {
+            // table constraints start with a keyword, column definitions with their name
+            if let Type::Keyword(
+                Keyword::CONSTRAINT
+                | Keyword::PRIMARY
+                | Keyword::UNIQUE
+                | Keyword::CHECK
+                | Keyword::FOREIGN,
+            ) = self.cur().ttype
+            {
+                table.constraints.push(self.table_constraint()?);
+            } else if !table.constraints.is_empty() {
                 let mut err = self.err(
-                    "Unexpected Token",
+                    "Column definition after table constraint",
                     &format!(
-                        "Wanted {:?} with {:?} or {:?} for VACUUM stmt, got {:?}",
-                        Type::Keyword(Keyword::INTO),
-                        Type::String("<filename>".to_string()),
-                        Type::Ident("<schema_name>".to_string()),
-                        self.cur().ttype.clone()
+                        "All column definitions have to precede the table constraints, got {:?}",
+                        self.cur().ttype
                     ),
-                    &self.cur().clone(),
+                    self.cur(),
                     Rule::Syntax,
                 );
-                err.doc_url = Some("https://www.sqlite.org/lang_vacuum.html");
-                self.errors.push(err);
-                self.advance(); // skip error_token
+                err.doc_url = Some("https://www.sqlite.org/lang_createtable.html");
+                self.emit(err);
+                self.skip_until_semicolon_or_eof();
+                return None;
+            } else {
+                table.columns.push(self.column_def()?);
+            }
+
+            if self.is(Type::Comma) {
+                self.advance();
+            } else {
+                break;
             }
         }
+        self.consume(Type::BraceRight);
 
-        // first path
-        if let Type::Semicolon = self.cur().ttype {
-            return some_box!(v);
+        // table-options: https://www.sqlite.org/syntax/table-options.html
+        while !self.is_eof() && !self.is(Type::Semicolon) {
+            // MySQL allows DEFAULT in front of its CHARSET and COLLATE table options
+            if self.is_keyword(Keyword::DEFAULT)
+                && self.tokens.get(self.pos + 1).is_some_and(|t| {
+                    matches!(&t.ttype, Type::Ident(name) if dialect::lookup(name, Position::TableOption).is_some())
+                })
+            {
+                self.advance();
+            }
+
+            if self.foreign_dialect(Position::TableOption) {
+                // skip the option, = and its value
+                self.advance();
+                self.advance();
+                if !self.is_eof() && !self.is(Type::Semicolon) {
+                    self.advance();
+                }
+                continue;
+            }
+
+            match &self.cur().ttype {
+                Type::Keyword(Keyword::WITHOUT) => {
+                    self.advance();
+                    if matches!(&self.cur().ttype, Type::Ident(name) if name.eq_ignore_ascii_case("ROWID"))
+                    {
+                        self.advance();
+                        table.without_rowid = true;
+                    } else {
+                        let mut err = self.err(
+                            "Unexpected Token",
+                            &format!("Wanted ROWID after WITHOUT, got {:?}", self.cur().ttype),
+                            self.cur(),
+                            Rule::Syntax,
+                        );
+                        err.doc_url = Some("https://www.sqlite.org/withoutrowid.html");
+                        self.emit(err);
+                        self.skip_until_semicolon_or_eof();
+                        return None;
+                    }
+                }
+                Type::Ident(name) if name.eq_ignore_ascii_case("STRICT") => {
+                    self.advance();
+                    table.strict = true;
+                }
+                _ => {
+                    let mut err = self.err(
+                        "Unexpected Token",
+                        &format!(
+                            "CREATE TABLE only allows the table options WITHOUT ROWID and STRICT after its column definitions, got {:?}",
+                            self.cur().ttype
+                        ),
+                        self.cur(),
+                        Rule::Syntax,
+                    );
+                    err.doc_url = Some("https://www.sqlite.org/syntax/table-options.html");
+                    self.emit(err);
+                    self.skip_until_semicolon_or_eof();
+                    return None;
+                }
+            }
+
+            if self.is(Type::Comma) {
+                self.advance();
+            }
         }
 
-        // if schema_name is specified
-        if let Type::Ident(_) = self.cur().ttype {
-            v.schema_name = Some(self.cur().clone());
-            self.advance(); // skip schema_name
+        self.expect_end("https://www.sqlite.org/lang_createtable.html");
+
+        some_box!(table)
+    }
+
+    /// https://www.sqlite.org/syntax/table-constraint.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn table_constraint(&mut self) -> Option<nodes::NamedTableConstraint> {
+        let mut name = None;
+        if self.is_keyword(Keyword::CONSTRAINT) {
+            self.advance();
+            name = Some(self.consume_ident(
+                "https://www.sqlite.org/syntax/table-constraint.html",
+                "name",
+            )?);
         }
 
-        // if INTO keyword is given is specified
-        if let Type::Keyword(Keyword::INTO) = self.cur().ttype {
-            self.advance(); // skip INTO
-            if let Type::String(_) = self.cur().ttype {
-                v.filename = Some(self.cur().clone());
-            } else {
+        let constraint = match self.cur().ttype {
+            Type::Keyword(Keyword::PRIMARY) => {
+                self.advance();
+                self.consume_keyword(Keyword::KEY);
+                nodes::TableConstraint::PrimaryKey {
+                    columns: self.indexed_columns()?,
+                    on_conflict: self.conflict_clause(),
+                }
+            }
+            Type::Keyword(Keyword::UNIQUE) => {
+                self.advance();
+                nodes::TableConstraint::Unique {
+                    columns: self.indexed_columns()?,
+                    on_conflict: self.conflict_clause(),
+                }
+            }
+            Type::Keyword(Keyword::CHECK) => {
+                self.advance();
+                self.consume(Type::BraceLeft);
+                let e = self.expr()?;
+                self.consume(Type::BraceRight);
+                nodes::TableConstraint::Check(Box::new(e))
+            }
+            Type::Keyword(Keyword::FOREIGN) => {
+                self.advance();
+                self.consume_keyword(Keyword::KEY);
+                self.consume(Type::BraceLeft);
+                let mut columns = vec![self.consume_ident(
+                    "https://www.sqlite.org/syntax/table-constraint.html",
+                    "column_name",
+                )?];
+                while self.is(Type::Comma) {
+                    self.advance();
+                    columns.push(self.consume_ident(
+                        "https://www.sqlite.org/syntax/table-constraint.html",
+                        "column_name",
+                    )?);
+                }
+                self.consume(Type::BraceRight);
+                nodes::TableConstraint::ForeignKey {
+                    columns,
+                    clause: self.foreign_key_clause()?,
+                }
+            }
+            _ => {
                 let mut err = self.err(
-                    "Unexpected Token",
+                    "Missing table constraint",
                     &format!(
-                        "Wanted {:?} for VACUUM stmt with {:?}, got {:?}",
-                        Type::String("<filename>".to_string()),
-                        Type::Keyword(Keyword::INTO),
-                        self.cur().ttype.clone()
+                        "Wanted PRIMARY KEY, UNIQUE, CHECK or FOREIGN KEY, got {:?}",
+                        self.cur().ttype
                     ),
-                    &self.cur().clone(),
+                    self.cur(),
                     Rule::Syntax,
                 );
-                err.doc_url = Some("https://www.sqlite.org/lang_vacuum.html");
-                self.errors.push(err);
+                err.doc_url = Some("https://www.sqlite.org/syntax/table-constraint.html");
+                self.emit(err);
+                self.skip_until_semicolon_or_eof();
+                return None;
             }
-            self.advance(); // skip filename or error token
-        }
+        };
 
-        self.expect_end("https://www.sqlite.org/lang_vacuum.html");
+        Some(nodes::NamedTableConstraint { name, constraint })
+    }
 
-        some_box!(v)
+    /// parses the parenthesized indexed-column list of PRIMARY KEY and UNIQUE table constraints,
+    /// see: https://www.sqlite.org/syntax/indexed-column.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn indexed_columns(&mut self) -> Option<Vec<nodes::OrderingTerm>> {
+        self.consume(Type::BraceLeft);
+        let mut columns = vec![self.ordering_term()?];
+        while self.is(Type::Comma) {
+            self.advance();
+            columns.push(self.ordering_term()?);
+        }
+        self.consume(Type::BraceRight);
+        Some(columns)
     }
 
-    /// see: https://www.sqlite.org/syntax/literal-value.html
+    /// https://www.sqlite.org/lang_createtrigger.html
     #[cfg_attr(feature = "trace", trace)]
-    fn literal_value(&mut self) -> Option<Box<dyn nodes::Node>> {
-        let cur = self.cur();
-        match cur.ttype {
-            Type::String(_)
-            | Type::Number(_)
-            | Type::Blob(_)
-            | Type::Keyword(Keyword::NULL)
-            | Type::Boolean(_)
-            | Type::Keyword(Keyword::CURRENT_TIME)
-            | Type::Keyword(Keyword::CURRENT_DATE)
-            | Type::Keyword(Keyword::CURRENT_TIMESTAMP) => {
-                let s: Option<Box<dyn nodes::Node>> = some_box!(nodes::Literal { t: cur.clone() });
-                // skipping over the current character
+    fn create_trigger_stmt(&mut self, t: Token, temporary: bool) -> Option<Box<dyn nodes::Node>> {
+        // skip TRIGGER
+        self.advance();
+
+        let if_not_exists = if self.is_keyword(Keyword::IF) {
+            self.advance();
+            self.consume_keyword(Keyword::NOT);
+            self.consume_keyword(Keyword::EXISTS);
+            true
+        } else {
+            false
+        };
+
+        let name = self.schema_table_container(Some("trigger"))?;
+
+        let timing = match self.cur().ttype {
+            Type::Keyword(Keyword::BEFORE) => {
                 self.advance();
-                s
+                Some(TriggerTiming::Before)
             }
+            Type::Keyword(Keyword::AFTER) => {
+                self.advance();
+                Some(TriggerTiming::After)
+            }
+            Type::Keyword(Keyword::INSTEAD) => {
+                self.advance();
+                self.consume_keyword(Keyword::OF);
+                Some(TriggerTiming::InsteadOf)
+            }
+            _ => None,
+        };
+
+        let mut update_of = None;
+        let event = match self.cur().ttype {
+            Type::Keyword(Keyword::DELETE) => {
+                self.advance();
+                TriggerEvent::Delete
+            }
+            Type::Keyword(Keyword::INSERT) => {
+                self.advance();
+                TriggerEvent::Insert
+            }
+            Type::Keyword(Keyword::UPDATE) => {
+                self.advance();
+                if self.is_keyword(Keyword::OF) {
+                    self.advance();
+                    let mut columns = vec![];
+                    loop {
+                        columns.push(self.consume_ident(
+                            "https://www.sqlite.org/lang_createtrigger.html",
+                            "column_name",
+                        )?);
+                        if self.is(Type::Comma) {
+                            self.advance();
+                        } else {
+                            break;
+                        }
+                    }
+                    update_of = Some(columns);
+                }
+                TriggerEvent::Update
+            }
+            _ => {
+                let mut err = self.err(
+                    "Unexpected Token",
+                    &format!(
+                        "CREATE TRIGGER requires either DELETE, INSERT or UPDATE at this point, got {:?}",
+                        self.cur().ttype
+                    ),
+                    self.cur(),
+                    Rule::Syntax,
+                );
+                err.doc_url = Some("https://www.sqlite.org/lang_createtrigger.html");
+                self.emit(err);
+                self.advance();
+                return None;
+            }
+        };
+
+        self.consume_keyword(Keyword::ON);
+        let table = self.consume_ident(
+            "https://www.sqlite.org/lang_createtrigger.html",
+            "table_name",
+        )?;
+
+        let for_each_row = if self.is_keyword(Keyword::FOR) {
+            self.advance();
+            self.consume_keyword(Keyword::EACH);
+            self.consume_keyword(Keyword::ROW);
+            true
+        } else {
+            false
+        };
+
+        let when = if self.is_keyword(Keyword::WHEN) {
+            self.advance();
+            self.expr()
+        } else {
+            None
+        };
+
+        self.consume_keyword(Keyword::BEGIN);
+
+        if self.is_keyword(Keyword::END) {
+            let mut err = self.err(
+                "Empty trigger body",
+                "The body of a trigger has to contain at least one DELETE, INSERT, UPDATE or SELECT statement",
+                self.cur(),
+                Rule::Syntax,
+            );
+            err.doc_url = Some("https://www.sqlite.org/lang_createtrigger.html");
+            self.emit(err);
+            self.skip_until_semicolon_or_eof();
+            return None;
+        }
+
+        let mut body = vec![];
+        while !self.is_eof() && !self.is_keyword(Keyword::END) {
+            match self.cur().ttype {
+                Type::Keyword(Keyword::DELETE)
+                | Type::Keyword(Keyword::INSERT)
+                | Type::Keyword(Keyword::REPLACE)
+                | Type::Keyword(Keyword::UPDATE)
+                | Type::Keyword(Keyword::SELECT) => match self.sql_stmt() {
+                    Some(stmt) => body.push(stmt),
+                    None => self.skip_until_semicolon_or_eof(),
+                },
+                _ => {
+                    let mut err = self.err(
+                        "Unexpected Token",
+                        &format!(
+                            "CREATE TRIGGER only allows DELETE, INSERT, UPDATE or SELECT statements in its body, got {:?}",
+                            self.cur().ttype
+                        ),
+                        self.cur(),
+                        Rule::Syntax,
+                    );
+                    err.doc_url = Some("https://www.sqlite.org/lang_createtrigger.html");
+                    self.emit(err);
+                    self.skip_until_semicolon_or_eof();
+                }
+            }
+            self.consume(Type::Semicolon);
+        }
+
+        self.consume_keyword(Keyword::END);
+        self.expect_end("https://www.sqlite.org/lang_createtrigger.html");
+
+        some_box!(nodes::CreateTrigger {
+            t,
+            temporary,
+            if_not_exists,
+            name,
+            timing,
+            event,
+            update_of,
+            table,
+            for_each_row,
+            when,
+            body,
+        })
+    }
+
+    /// https://www.sqlite.org/lang_createvtab.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn create_virtual_table_stmt(
+        &mut self,
+        t: Token,
+        temporary: bool,
+    ) -> Option<Box<dyn nodes::Node>> {
+        if temporary {
+            let mut err = self.err(
+                "Temporary virtual table",
+                "sqlite does not support temporary virtual tables, remove TEMP or TEMPORARY",
+                &t,
+                Rule::SqliteUnsupported,
+            );
+            err.doc_url = Some("https://www.sqlite.org/lang_createvtab.html");
+            self.emit(err);
+        }
+
+        // skip VIRTUAL
+        self.advance();
+        self.consume_keyword(Keyword::TABLE);
+
+        let if_not_exists = if self.is_keyword(Keyword::IF) {
+            self.advance();
+            self.consume_keyword(Keyword::NOT);
+            self.consume_keyword(Keyword::EXISTS);
+            true
+        } else {
+            false
+        };
+
+        let name = self.schema_table_container(None)?;
+
+        if !self.is_keyword(Keyword::USING) {
+            let mut err = self.err(
+                "Missing USING",
+                &format!(
+                    "CREATE VIRTUAL TABLE requires USING module-name at this point, got {:?}",
+                    self.cur().ttype
+                ),
+                self.cur(),
+                Rule::Syntax,
+            );
+            err.doc_url = Some("https://www.sqlite.org/lang_createvtab.html");
+            self.emit(err);
+            self.skip_until_semicolon_or_eof();
+            return None;
+        }
+        // skip USING
+        self.advance();
+
+        let module =
+            self.consume_ident("https://www.sqlite.org/lang_createvtab.html", "module_name")?;
+
+        // module arguments are not sql, but interpreted by the module, thus only the nesting of
+        // parenthesis is tracked to find the end of each argument
+        let mut arguments = vec![];
+        if self.is(Type::BraceLeft) {
+            let open = self.cur().clone();
+            self.advance();
+            let mut argument = vec![];
+            let mut depth = 0;
+            loop {
+                match self.cur().ttype {
+                    // stop at the end of the statement to not consume the following statements
+                    Type::Eof | Type::Semicolon => {
+                        let mut err = self.err(
+                            "Unterminated module arguments",
+                            "The module arguments of CREATE VIRTUAL TABLE are missing their closing parenthesis",
+                            &open,
+                            Rule::Syntax,
+                        );
+                        err.doc_url = Some("https://www.sqlite.org/lang_createvtab.html");
+                        self.emit(err);
+                        return None;
+                    }
+                    Type::BraceRight if depth == 0 => {
+                        self.advance();
+                        break;
+                    }
+                    Type::Comma if depth == 0 => {
+                        arguments.push(std::mem::take(&mut argument));
+                        self.advance();
+                        continue;
+                    }
+                    Type::BraceLeft => depth += 1,
+                    Type::BraceRight => depth -= 1,
+                    _ => {}
+                }
+                argument.push(self.cur().clone());
+                self.advance();
+            }
+            // USING module() passes no arguments
+            if !argument.is_empty() || !arguments.is_empty() {
+                arguments.push(argument);
+            }
+        }
+
+        self.expect_end("https://www.sqlite.org/lang_createvtab.html");
+
+        some_box!(nodes::CreateVirtualTable {
+            t,
+            if_not_exists,
+            name,
+            module,
+            arguments,
+        })
+    }
+
+    /// https://www.sqlite.org/lang_createview.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn create_view_stmt(&mut self, t: Token, temporary: bool) -> Option<Box<dyn nodes::Node>> {
+        // skip VIEW
+        self.advance();
+
+        let if_not_exists = if self.is_keyword(Keyword::IF) {
+            self.advance();
+            self.consume_keyword(Keyword::NOT);
+            self.consume_keyword(Keyword::EXISTS);
+            true
+        } else {
+            false
+        };
+
+        let name = self.schema_table_container(Some("view"))?;
+
+        let mut columns = vec![];
+        if self.is(Type::BraceLeft) {
+            self.advance();
+            if self.is(Type::BraceRight) {
+                let mut err = self.err(
+                    "Empty column list",
+                    "The column list of a view has to contain at least one column, omit the parenthesis to use the columns of its select",
+                    self.cur(),
+                    Rule::Syntax,
+                );
+                err.doc_url = Some("https://www.sqlite.org/lang_createview.html");
+                self.emit(err);
+                self.skip_until_semicolon_or_eof();
+                return None;
+            }
+            columns.push(
+                self.consume_ident("https://www.sqlite.org/lang_createview.html", "column_name")?,
+            );
+            while self.is(Type::Comma) {
+                self.advance();
+                columns.push(
+                    self.consume_ident(
+                        "https://www.sqlite.org/lang_createview.html",
+                        "column_name",
+                    )?,
+                );
+            }
+            self.consume(Type::BraceRight);
+        }
+
+        self.consume_keyword(Keyword::AS);
+
+        let select =
+            self.create_select_body("a view", "https://www.sqlite.org/lang_createview.html")?;
+
+        self.expect_end("https://www.sqlite.org/lang_createview.html");
+
+        some_box!(nodes::CreateView {
+            t,
+            temporary,
+            if_not_exists,
+            name,
+            columns,
+            select,
+        })
+    }
+
+    /// parses the select-stmt forming the body of a view or of CREATE TABLE ... AS, what names
+    /// the statement in error messages
+    #[cfg_attr(feature = "trace", trace)]
+    fn create_select_body(
+        &mut self,
+        what: &str,
+        doc: &'static str,
+    ) -> Option<Box<dyn nodes::Node>> {
+        let select: Box<dyn nodes::Node> = match self.cur().ttype {
+            Type::Keyword(Keyword::SELECT) => Box::new(self.select()?),
+            Type::Keyword(Keyword::VALUES) => Box::new(self.values()?),
+            Type::Keyword(Keyword::WITH) => {
+                let with = self.cur().clone();
+                let (recursive, ctes) = self.with_clause()?;
+                let child: Box<dyn nodes::Node> = match self.cur().ttype {
+                    Type::Keyword(Keyword::SELECT) => Box::new(self.select()?),
+                    Type::Keyword(Keyword::VALUES) => Box::new(self.values()?),
+                    _ => {
+                        let mut err = self.err(
+                            "Unexpected Token",
+                            &format!(
+                                "The WITH clause of {what} has to be followed by SELECT or VALUES, got {:?}",
+                                self.cur().ttype
+                            ),
+                            self.cur(),
+                            Rule::Syntax,
+                        );
+                        err.doc_url = Some(doc);
+                        self.emit(err);
+                        self.advance();
+                        return None;
+                    }
+                };
+                Box::new(nodes::WithClause {
+                    t: with,
+                    recursive,
+                    ctes,
+                    child,
+                })
+            }
+            _ => {
+                let mut err = self.err(
+                    "Unexpected Token",
+                    &format!(
+                        "The body of {what} has to be a select-stmt, got {:?}",
+                        self.cur().ttype
+                    ),
+                    self.cur(),
+                    Rule::Syntax,
+                );
+                err.doc_url = Some(doc);
+                self.emit(err);
+                self.advance();
+                return None;
+            }
+        };
+        Some(select)
+    }
+
+    /// https://www.sqlite.org/pragma.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn pragma_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
+        let t = self.cur().clone();
+
+        // skip PRAGMA
+        self.advance();
+
+        // PRAGMA needs a target name
+        let schema_and_pragma = self.schema_table_container(Some("pragma"))?;
+        // schema_table_container consumed the pragma name last
+        let name = self.tokens[self.pos - 1].clone();
+        self.pragma_name(&schema_and_pragma, &name);
+
+        let pragma = if self.is(Type::Semicolon) {
+            Pragma {
+                t,
+                name: schema_and_pragma,
+                invocation: nodes::PragmaInvocation::Query,
+            }
+        } else if self.is(Type::Equal) {
+            self.advance();
+            match self.cur().ttype {
+                Type::String(_)
+                | Type::Integer(_)
+                | Type::Float(_)
+                | Type::Ident(_)
+                | Type::Keyword(_)
+                | Type::Boolean(_) => {}
+                _ => {
+                    let cur = self.cur().clone();
+                    self.push_err("Bad pragma value", &format!("A pragmas assignment value has to be either String, Number, Ident, Boolean or a Keyword, got {:?} instead", cur.ttype), &cur, Rule::Syntax,);
+                    self.advance();
+                }
+            }
+            let p = Pragma {
+                t,
+                name: schema_and_pragma,
+                invocation: nodes::PragmaInvocation::Assign {
+                    value: self.cur().clone(),
+                },
+            };
+            self.advance();
+            p
+        } else if self.is(Type::BraceLeft) {
+            self.advance();
+            match self.cur().ttype {
+                Type::String(_)
+                | Type::Integer(_)
+                | Type::Float(_)
+                | Type::Ident(_)
+                | Type::Keyword(_)
+                | Type::Boolean(_) => {}
+                _ => {
+                    let cur = self.cur().clone();
+                    self.push_err("Bad pragma value", &format!("A pragmas call value has to be either String, Number, Ident, Boolean or a Keyword, got {:?} instead", cur.ttype), &cur, Rule::Syntax,);
+                    self.advance();
+                }
+            }
+            let p = Pragma {
+                t,
+                name: schema_and_pragma,
+                invocation: nodes::PragmaInvocation::Call {
+                    value: self.cur().clone(),
+                },
+            };
+            self.advance();
+            self.consume(Type::BraceRight);
+            p
+        } else {
+            let cur = self.cur().clone();
+            self.push_err(
+                "Bad pragma value",
+                &format!(
+                    "A pragmas rhs value has to be either an assignment via '=', a call via '(<arg>)' or simply be a query, got {:?} instead",
+                    cur.ttype
+                ),
+                &cur,
+                Rule::Syntax,
+            );
+            self.advance();
+            return None;
+        };
+
+        if let nodes::PragmaInvocation::Assign { value } | nodes::PragmaInvocation::Call { value } =
+            &pragma.invocation
+        {
+            self.pragma_boolean_value(&pragma.name, value);
+        }
+
+        self.expect_end("https://www.sqlite.org/pragma.html");
+
+        some_box!(pragma)
+    }
+
+    /// checks the name of a pragma against the pragmas sqlite knows and the ones configured via
+    /// `pragmas`, emits [Rule::UnknownPragma] with the closest known pragma for unknown names.
+    /// Quoted names are exempt, since quoting signals a deliberately unusual name
+    fn pragma_name(&mut self, name: &SchemaTableContainer, token: &Token) {
+        let name = name.table();
+        // quoted identifiers lex to plain identifiers, but their token spans the quotes too.
+        // Token spans count characters, not bytes
+        let quoted = matches!(token.ttype, Type::String(_))
+            || token.end - token.start != name.chars().count();
+        if quoted
+            || pragma::is_known(name)
+            || self.pragmas.iter().any(|p| p.eq_ignore_ascii_case(name))
+        {
+            return;
+        }
+        let mut err = self.err(
+            "Unknown pragma",
+            &format!(
+                "'{name}' is not a pragma sqlite knows, did you mean: {}",
+                pragma::suggestion(name)
+            ),
+            token,
+            Rule::UnknownPragma,
+        );
+        err.doc_url = Some("https://www.sqlite.org/pragma.html#toc");
+        self.emit(err);
+    }
+
+    fn pragma_boolean_value(&mut self, name: &SchemaTableContainer, value: &Token) {
+        let name = name.table();
+        if !pragma::is_boolean(name) {
+            return;
+        }
+
+        let mut err = match &value.ttype {
+            Type::Boolean(_) => return,
+            Type::Integer(0 | 1) => return,
+            Type::Ident(s) | Type::String(s) if pragma::is_boolean_spelling(s) => return,
+            Type::Keyword(k) if pragma::is_boolean_spelling((*k).into()) => return,
+            Type::Integer(n) => self.err(
+                "Unusual boolean pragma value",
+                &format!(
+                    "`{name}` treats any non zero integer as true, consider using 1, ON, TRUE or YES instead of {n}"
+                ),
+                value,
+                Rule::Quirk,
+            ),
+            _ => self.err(
+                "Bad boolean pragma value",
+                &format!(
+                    "`{name}` accepts a boolean: 0, 1, ON, OFF, TRUE, FALSE, YES or NO, got {:?}, sqlite silently treats unknown values as false",
+                    value.ttype
+                ),
+                value,
+                Rule::Syntax,
+            ),
+        };
+        err.doc_url = Some("https://www.sqlite.org/pragma.html#syntax");
+        self.emit(err);
+    }
+
+    /// https://www.sqlite.org/lang_altertable.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn alter_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
+        let mut a = nodes::Alter {
+            t: self.cur().clone(),
+            target: SchemaTableContainer::Table(String::new()),
+            rename_to: None,
+            rename_column_target: None,
+            new_column_name: None,
+            add_column: None,
+            drop_column: None,
+        };
+
+        self.advance();
+        self.consume(Type::Keyword(Keyword::TABLE));
+
+        a.target = self.schema_table_container(None)?;
+
+        match self.cur().ttype {
+            Type::Keyword(Keyword::RENAME) => {
+                self.advance();
+                if self.is(Type::Keyword(Keyword::TO)) {
+                    // RENAME TO <new_table_name>
+                    self.advance();
+                    let new_table_name = self.consume_ident(
+                        "https://www.sqlite.org/lang_altertable.html",
+                        "new_table_name",
+                    )?;
+                    a.rename_to = Some(new_table_name);
+                } else {
+                    if self.is(Type::Keyword(Keyword::COLUMN)) {
+                        self.advance();
+                    }
+
+                    a.rename_column_target = self.consume_ident(
+                        "https://www.sqlite.org/lang_altertable.html",
+                        "column_name",
+                    );
+                    self.consume(Type::Keyword(Keyword::TO));
+                    a.new_column_name = self.consume_ident(
+                        "https://www.sqlite.org/lang_altertable.html",
+                        "column_name",
+                    );
+                }
+            }
+            Type::Keyword(Keyword::ADD) => {
+                self.advance();
+                if self.is(Type::Keyword(Keyword::COLUMN)) {
+                    self.advance();
+                }
+
+                a.add_column = self.column_def();
+            }
+            Type::Keyword(Keyword::DROP) => {
+                self.advance();
+                if self.is(Type::Keyword(Keyword::COLUMN)) {
+                    self.advance();
+                }
+                a.drop_column = self
+                    .consume_ident("https://www.sqlite.org/lang_altertable.html", "column_name");
+            }
+            _ => {
+                let mut err = self.err(
+                    "Unexpected Token",
+                    &format!(
+                        "ALTER requires either RENAME, ADD or DROP at this point, got {:?}",
+                        self.cur().ttype
+                    ),
+                    self.cur(),
+                    Rule::Syntax,
+                );
+                err.doc_url = Some("https://www.sqlite.org/lang_altertable.html");
+                self.emit(err);
+                self.advance();
+                return None;
+            }
+        }
+
+        self.expect_end("https://www.sqlite.org/lang_altertable.html");
+
+        some_box!(a)
+    }
+
+    /// https://www.sqlite.org/syntax/reindex-stmt.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn reindex_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
+        let mut r = nodes::Reindex {
+            t: self.cur().clone(),
+            target: None,
+        };
+        self.advance();
+
+        // REINDEX has a path with no further nodes
+        if self.is(Type::Semicolon) {
+            return some_box!(r);
+        }
+
+        r.target = self.schema_table_container(None);
+
+        self.expect_end("https://www.sqlite.org/syntax/reindex-stmt.html");
+
+        some_box!(r)
+    }
+
+    /// https://www.sqlite.org/syntax/attach-stmt.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn attach_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
+        let t = self.cur().clone();
+        // skipping ATTACH
+        self.advance();
+        // skipping optional DATABASE
+        if self.is(Type::Keyword(Keyword::DATABASE)) {
+            self.advance();
+        }
+
+        let mut a = nodes::Attach {
+            t,
+            schema_name: String::new(),
+            expr: self.expr()?,
+        };
+
+        self.consume(Type::Keyword(Keyword::AS));
+
+        a.schema_name =
+            self.consume_ident("https://www.sqlite.org/lang_attach.html", "schema_name")?;
+
+        self.expect_end("https://www.sqlite.org/lang_attach.html");
+
+        some_box!(a)
+    }
+
+    /// https://www.sqlite.org/syntax/release-stmt.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn release_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
+        let mut r = nodes::Release {
+            t: self.cur().clone(),
+            savepoint_name: String::new(),
+        };
+        self.advance();
+
+        if self.is(Type::Keyword(Keyword::SAVEPOINT)) {
+            self.advance();
+        }
+
+        r.savepoint_name = self.consume_ident(
+            "https://www.sqlite.org/syntax/release-stmt.html",
+            "savepoint_name",
+        )?;
+
+        self.expect_end("https://www.sqlite.org/syntax/release-stmt.html");
+
+        some_box!(r)
+    }
+
+    /// https://www.sqlite.org/syntax/savepoint-stmt.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn savepoint_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
+        let mut s = nodes::Savepoint {
+            t: self.cur().clone(),
+            savepoint_name: String::new(),
+        };
+        self.advance();
+        s.savepoint_name = self.consume_ident(
+            "https://www.sqlite.org/syntax/savepoint-stmt.html",
+            "savepoint_name",
+        )?;
+        self.expect_end("https://www.sqlite.org/lang_savepoint.html");
+
+        some_box!(s)
+    }
+
+    /// https://www.sqlite.org/lang_dropindex.html
+    /// https://www.sqlite.org/lang_droptable.html
+    /// https://www.sqlite.org/lang_droptrigger.html
+    /// https://www.sqlite.org/lang_dropview.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn drop_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
+        let t = self.cur().clone();
+        self.advance();
+
+        match self.cur().ttype {
+            Type::Keyword(Keyword::INDEX) => (),
+            Type::Keyword(Keyword::TABLE) => (),
+            Type::Keyword(Keyword::TRIGGER) => (),
+            Type::Keyword(Keyword::VIEW) => (),
+            _ => {
+                let mut err = self.err(
+                        "Unexpected Token",
+                        &format!(
+                            "DROP requires either TRIGGER, TABLE, TRIGGER or VIEW at this point, got {:?}",
+                            self.cur().ttype
+                        ),
+                        self.cur(),
+                        Rule::Syntax,
+                    );
+                err.doc_url = Some("https://www.sqlite.org/lang.html");
+                self.emit(err);
+                self.advance();
+                return None;
+            }
+        }
+
+        let ttype = {
+            let Type::Keyword(keyword) = &self.cur().ttype else {
+                unreachable!("self.cur() in (in the set theory kind) {{INDEX,TABLE,TRIGGER,VIEW}}")
+            };
+            *keyword
+        };
+
+        // skip either INDEX;TABLE;TRIGGER or VIEW
+        self.advance();
+
+        let if_exists = if self.is(Type::Keyword(Keyword::IF)) {
+            self.advance();
+            self.consume(Type::Keyword(Keyword::EXISTS));
+            true
+        } else {
+            false
+        };
+
+        let argument = self.schema_table_container(None)?;
+
+        some_box!(nodes::Drop {
+            t,
+            ttype,
+            if_exists,
+            argument,
+        })
+    }
+
+    /// https://www.sqlite.org/syntax/analyze-stmt.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn analyse_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
+        let mut a = nodes::Analyze {
+            t: self.cur().clone(),
+            target: None,
+        };
+
+        self.advance();
+
+        // inlined Parser::schema_table_container
+        a.target = match self.cur().ttype.clone() {
+            Type::Ident(schema) if self.next_is(Type::Dot) => {
+                self.advance();
+                self.advance();
+                if let Type::Ident(table) = &self.cur().ttype {
+                    let table = table.clone();
+                    self.advance();
+                    Some(SchemaTableContainer::SchemaAndTable { schema, table })
+                } else if let Type::String(table) = &self.cur().ttype {
+                    let table = table.clone();
+                    self.advance();
+                    Some(SchemaTableContainer::SchemaAndTable { schema, table })
+                } else {
+                    let cur = self.cur().clone();
+                    self.emit(match cur.ttype {
+                        Type::Keyword(keyword) => {
+                            let as_str: &str = keyword.into();
+                            self.err(
+                            "Malformed table name",
+                            &format!("`{as_str}` is a keyword, if you want to use it as a table or column name, quote it: '{as_str}'"),
+                            &cur, Rule::Syntax)
+                        }
+                        _ => self.err(
+                            "Malformed table name",
+                            &format!(
+                                "expected a table name after <schema_name>. - got {:?}",
+                                cur.ttype
+                            ),
+                            &cur,
+                            Rule::Syntax,
+                        ),
+                    });
+
+                    // skip wrong token, should I skip to the next statement via
+                    // self.skip_until_semicolon_or_eof?
+                    self.advance();
+                    None
+                }
+            }
+            Type::Ident(table_name) | Type::String(table_name) => {
+                // skip table_name
+                self.advance();
+                Some(SchemaTableContainer::Table(table_name))
+            }
+            _ => None,
+        };
+
+        self.expect_end("https://www.sqlite.org/lang_analyze.html");
+
+        some_box!(a)
+    }
+
+    /// https://www.sqlite.org/syntax/detach-stmt.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn detach_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
+        let t = self.cur().clone();
+        self.advance();
+
+        // skip optional DATABASE path
+        if self.is(Type::Keyword(Keyword::DATABASE)) {
+            self.advance();
+        }
+
+        let schema_name =
+            self.consume_ident("https://www.sqlite.org/lang_detach.html", "schema_name")?;
+
+        let d = nodes::Detach { t, schema_name };
+
+        self.expect_end("https://www.sqlite.org/lang_detach.html");
+
+        some_box!(d)
+    }
+
+    /// https://www.sqlite.org/syntax/rollback-stmt.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn rollback_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
+        let mut rollback = nodes::Rollback {
+            t: self.cur().clone(),
+            save_point: None,
+        };
+        self.advance();
+
+        match self.cur().ttype {
+            Type::Keyword(Keyword::TRANSACTION) | Type::Keyword(Keyword::TO) | Type::Semicolon => {}
+            _ => {
+                let mut err = self.err(
+                    "Unexpected Token",
+                    &format!(
+                        "ROLLBACK requires TRANSACTION, TO or to end at this point, got {:?}",
+                        self.cur().ttype
+                    ),
+                    self.cur(),
+                    Rule::Syntax,
+                );
+                err.doc_url = Some("https://www.sqlite.org/lang_transaction.html");
+                self.emit(err);
+            }
+        }
+
+        // optional TRANSACTION
+        if self.is(Type::Keyword(Keyword::TRANSACTION)) {
+            self.advance();
+        }
+
+        // optional TO
+        if self.is(Type::Keyword(Keyword::TO)) {
+            self.advance();
+
+            // optional SAVEPOINT
+            if self.is(Type::Keyword(Keyword::SAVEPOINT)) {
+                self.advance();
+            }
+
+            match self.cur().ttype {
+                Type::Keyword(Keyword::SAVEPOINT) | Type::Ident(_) | Type::Semicolon => {}
+                _ => {
+                    let mut err = self.err(
+                        "Unexpected Token",
+                        &format!(
+                            "ROLLBACK requires SAVEPOINT, Ident or to end at this point, got {:?}",
+                            self.cur().ttype
+                        ),
+                        self.cur(),
+                        Rule::Syntax,
+                    );
+                    err.doc_url = Some("https://www.sqlite.org/lang_transaction.html");
+                    self.emit(err);
+                    self.advance();
+                }
+            }
+
+            if let Type::Ident(str) = &self.cur().ttype {
+                rollback.save_point = Some(String::from(str));
+            } else {
+                let mut err = self.err(
+                    "Unexpected Token",
+                    &format!(
+                        "ROLLBACK wants Ident as <savepoint-name>, got {:?}",
+                        self.cur().ttype
+                    ),
+                    self.cur(),
+                    Rule::Syntax,
+                );
+                err.doc_url = Some("https://www.sqlite.org/lang_transaction.html");
+                self.emit(err);
+            }
+            self.advance();
+        }
+
+        self.expect_end("https://www.sqlite.org/lang_transaction.html");
+
+        some_box!(rollback)
+    }
+
+    /// https://www.sqlite.org/syntax/commit-stmt.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn commit_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
+        let commit: Option<Box<dyn nodes::Node>> = some_box!(nodes::Commit {
+            t: self.cur().clone(),
+        });
+
+        // skip either COMMIT or END
+        self.advance();
+
+        match self.cur().ttype {
+            // expected end 1
+            Type::Semicolon => (),
+            // expected end 2, optional
+            Type::Keyword(Keyword::TRANSACTION) => self.advance(),
+            _ => {
+                let mut err = self.err(
+                    "Unexpected Token",
+                    &format!(
+                        "Wanted Keyword(TRANSACTION) or Semicolon, got {:?}",
+                        self.cur().ttype
+                    ),
+                    self.cur(),
+                    Rule::Syntax,
+                );
+                err.doc_url = Some("https://www.sqlite.org/lang_transaction.html");
+                self.emit(err);
+                self.advance();
+            }
+        }
+
+        self.expect_end("https://www.sqlite.org/lang_transaction.html");
+
+        commit
+    }
+
+    /// https://www.sqlite.org/syntax/begin-stmt.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn begin_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
+        let mut begin: nodes::Begin = nodes::Begin {
+            t: self.cur().clone(),
+            transaction_kind: None,
+        };
+
+        // skip BEGIN
+        self.advance();
+
+        // skip modifiers
+        match self.cur().ttype {
+            // BEGIN;
+            Type::Semicolon => return some_box!(begin),
+            Type::Keyword(Keyword::DEFERRED)
+            | Type::Keyword(Keyword::IMMEDIATE)
+            | Type::Keyword(Keyword::EXCLUSIVE) => {
+                begin.transaction_kind = if let Type::Keyword(word) = &self.cur().ttype {
+                    Some(*word)
+                } else {
+                    None
+                };
+                self.advance()
+            }
+            _ => {}
+        }
+
+        match self.cur().ttype {
+            Type::Semicolon => return some_box!(begin),
+            // ending
+            Type::Keyword(Keyword::TRANSACTION) => self.advance(),
+            Type::Keyword(Keyword::DEFERRED)
+            | Type::Keyword(Keyword::IMMEDIATE)
+            | Type::Keyword(Keyword::EXCLUSIVE) => {
+                let mut err = self.err(
+                    "Unexpected Token",
+                    "BEGIN does not allow multiple transaction behaviour modifiers",
+                    self.cur(),
+                    Rule::Syntax,
+                );
+                err.doc_url = Some("https://www.sqlite.org/lang_transaction.html");
+                self.emit(err);
+                // TODO: think about if this is smart at this point, skipping to the next ; could
+                // be skipping too many tokens
+                self.skip_until_semicolon_or_eof();
+            }
+            _ => {
+                let mut err = self.err(
+                    "Unexpected Token",
+                    &format!(
+                        "Wanted any of TRANSACTION, DEFERRED, IMMEDIATE or EXCLUSIVE before this point, got {:?}",
+                        self.cur().ttype
+                    ),
+                    self.cur(),
+                    Rule::Syntax,
+                );
+                err.doc_url = Some("https://www.sqlite.org/lang_transaction.html");
+                self.emit(err);
+            }
+        }
+
+        self.expect_end("https://www.sqlite.org/lang_transaction.html");
+
+        some_box!(begin)
+    }
+
+    /// https://www.sqlite.org/lang_vacuum.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn vacuum_stmt(&mut self) -> Option<Box<dyn nodes::Node>> {
+        let mut v = nodes::Vacuum {
+            t: self.cur().clone(),
+            schema_name: None,
+            filename: None,
+        };
+        self.consume(Type::Keyword(Keyword::VACUUM));
+
+        match self.cur().ttype {
+            Type::Semicolon | Type::Ident(_) | Type::Keyword(Keyword::INTO) => {}
+            _ => {
+                let mut err = self.err(
+                    "Unexpected Token",
+                    &format!(
+                        "Wanted {:?} with {:?} or {:?} for VACUUM stmt, got {:?}",
+                        Type::Keyword(Keyword::INTO),
+                        Type::String("<filename>".to_string()),
+                        Type::Ident("<schema_name>".to_string()),
+                        self.cur().ttype.clone()
+                    ),
+                    &self.cur().clone(),
+                    Rule::Syntax,
+                );
+                err.doc_url = Some("https://www.sqlite.org/lang_vacuum.html");
+                self.emit(err);
+                self.advance(); // skip error_token
+            }
+        }
+
+        // first path
+        if let Type::Semicolon = self.cur().ttype {
+            return some_box!(v);
+        }
+
+        // if schema_name is specified
+        if let Type::Ident(_) = self.cur().ttype {
+            v.schema_name = Some(self.cur().clone());
+            self.advance(); // skip schema_name
+        }
+
+        // if INTO keyword is given is specified
+        if let Type::Keyword(Keyword::INTO) = self.cur().ttype {
+            self.advance(); // skip INTO
+            if let Type::String(_) = self.cur().ttype {
+                v.filename = Some(self.cur().clone());
+            } else {
+                let mut err = self.err(
+                    "Unexpected Token",
+                    &format!(
+                        "Wanted {:?} for VACUUM stmt with {:?}, got {:?}",
+                        Type::String("<filename>".to_string()),
+                        Type::Keyword(Keyword::INTO),
+                        self.cur().ttype.clone()
+                    ),
+                    &self.cur().clone(),
+                    Rule::Syntax,
+                );
+                err.doc_url = Some("https://www.sqlite.org/lang_vacuum.html");
+                self.emit(err);
+            }
+            self.advance(); // skip filename or error token
+        }
+
+        self.expect_end("https://www.sqlite.org/lang_vacuum.html");
+
+        some_box!(v)
+    }
+
+    /// see: https://www.sqlite.org/syntax/literal-value.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn literal_value(&mut self) -> Option<Box<dyn nodes::Node>> {
+        let cur = self.cur();
+        match cur.ttype {
+            Type::String(_)
+            | Type::Integer(_)
+            | Type::Float(_)
+            | Type::Blob(_)
+            | Type::Keyword(Keyword::NULL)
+            | Type::Boolean(_)
+            | Type::Keyword(Keyword::CURRENT_TIME)
+            | Type::Keyword(Keyword::CURRENT_DATE)
+            | Type::Keyword(Keyword::CURRENT_TIMESTAMP) => {
+                let s: Option<Box<dyn nodes::Node>> = some_box!(nodes::Literal { t: cur.clone() });
+                // skipping over the current character
+                self.advance();
+                s
+            }
+            _ => {
+                let mut err = self.err("Unexpected Token", &format!("Wanted a literal (any of number,string,blob,null,true,false,CURRENT_TIME,CURRENT_DATE,CURRENT_DATE), got {:?}", cur.ttype),cur, Rule::Syntax);
+                err.doc_url = Some("https://www.sqlite.org/syntax/literal-value.html");
+                self.emit(err);
+                self.advance();
+                None
+            }
+        }
+    }
+
+    /// parses an sql expression: https://www.sqlite.org/syntax/expr.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn expr(&mut self) -> Option<nodes::Expr> {
+        self.expr_with_precedence(0)
+    }
+
+    /// binding power of binary operators, see:
+    /// https://www.sqlite.org/lang_expr.html#operators_and_parse_affecting_attributes
+    fn binary_precedence(ttype: &Type) -> Option<u8> {
+        Some(match ttype {
+            Type::Keyword(Keyword::OR) => 1,
+            Type::Keyword(Keyword::AND) => 2,
+            Type::Equal | Type::DoubleEqual | Type::NotEqual => 4,
+            Type::LessThan | Type::LessEq | Type::GreaterThan | Type::GreaterEq => 5,
+            Type::Ampersand | Type::Pipe | Type::ShiftLeft | Type::ShiftRight => 7,
+            Type::Plus | Type::Minus => 8,
+            Type::Asterisk | Type::Slash | Type::Percent => 9,
+            // sqlite groups the json operators with ||, not below arithmetic
+            Type::Concat | Type::Arrow | Type::DoubleArrow => 10,
+            _ => return None,
+        })
+    }
+
+    /// wraps an expression made up of other expressions into an Expr
+    fn compound_expr(t: Token, compound: Box<dyn nodes::Node>) -> nodes::Expr {
+        nodes::Expr {
+            t,
+            literal: None,
+            bind: None,
+            schema: None,
+            table: None,
+            column: None,
+            compound: Some(compound),
+        }
+    }
+
+    /// precedence climbing over binary operators, all of them are left associative, nesting is
+    /// limited to [Parser::max_expr_depth]
+    #[cfg_attr(feature = "trace", trace)]
+    fn expr_with_precedence(&mut self, min_precedence: u8) -> Option<nodes::Expr> {
+        self.nested(|p| p.expr_with_precedence_inner(min_precedence))
+    }
+
+    /// calls f one level of nesting deeper, emits a Rule::Syntax error instead if this exceeds
+    /// [Parser::max_expr_depth]. The stack is grown on demand, so the limit is reached before the
+    /// stack runs out regardless of the stack size of the calling thread
+    fn nested<T>(&mut self, f: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+        if self.expr_depth >= self.max_expr_depth {
+            let mut err = self.err(
+                "Expression nesting too deep",
+                &format!(
+                    "Expressions and subqueries may be nested at most {} levels deep, consider simplifying the expression",
+                    self.max_expr_depth
+                ),
+                self.cur(),
+                Rule::Syntax,
+            );
+            err.doc_url = Some("https://www.sqlite.org/limits.html#max_expr_depth");
+            self.emit(err);
+            self.skip_until_semicolon_or_eof();
+            return None;
+        }
+        self.expr_depth += 1;
+        let result = stacker::maybe_grow(Self::STACK_RED_ZONE, Self::STACK_SEGMENT, || f(self));
+        self.expr_depth -= 1;
+        result
+    }
+
+    fn expr_with_precedence_inner(&mut self, min_precedence: u8) -> Option<nodes::Expr> {
+        let mut lhs = self.primary_expr()?;
+        loop {
+            // keyword operators share the precedence of the equality operators and may be
+            // negated via a NOT prefix: x NOT BETWEEN 1 AND 2
+            let negated = self.is_keyword(Keyword::NOT)
+                && self.tokens.get(self.pos + 1).is_some_and(|t| {
+                    matches!(
+                        t.ttype,
+                        Type::Keyword(
+                            Keyword::BETWEEN
+                                | Keyword::IN
+                                | Keyword::LIKE
+                                | Keyword::GLOB
+                                | Keyword::MATCH
+                                | Keyword::REGEXP
+                                | Keyword::NULL
+                        )
+                    )
+                });
+            let keyword_operator = if negated {
+                self.tokens.get(self.pos + 1).map(|t| t.ttype.clone())
+            } else {
+                Some(self.cur().ttype.clone())
+            };
+
+            match keyword_operator {
+                // x IS [NOT] y, x ISNULL, x NOTNULL and x NOT NULL
+                Some(Type::Keyword(Keyword::IS | Keyword::ISNULL | Keyword::NOTNULL))
+                | Some(Type::Keyword(Keyword::NULL))
+                    if negated || !self.is_keyword(Keyword::NULL) =>
+                {
+                    if 4 <= min_precedence {
+                        break;
+                    }
+                    lhs = self.is_expr(lhs)?;
+                }
+                Some(Type::Keyword(
+                    Keyword::BETWEEN
+                    | Keyword::IN
+                    | Keyword::LIKE
+                    | Keyword::GLOB
+                    | Keyword::MATCH
+                    | Keyword::REGEXP,
+                )) => {
+                    if 4 <= min_precedence {
+                        break;
+                    }
+                    let t = self.cur().clone();
+                    if negated {
+                        // skip NOT
+                        self.advance();
+                    }
+                    lhs = self.keyword_operator_expr(t, lhs, negated)?;
+                }
+                _ => {
+                    let Some(precedence) = Self::binary_precedence(&self.cur().ttype) else {
+                        break;
+                    };
+                    if precedence <= min_precedence {
+                        break;
+                    }
+                    let op = self.cur().clone();
+                    // skip operator
+                    self.advance();
+                    let rhs = self.expr_with_precedence(precedence)?;
+                    lhs = Self::compound_expr(
+                        op.clone(),
+                        Box::new(nodes::BinaryExpr {
+                            t: op.clone(),
+                            op,
+                            left: Box::new(lhs),
+                            right: Box::new(rhs),
+                        }),
+                    );
+                }
+            }
+        }
+        Some(lhs)
+    }
+
+    /// parses [NOT] EXISTS (select-stmt), the current token is either NOT or EXISTS, see:
+    /// https://www.sqlite.org/lang_expr.html#the_exists_operator
+    #[cfg_attr(feature = "trace", trace)]
+    fn exists_expr(&mut self, negated: bool) -> Option<nodes::Expr> {
+        let t = self.cur().clone();
+        if negated {
+            // skip NOT
+            self.advance();
+        }
+        // skip EXISTS
+        self.advance();
+        self.consume(Type::BraceLeft);
+        if !self.is_keyword(Keyword::SELECT) {
+            let mut err = self.err(
+                "Unexpected Token",
+                &format!(
+                    "EXISTS requires a select-stmt in parenthesis, got {:?}",
+                    self.cur().ttype
+                ),
+                self.cur(),
+                Rule::Syntax,
+            );
+            err.doc_url = Some("https://www.sqlite.org/lang_expr.html#the_exists_operator");
+            self.emit(err);
+            self.advance();
+            return None;
+        }
+        let query = Box::new(self.select()?);
+        self.consume(Type::BraceRight);
+        Some(Self::compound_expr(
+            t.clone(),
+            Box::new(nodes::ExistsExpr { t, negated, query }),
+        ))
+    }
+
+    /// parses x IS [NOT] [DISTINCT FROM] y and the postfix null checks x ISNULL, x NOTNULL and x
+    /// NOT NULL, the postfix forms are desugared to x IS [NOT] NULL
+    #[cfg_attr(feature = "trace", trace)]
+    fn is_expr(&mut self, left: nodes::Expr) -> Option<nodes::Expr> {
+        let t = self.cur().clone();
+        // skip IS, ISNULL, NOTNULL or NOT
+        self.advance();
+
+        let null = |t: &Token| nodes::Expr {
+            t: t.clone(),
+            literal: Some(Token {
+                ttype: Type::Keyword(Keyword::NULL),
+                ..t.clone()
+            }),
+            bind: None,
+            schema: None,
+            table: None,
+            column: None,
+            compound: None,
+        };
+
+        let mut distinct = false;
+        let (negated, right) = match t.ttype {
+            Type::Keyword(Keyword::IS) => {
+                let negated = if self.is_keyword(Keyword::NOT) {
+                    self.advance();
+                    true
+                } else {
+                    false
+                };
+                // IS [NOT] DISTINCT FROM, sqlite 3.39+, see [crate::parser::analyse::is_expr]
+                if self.is_keyword(Keyword::DISTINCT) {
+                    self.advance();
+                    if !self.is_keyword(Keyword::FROM) {
+                        let mut err = self.err(
+                            "Unexpected Token",
+                            &format!(
+                                "IS {}DISTINCT has to be followed by FROM, got {:?}",
+                                if negated { "NOT " } else { "" },
+                                self.cur().ttype
+                            ),
+                            self.cur(),
+                            Rule::Syntax,
+                        );
+                        err.doc_url = Some(
+                            "https://www.sqlite.org/lang_expr.html#the_is_and_is_not_operators",
+                        );
+                        self.emit(err);
+                        return None;
+                    }
+                    self.advance();
+                    distinct = true;
+                }
+                (negated, self.expr_with_precedence(4)?)
+            }
+            Type::Keyword(Keyword::ISNULL) => (false, null(&t)),
+            Type::Keyword(Keyword::NOTNULL) => (true, null(&t)),
+            // NOT NULL
             _ => {
-                let mut err = self.err("Unexpected Token", &format!("Wanted a literal (any of number,string,blob,null,true,false,CURRENT_TIME,CURRENT_DATE,CURRENT_DATE), got {:?}", cur.ttype),cur, Rule::Syntax);
-                err.doc_url = Some("https://www.sqlite.org/syntax/literal-value.html");
-                self.errors.push(err);
+                let null_token = self.cur().clone();
+                // skip NULL
+                self.advance();
+                (true, null(&null_token))
+            }
+        };
+
+        Some(Self::compound_expr(
+            t.clone(),
+            Box::new(nodes::IsExpr {
+                t,
+                negated,
+                distinct,
+                left: Box::new(left),
+                right: Box::new(right),
+            }),
+        ))
+    }
+
+    /// parses the right hand side of BETWEEN, IN, LIKE, GLOB, MATCH and REGEXP, the current token
+    /// is the operator keyword, NOT was already skipped and is passed via negated
+    #[cfg_attr(feature = "trace", trace)]
+    fn keyword_operator_expr(
+        &mut self,
+        t: Token,
+        operand: nodes::Expr,
+        negated: bool,
+    ) -> Option<nodes::Expr> {
+        let op = self.cur().clone();
+        let keyword = match op.ttype {
+            Type::Keyword(keyword) => keyword,
+            _ => unreachable!("keyword_operator_expr is only called with a keyword"),
+        };
+        // skip operator keyword
+        self.advance();
+
+        let compound: Box<dyn nodes::Node> = match keyword {
+            // expr [NOT] BETWEEN expr AND expr, both bounds bind tighter than AND
+            Keyword::BETWEEN => {
+                let low = self.expr_with_precedence(4)?;
+                self.consume_keyword(Keyword::AND);
+                let high = self.expr_with_precedence(4)?;
+                Box::new(nodes::BetweenExpr {
+                    t: t.clone(),
+                    negated,
+                    operand: Box::new(operand),
+                    low: Box::new(low),
+                    high: Box::new(high),
+                })
+            }
+            // expr [NOT] IN (expr-list | select-stmt) | [schema-name.]table-name
+            Keyword::IN => {
+                let body = if self.is(Type::BraceLeft) {
+                    // skip (
+                    self.advance();
+                    match self.cur().ttype {
+                        Type::Keyword(Keyword::SELECT) => {
+                            let select = self.select()?;
+                            self.consume(Type::BraceRight);
+                            nodes::InBody::Subquery(Box::new(select))
+                        }
+                        Type::Keyword(Keyword::WITH) => {
+                            let with = self.cur().clone();
+                            let (recursive, ctes) = self.with_clause()?;
+                            let select = self.select()?;
+                            self.consume(Type::BraceRight);
+                            nodes::InBody::Subquery(Box::new(nodes::WithClause {
+                                t: with,
+                                recursive,
+                                ctes,
+                                child: Box::new(select),
+                            }))
+                        }
+                        // sqlite accepts an empty list: x IN ()
+                        Type::BraceRight => {
+                            self.advance();
+                            nodes::InBody::List(vec![])
+                        }
+                        _ => {
+                            let mut list = vec![self.expr()?];
+                            while self.is(Type::Comma) {
+                                self.advance();
+                                list.push(self.expr()?);
+                            }
+                            self.consume(Type::BraceRight);
+                            nodes::InBody::List(list)
+                        }
+                    }
+                } else {
+                    nodes::InBody::Table(self.schema_table_container(None)?)
+                };
+                Box::new(nodes::InExpr {
+                    t: t.clone(),
+                    negated,
+                    operand: Box::new(operand),
+                    body,
+                })
+            }
+            // expr [NOT] LIKE expr [ESCAPE expr] | expr [NOT] GLOB|MATCH|REGEXP expr
+            _ => {
+                if keyword == Keyword::REGEXP {
+                    let mut err = self.err(
+                        "REGEXP is not implemented by sqlite",
+                        "sqlite has no built-in regexp() function, REGEXP raises an error unless an application defined regexp() function is registered, for instance via the regexp extension",
+                        &op,
+                        Rule::SqliteUnsupported,
+                    );
+                    err.doc_url = Some(
+                        "https://www.sqlite.org/lang_expr.html#the_like_glob_regexp_match_and_extract_operators",
+                    );
+                    self.emit(err);
+                }
+
+                let pattern = self.expr_with_precedence(4)?;
+                let escape = if self.is_keyword(Keyword::ESCAPE) {
+                    if keyword != Keyword::LIKE {
+                        let mut err = self.err(
+                            "Unexpected ESCAPE",
+                            &format!(
+                                "ESCAPE is only allowed for LIKE, not for {}",
+                                <&str>::from(keyword)
+                            ),
+                            self.cur(),
+                            Rule::Syntax,
+                        );
+                        err.doc_url = Some(
+                            "https://www.sqlite.org/lang_expr.html#the_like_glob_regexp_match_and_extract_operators",
+                        );
+                        self.emit(err);
+                    }
+                    self.advance();
+                    Some(Box::new(self.expr_with_precedence(6)?))
+                } else {
+                    None
+                };
+                Box::new(nodes::LikeExpr {
+                    t: t.clone(),
+                    negated,
+                    op: keyword,
+                    operand: Box::new(operand),
+                    pattern: Box::new(pattern),
+                    escape,
+                })
+            }
+        };
+
+        Some(Self::compound_expr(t, compound))
+    }
+
+    /// parses the operands of an expression, such as literals, bind parameters and column names
+    #[cfg_attr(feature = "trace", trace)]
+    fn primary_expr(&mut self) -> Option<nodes::Expr> {
+        let mut e = nodes::Expr {
+            t: self.cur().clone(),
+            literal: None,
+            bind: None,
+            schema: None,
+            table: None,
+            column: None,
+            compound: None,
+        };
+        match self.cur().ttype {
+            // literal value
+            Type::String(_)
+            | Type::Integer(_)
+            | Type::Float(_)
+            | Type::Blob(_)
+            | Type::Keyword(Keyword::NULL)
+            | Type::Boolean(_)
+            | Type::Keyword(Keyword::CURRENT_TIME)
+            | Type::Keyword(Keyword::CURRENT_DATE)
+            | Type::Keyword(Keyword::CURRENT_TIMESTAMP) => {
+                e.literal = self.literal_value().map(|e| e.token().clone())
+            }
+            // bind parameter with optional counter: ?[NNN]
+            Type::Param(counter) => {
+                // sqlite documentation says: But because it is easy to miscount the question marks, the
+                // use of this parameter format is discouraged. Programmers are encouraged to use
+                // one of the symbolic formats [...] or the ?NNN format [...] instead.
+                e.bind = Some(BindParameter {
+                    t: self.cur().clone(),
+                    counter: (counter != Type::PARAM_AUTO).then_some(counter),
+                    name: None,
+                });
+                self.advance();
+            }
+            // bind parameter with required ident: [:@$]<ident>
+            Type::ParamName(ref name) => {
+                e.bind = Some(BindParameter {
+                    t: self.cur().clone(),
+                    counter: None,
+                    name: Some(name.clone()),
+                });
+                self.advance();
+            }
+            // the lexer only produces a standalone prefix if no identifier follows it
+            Type::Colon | Type::At | Type::Dollar => {
+                let t = self.cur().clone();
+                self.push_err(
+                    "Invalid bind parameter",
+                    &format!(
+                        "Bind parameter with {:?} requires an identifier as a postfix",
+                        t.ttype
+                    ),
+                    &t,
+                    Rule::Syntax,
+                );
+                // skip invalid token
+                self.advance();
+                return None;
+            }
+            Type::Keyword(Keyword::CASE) => return self.case_expr(),
+            // [NOT] EXISTS (select-stmt)
+            Type::Keyword(Keyword::EXISTS) => return self.exists_expr(false),
+            Type::Keyword(Keyword::NOT)
+                if self
+                    .tokens
+                    .get(self.pos + 1)
+                    .is_some_and(|t| t.ttype == Type::Keyword(Keyword::EXISTS)) =>
+            {
+                return self.exists_expr(true);
+            }
+            // (select-stmt) scalar subquery and (expr) grouping, the grouping parenthesis are
+            // only relevant for precedence and thus not represented in the ast
+            Type::BraceLeft => {
+                let t = self.cur().clone();
+                // skip Type::BraceLeft
+                self.advance();
+                if self.is_keyword(Keyword::SELECT) {
+                    let query = Box::new(self.select()?);
+                    self.consume(Type::BraceRight);
+                    return Some(Self::compound_expr(
+                        t.clone(),
+                        Box::new(nodes::SubqueryExpr { t, query }),
+                    ));
+                }
+                let expr = self.expr()?;
+                self.consume(Type::BraceRight);
+                return Some(expr);
+            }
+            // unary operators: NOT expr, -expr, +expr, ~expr; NOT binds looser than comparisons,
+            // the prefix operators tighter than all binary operators
+            Type::Keyword(Keyword::NOT) | Type::Minus | Type::Plus | Type::Tilde => {
+                let op = self.cur().clone();
+                // skip operator
+                self.advance();
+                let operand = self.expr_with_precedence(match op.ttype {
+                    Type::Keyword(Keyword::NOT) => 3,
+                    _ => 11,
+                })?;
+                return Some(Self::compound_expr(
+                    op.clone(),
+                    Box::new(nodes::UnaryExpr {
+                        t: op.clone(),
+                        op,
+                        operand: Box::new(operand),
+                    }),
+                ));
+            }
+            // CAST(expr AS type-name)
+            Type::Keyword(Keyword::CAST) if self.next_is(Type::BraceLeft) => {
+                let t = self.cur().clone();
+                // skip CAST and Type::BraceLeft
+                self.advance();
+                self.advance();
+                let expr = Box::new(self.expr()?);
+                self.consume_keyword(Keyword::AS);
+                let type_name = self.type_name()?;
+                self.consume(Type::BraceRight);
+                return Some(Self::compound_expr(
+                    t.clone(),
+                    Box::new(nodes::CastExpr { t, expr, type_name }),
+                ));
+            }
+            Type::Ident(_) => {
+                // this is the start of a function, either function(...) or schema.function(...)
+                if self.next_is(Type::BraceLeft)
+                    || (self.next_is(Type::Dot)
+                        && matches!(
+                            self.tokens.get(self.pos + 2).map(|t| &t.ttype),
+                            Some(Type::Ident(_))
+                        )
+                        && self
+                            .tokens
+                            .get(self.pos + 3)
+                            .is_some_and(|t| t.ttype == Type::BraceLeft))
+                {
+                    return self.function_call();
+                }
+
+                // [schema-name.][table-name.]<column-name>, the first two levels are parsed as
+                // either schema.table or table and shifted into table.column or column if no
+                // further level follows
+                match self.schema_table_container(None)? {
+                    SchemaTableContainer::Table(column) => e.column = Some(column),
+                    SchemaTableContainer::SchemaAndTable { schema, table } => {
+                        if self.is(Type::Dot) {
+                            // skip Type::Dot
+                            self.advance();
+                            e.schema = Some(schema);
+                            e.table = Some(table);
+                            e.column = Some(self.column_name()?);
+                        } else {
+                            e.table = Some(schema);
+                            e.column = Some(table);
+                        }
+                    }
+                }
+            }
+            _ => {
+                let cur = self.cur().clone();
+                self.push_err(
+                    "Invalid construct",
+                    &format!(
+                        "At this point in an expression, {:?} is not a valid construct",
+                        cur.ttype
+                    ),
+                    &cur,
+                    Rule::Syntax,
+                );
+                self.advance();
+                return None;
+            }
+        }
+        Some(e)
+    }
+
+    /// function-name(function-arguments) [filter-clause] [over-clause], see:
+    /// https://www.sqlite.org/syntax/expr.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn function_call(&mut self) -> Option<nodes::Expr> {
+        let t = self.cur().clone();
+        let mut schema = None;
+        if self.next_is(Type::Dot) {
+            schema =
+                Some(self.consume_ident("https://www.sqlite.org/syntax/expr.html", "schema_name")?);
+            // skip Type::Dot
+            self.advance();
+        }
+        let name_t = self.cur().clone();
+        let name =
+            self.consume_ident("https://www.sqlite.org/syntax/expr.html", "function_name")?;
+        self.consume(Type::BraceLeft);
+
+        if let Some(schema) = &schema
+            && functions::lookup(&name).is_some()
+        {
+            let mut err = self.err(
+                "Schema qualified built-in function",
+                &format!(
+                    "{name} is a built-in function, built-in functions do not belong to a schema, thus the `{schema}.` prefix is ignored"
+                ),
+                &name_t,
+                Rule::Quirk,
+            );
+            err.start = t.start;
+            err.doc_url = Some("https://www.sqlite.org/lang_corefunc.html");
+            self.emit(err);
+        }
+
+        let mut f = nodes::FunctionCall {
+            t: t.clone(),
+            schema,
+            name,
+            distinct: false,
+            args: vec![],
+            star: false,
+            filter: None,
+            over: None,
+        };
+
+        // https://www.sqlite.org/syntax/function-arguments.html, sqlite also accepts ALL, the
+        // default, in place of DISTINCT
+        let modifier = match self.cur().ttype {
+            Type::Keyword(keyword @ (Keyword::DISTINCT | Keyword::ALL)) => {
+                f.distinct = keyword == Keyword::DISTINCT;
+                let t = self.cur().clone();
+                self.advance();
+                Some((keyword, t))
+            }
+            _ => None,
+        };
+
+        if let Some((keyword, modifier)) = &modifier
+            && (self.is(Type::Asterisk) || self.is(Type::BraceRight))
+        {
+            let as_str: &str = (*keyword).into();
+            let mut err = self.err(
+                format!("{as_str} without arguments"),
+                &format!(
+                    "{as_str} requires at least one argument expression, it can not be combined with * or an empty argument list"
+                ),
+                modifier,
+                Rule::Syntax,
+            );
+            err.doc_url = Some("https://www.sqlite.org/syntax/function-arguments.html");
+            self.emit(err);
+        }
+
+        if self.is(Type::Asterisk) {
+            self.advance();
+            f.star = true;
+        } else if !self.is(Type::BraceRight) {
+            f.args.push(self.expr()?);
+            while self.is(Type::Comma) {
+                self.advance();
+                f.args.push(self.expr()?);
+            }
+        }
+        self.consume(Type::BraceRight);
+
+        // https://www.sqlite.org/syntax/filter-clause.html
+        if self.is_keyword(Keyword::FILTER) {
+            self.advance();
+            self.consume(Type::BraceLeft);
+            self.consume_keyword(Keyword::WHERE);
+            f.filter = Some(Box::new(self.expr()?));
+            self.consume(Type::BraceRight);
+        }
+
+        // https://www.sqlite.org/syntax/over-clause.html
+        if self.is_keyword(Keyword::OVER) {
+            self.advance();
+            f.over = Some(Box::new(if self.is(Type::BraceLeft) {
+                self.advance();
+                let window = self.window_defn()?;
+                self.consume(Type::BraceRight);
+                window
+            } else {
+                let t = self.cur().clone();
+                nodes::WindowDefn {
+                    t,
+                    name: Some(self.consume_ident(
+                        "https://www.sqlite.org/syntax/over-clause.html",
+                        "window_name",
+                    )?),
+                    partition_by: vec![],
+                    order_by: vec![],
+                    frame: None,
+                }
+            }));
+        }
+
+        Some(Self::compound_expr(t, Box::new(f)))
+    }
+
+    /// the contents of a window definition, without its parenthesis, see:
+    /// https://www.sqlite.org/syntax/window-defn.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn window_defn(&mut self) -> Option<nodes::WindowDefn> {
+        let mut w = nodes::WindowDefn {
+            t: self.cur().clone(),
+            name: None,
+            partition_by: vec![],
+            order_by: vec![],
+            frame: None,
+        };
+
+        if let Type::Ident(name) = &self.cur().ttype {
+            w.name = Some(name.clone());
+            self.advance();
+        }
+
+        if self.is_keyword(Keyword::PARTITION) {
+            self.advance();
+            self.consume_keyword(Keyword::BY);
+            w.partition_by.push(self.expr()?);
+            while self.is(Type::Comma) {
                 self.advance();
-                None
+                w.partition_by.push(self.expr()?);
             }
         }
+
+        if self.is_keyword(Keyword::ORDER) {
+            w.order_by = self.order_by_clause()?;
+        }
+
+        if let Type::Keyword(Keyword::RANGE | Keyword::ROWS | Keyword::GROUPS) = self.cur().ttype {
+            w.frame = Some(self.frame_spec()?);
+        }
+
+        Some(w)
     }
 
-    /// parses an sql expression: https://www.sqlite.org/syntax/expr.html
-    fn expr(&mut self) -> Option<nodes::Expr> {
-        let mut e = nodes::Expr {
-            t: self.cur().clone(),
-            literal: None,
-            bind: None,
-            schema: None,
-            table: None,
-            column: None,
+    /// https://www.sqlite.org/syntax/ordering-term.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn ordering_term(&mut self) -> Option<nodes::OrderingTerm> {
+        let t = self.cur().clone();
+        let expr = self.expr()?;
+
+        let collate = if self.is_keyword(Keyword::COLLATE) {
+            self.advance();
+            Some(self.consume_ident(
+                "https://www.sqlite.org/syntax/ordering-term.html",
+                "collation_name",
+            )?)
+        } else {
+            None
         };
-        match self.cur().ttype {
-            // literal value
-            Type::String(_)
-            | Type::Number(_)
-            | Type::Blob(_)
-            | Type::Keyword(Keyword::NULL)
-            | Type::Boolean(_)
-            | Type::Keyword(Keyword::CURRENT_TIME)
-            | Type::Keyword(Keyword::CURRENT_DATE)
-            | Type::Keyword(Keyword::CURRENT_TIMESTAMP) => {
-                e.literal = self.literal_value().map(|e| e.token().clone())
-            }
-            // bind parameter with optional ident: ?[ident]
-            Type::Question => {
-                // sqlite documentation says: But because it is easy to miscount the question marks, the
-                // use of this parameter format is discouraged. Programmers are encouraged to use
-                // one of the symbolic formats [...] or the ?NNN format [...] instead.
-                let mut param = BindParameter {
-                    t: self.cur().clone(),
 
-                    counter: None,
-                    name: None,
-                };
+        let order = match self.cur().ttype {
+            Type::Keyword(keyword @ (Keyword::ASC | Keyword::DESC)) => {
                 self.advance();
+                Some(keyword)
+            }
+            _ => None,
+        };
 
-                // question mark can have a number after them, but they are optional
-                if let Token {
-                    ttype: Type::Number(_),
-                    ..
-                } = self.cur()
-                {
-                    param.counter = self.literal_value();
+        let nulls = if self.is_keyword(Keyword::NULLS) {
+            self.advance();
+            match self.cur().ttype {
+                Type::Keyword(keyword @ (Keyword::FIRST | Keyword::LAST)) => {
+                    self.advance();
+                    Some(keyword)
+                }
+                _ => {
+                    let mut err = self.err(
+                        "Unexpected Token",
+                        &format!(
+                            "NULLS requires either FIRST or LAST at this point, got {:?}",
+                            self.cur().ttype
+                        ),
+                        self.cur(),
+                        Rule::Syntax,
+                    );
+                    err.doc_url = Some("https://www.sqlite.org/syntax/ordering-term.html");
+                    self.emit(err);
+                    self.advance();
+                    return None;
                 }
-                e.bind = Some(param)
             }
-            // bind parameter with required ident: [:@$]<ident>
-            Type::Colon | Type::At | Type::Dollar => {
-                let mut bind = BindParameter {
-                    t: self.cur().clone(),
-                    counter: None,
-                    name: None,
-                };
-                self.advance();
+        } else {
+            None
+        };
 
-                // all bind params need an identifier, because they need to be named
-                if let Token {
-                    ttype: Type::Ident(ident),
-                    ..
-                } = self.cur()
-                {
-                    bind.name = Some(ident.clone());
+        Some(nodes::OrderingTerm {
+            t,
+            expr,
+            ordinal: None,
+            collate,
+            order,
+            nulls,
+        })
+    }
+
+    /// https://www.sqlite.org/syntax/frame-spec.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn frame_spec(&mut self) -> Option<nodes::FrameSpec> {
+        let t = self.cur().clone();
+        let unit = match t.ttype {
+            Type::Keyword(keyword) => keyword,
+            _ => unreachable!("frame_spec is only called with RANGE, ROWS or GROUPS"),
+        };
+        // skip RANGE, ROWS or GROUPS
+        self.advance();
+
+        let (start, end) = if self.is_keyword(Keyword::BETWEEN) {
+            self.advance();
+            let start = self.frame_bound()?;
+            self.consume_keyword(Keyword::AND);
+            (start, Some(self.frame_bound()?))
+        } else {
+            (self.frame_bound()?, None)
+        };
+
+        let exclude = if self.is_keyword(Keyword::EXCLUDE) {
+            self.advance();
+            let keyword = match self.cur().ttype {
+                Type::Keyword(Keyword::NO) => {
                     self.advance();
-                } else {
-                    self.push_err(
-                        "Invalid bind parameter",
+                    self.consume_keyword(Keyword::OTHERS);
+                    Keyword::NO
+                }
+                Type::Keyword(Keyword::CURRENT) => {
+                    self.advance();
+                    self.consume_keyword(Keyword::ROW);
+                    Keyword::CURRENT
+                }
+                Type::Keyword(keyword @ (Keyword::GROUP | Keyword::TIES)) => {
+                    self.advance();
+                    keyword
+                }
+                _ => {
+                    let mut err = self.err(
+                        "Unexpected Token",
                         &format!(
-                            "Bind parameter with {:?} requires an identifier as a postfix",
-                            bind.t.ttype
+                            "EXCLUDE requires either NO OTHERS, CURRENT ROW, GROUP or TIES at this point, got {:?}",
+                            self.cur().ttype
                         ),
-                        &bind.t,
+                        self.cur(),
                         Rule::Syntax,
                     );
-                    // skip invalid token
+                    err.doc_url = Some("https://www.sqlite.org/syntax/frame-spec.html");
+                    self.emit(err);
                     self.advance();
                     return None;
                 }
-                e.bind = Some(bind);
+            };
+            Some(keyword)
+        } else {
+            None
+        };
+
+        Some(nodes::FrameSpec {
+            t,
+            unit,
+            start,
+            end,
+            exclude,
+        })
+    }
+
+    /// a single boundary of a frame-spec: UNBOUNDED PRECEDING, expr PRECEDING, CURRENT ROW, expr
+    /// FOLLOWING or UNBOUNDED FOLLOWING
+    #[cfg_attr(feature = "trace", trace)]
+    fn frame_bound(&mut self) -> Option<nodes::FrameBound> {
+        match self.cur().ttype {
+            Type::Keyword(Keyword::UNBOUNDED) => {
+                self.advance();
+                if self.is_keyword(Keyword::FOLLOWING) {
+                    self.advance();
+                    Some(nodes::FrameBound::UnboundedFollowing)
+                } else {
+                    self.consume_keyword(Keyword::PRECEDING);
+                    Some(nodes::FrameBound::UnboundedPreceding)
+                }
             }
-            Type::Ident(_) => {
-                // this is the start of a function
-                if self.next_is(Type::BraceLeft) {
-                    todo!("function-name(function-arguments) [filter-clause] [over-clause]")
+            Type::Keyword(Keyword::CURRENT) => {
+                self.advance();
+                self.consume_keyword(Keyword::ROW);
+                Some(nodes::FrameBound::CurrentRow)
+            }
+            _ => {
+                let expr = self.expr()?;
+                match self.cur().ttype {
+                    Type::Keyword(Keyword::PRECEDING) => {
+                        self.advance();
+                        Some(nodes::FrameBound::Preceding(expr))
+                    }
+                    Type::Keyword(Keyword::FOLLOWING) => {
+                        self.advance();
+                        Some(nodes::FrameBound::Following(expr))
+                    }
+                    _ => {
+                        let mut err = self.err(
+                            "Unexpected Token",
+                            &format!(
+                                "Frame boundary requires either PRECEDING or FOLLOWING at this point, got {:?}",
+                                self.cur().ttype
+                            ),
+                            self.cur(),
+                            Rule::Syntax,
+                        );
+                        err.doc_url = Some("https://www.sqlite.org/syntax/frame-spec.html");
+                        self.emit(err);
+                        self.advance();
+                        None
+                    }
                 }
+            }
+        }
+    }
 
-                // this sets either the schema, the table or the column
-                todo!("[schema-name.][table-name.]<column-name>");
+    /// parses the column name of schema_name.table_name.column_name
+    fn column_name(&mut self) -> Option<String> {
+        let cur = self.cur().clone();
+        match &cur.ttype {
+            Type::Ident(column) => {
+                self.advance();
+                Some(column.clone())
+            }
+            Type::Keyword(keyword) => {
+                let as_str: &str = (*keyword).into();
+                let mut err = self.err(
+                    "Malformed column name",
+                    &format!(
+                        "`{as_str}` is a keyword, if you want to use it as a column name, quote it: '{as_str}'"
+                    ),
+                    &cur,
+                    Rule::Syntax,
+                );
+                err.doc_url = Some("https://www.sqlite.org/lang_keywords.html");
+                self.emit(err);
+                self.advance();
+                None
             }
             _ => {
-                let cur = self.cur().clone();
                 self.push_err(
-                    "Invalid construct",
+                    "Malformed column name",
                     &format!(
-                        "At this point in an expression, {:?} is not a valid construct",
+                        "expected a column name after <schema_name>.<table_name>. - got {:?}",
                         cur.ttype
                     ),
                     &cur,
                     Rule::Syntax,
                 );
                 self.advance();
-                return None;
+                None
             }
         }
-        Some(e)
     }
 
     /// parses schema_name.table_name and table_name
@@ -1177,19 +4165,19 @@ impl<'a> Parser<'a> {
                 } else {
                     // we got schema_name. but not Ident|String following? this is a syntax error
                     let cur = self.cur().clone();
-                    self.errors.push(match cur.ttype {
+                    self.emit(match cur.ttype {
                         Type::Keyword(keyword) => {
-                let target_name = target_name.unwrap_or_else(|| "table");
+                let target_name = target_name.unwrap_or("table");
                             let as_str: &str = keyword.into();
                             self.err(
-                            &format!("Malformed {target_name} name"),
+                            format!("Malformed {target_name} name"),
                             &format!("`{as_str}` is a keyword, if you want to use it as a {target_name} or column name, quote it: '{as_str}'"),
                             &cur, Rule::Syntax)
                         }
                         _ => {
-                let target_name = target_name.unwrap_or_else(|| "table");
+                let target_name = target_name.unwrap_or("table");
                             self.err(
-                                                    &format!("Malformed {target_name} name"),
+                                                    format!("Malformed {target_name} name"),
                                                     &format!(
                                                         "expected a {target_name} name after <schema_name>. - got {:?}",
                                                         cur.ttype
@@ -1213,9 +4201,9 @@ impl<'a> Parser<'a> {
             }
             _ => {
                 let cur = self.cur().clone();
-                let target_name = target_name.unwrap_or_else(|| "table");
+                let target_name = target_name.unwrap_or("table");
                 self.push_err(
-                    &format!("Malformed {} name", target_name),
+                    format!("Malformed {} name", target_name),
                     &format!(
                         "expected either schema_name.{} or {}, got {:?}",
                         target_name, target_name, cur.ttype
@@ -1257,7 +4245,7 @@ impl<'a> Parser<'a> {
                             Rule::Syntax,
                         );
                         err.doc_url = Some("https://www.sqlite.org/syntax/conflict-clause.html");
-                        self.errors.push(err);
+                        self.emit(err);
                     }
                 }
             } else {
@@ -1271,7 +4259,7 @@ impl<'a> Parser<'a> {
                     Rule::Syntax,
                 );
                 err.doc_url = Some("https://www.sqlite.org/syntax/conflict-clause.html");
-                self.errors.push(err);
+                self.emit(err);
             }
             self.advance();
         }
@@ -1298,7 +4286,7 @@ impl<'a> Parser<'a> {
                         Rule::Syntax,
                     );
                     err.doc_url = Some("https://www.sqlite.org/syntax/foreign-key-clause.html");
-                    self.errors.push(err);
+                    self.emit(err);
                 }
             };
 
@@ -1340,7 +4328,7 @@ impl<'a> Parser<'a> {
                         Rule::Syntax,
                     );
                     err.doc_url = Some("https://www.sqlite.org/syntax/foreign-key-clause.html");
-                    self.errors.push(err);
+                    self.emit(err);
                     self.advance();
                     None
                 }
@@ -1359,7 +4347,24 @@ impl<'a> Parser<'a> {
                 Type::Keyword(Keyword::FULL) => Some(ForeignKeyMatch::Full),
                 Type::Keyword(Keyword::PARTIAL) => Some(ForeignKeyMatch::Partial),
                 Type::Keyword(Keyword::SIMPLE) => Some(ForeignKeyMatch::Simple),
-                _ => todo!("error handling MATCH <kind>"),
+                _ => {
+                    let mut err = self.err(
+                        "Unexpected Token",
+                        &format!(
+                            "Wanted FULL, PARTIAL or SIMPLE after MATCH, got {:?}.",
+                            self.cur().ttype
+                        ),
+                        self.cur(),
+                        Rule::Syntax,
+                    );
+                    err.doc_url = Some("https://www.sqlite.org/syntax/foreign-key-clause.html");
+                    self.emit(err);
+                    // skip the unexpected token, the statement terminator is left for the caller
+                    if !self.is(Type::Semicolon) && !self.is_eof() {
+                        self.advance();
+                    }
+                    return None;
+                }
             };
             self.advance();
             self.foreign_key_clause_on_and_match(fk)
@@ -1431,7 +4436,7 @@ impl<'a> Parser<'a> {
                         Rule::Syntax,
                     );
                         err.doc_url = Some("https://www.sqlite.org/syntax/foreign-key-clause.html");
-                        self.errors.push(err);
+                        self.emit(err);
                     }
                 };
 
@@ -1446,77 +4451,115 @@ impl<'a> Parser<'a> {
         Some(fk)
     }
 
-    /// https://www.sqlite.org/syntax/column-def.html
+    /// parses a type name and resolves it to its storage class, emits a [Rule::Quirk] if the type
+    /// name is not a sqlite type, see: https://www.sqlite.org/syntax/type-name.html
     #[cfg_attr(feature = "trace", trace)]
-    fn column_def(&mut self) -> Option<nodes::ColumnDef> {
-        let mut def = nodes::ColumnDef {
-            t: self.cur().clone(),
-            name: String::new(),
-            type_name: None,
-            constraints: vec![],
+    fn type_name(&mut self) -> Option<SqliteStorageClass> {
+        let Type::Ident(name) = self.cur().ttype.clone() else {
+            let mut err = self.err(
+                "Missing type name",
+                &format!("Wanted a type name, got {:?}", self.cur().ttype),
+                self.cur(),
+                Rule::Syntax,
+            );
+            err.doc_url = Some("https://www.sqlite.org/syntax/type-name.html");
+            self.emit(err);
+            self.advance();
+            return None;
         };
+        let is_foreign = self.foreign_dialect(Position::Type);
+        let start = self.cur().clone();
+        // type names may consist of multiple words, such as DOUBLE PRECISION, idents following
+        // them are column constraints of other dialects, such as AUTO_INCREMENT
+        let mut name = name;
+        while let Some(Token {
+            ttype: Type::Ident(word),
+            ..
+        }) = self.tokens.get(self.pos + 1)
+        {
+            if dialect::lookup(word, Position::ColumnConstraint).is_some() {
+                break;
+            }
+            name = format!("{name} {word}");
+            self.advance();
+        }
+        // sqlite matches type names case insensitive
+        let upper = name.to_uppercase();
+        let class = SqliteStorageClass::from_str(&upper);
+
+        if !is_foreign && SqliteStorageClass::from_str_strict(&upper).is_none() {
+            let mut e = self.err(
+                format!("Type `{name}` is not a sqlite type and thus will be of type INTEGER"),
+                "Consider using a known sqlite type: TEXT, BLOB, REAL or INTEGER",
+                &start,
+                Rule::Quirk,
+            );
+            e.doc_url = Some("https://www.sqlite.org/datatype3.html");
+            self.emit(e);
+        }
 
-        def.name = self.consume_ident("https://www.sqlite.org/syntax/column-def.html", "name")?;
-
-        // we got a type_name: https://www.sqlite.org/syntax/type-name.html
-        if let Type::Ident(name) = &self.cur().ttype {
-            def.type_name = Some(SqliteStorageClass::from_str(name));
+        // skip type name
+        self.advance();
 
-            if SqliteStorageClass::from_str_strict(name.as_str()).is_none() {
-                let mut e = self.err(
-                    format!("Type `{name}` is not a sqlite type and thus will be of type INTEGER"),
-                    "Consider using a known sqlite type: TEXT, BLOB, REAL or INTEGER",
+        if self.is(Type::BraceLeft) {
+            // skip Type::BraceLeft
+            self.advance();
+            if let Type::Integer(_) | Type::Float(_) = self.cur().ttype {
+                self.advance();
+            } else {
+                let mut err = self.err(
+                    "Unexpected Token",
+                    &format!(
+                        "Wanted a Number after Type::BraceLeft, got {:?}.",
+                        self.cur().ttype
+                    ),
                     self.cur(),
-                    Rule::Quirk,
+                    Rule::Syntax,
                 );
-                e.doc_url = Some("https://www.sqlite.org/datatype3.html");
-                self.errors.push(e);
+                err.doc_url = Some("https://www.sqlite.org/syntax/type-name.html");
+                self.emit(err);
+                self.advance();
             }
 
-            // skip type name
-            self.advance();
-
-            if self.is(Type::BraceLeft) {
-                // skip Type::BraceLeft
+            if self.is(Type::Comma) {
                 self.advance();
-                if let Type::Number(_) = self.cur().ttype {
+                if let Type::Integer(_) | Type::Float(_) = self.cur().ttype {
                     self.advance();
                 } else {
                     let mut err = self.err(
                         "Unexpected Token",
                         &format!(
-                            "Wanted a Number after Type::BraceLeft, got {:?}.",
+                            "Wanted a Number after Type::BraceLeft, Type::Number and Type::Comma, got {:?}.",
                             self.cur().ttype
                         ),
                         self.cur(),
                         Rule::Syntax,
                     );
                     err.doc_url = Some("https://www.sqlite.org/syntax/type-name.html");
-                    self.errors.push(err);
+                    self.emit(err);
                     self.advance();
                 }
-
-                if self.is(Type::Comma) {
-                    self.advance();
-                    if let Type::Number(_) = self.cur().ttype {
-                        self.advance();
-                    } else {
-                        let mut err = self.err(
-                            "Unexpected Token",
-                            &format!(
-                                "Wanted a Number after Type::BraceLeft, Type::Number and Type::Comma, got {:?}.",
-                                self.cur().ttype
-                            ),
-                            self.cur(),
-                            Rule::Syntax,
-                        );
-                        err.doc_url = Some("https://www.sqlite.org/syntax/type-name.html");
-                        self.errors.push(err);
-                        self.advance();
-                    }
-                }
-                self.consume(Type::BraceRight);
             }
+            self.consume(Type::BraceRight);
+        }
+
+        Some(class)
+    }
+
+    /// https://www.sqlite.org/syntax/column-def.html
+    #[cfg_attr(feature = "trace", trace)]
+    fn column_def(&mut self) -> Option<nodes::ColumnDef> {
+        let mut def = nodes::ColumnDef {
+            t: self.cur().clone(),
+            name: String::new(),
+            type_name: None,
+            constraints: vec![],
+        };
+
+        def.name = self.consume_ident("https://www.sqlite.org/syntax/column-def.html", "name")?;
+
+        if let Type::Ident(_) = &self.cur().ttype {
+            def.type_name = self.type_name();
         } else {
             let tok = self
                 .tokens
@@ -1528,13 +4571,14 @@ impl<'a> Parser<'a> {
                 file: self.name.to_string(),
                 line: tok.line,
                 rule: Rule::Quirk,
+                severity: Rule::Quirk.severity(),
                 note: "SQLite allows columns without a declared type. Such columns use dynamic typing and type affinity is not enforced. Consider adding TEXT, BLOB, REAL, or INTEGER if this is unintended.".into(),
                 msg: "Possibly unintended flexible typed column".into(),
                 start: tok.start,
                 end: tok.end,
                 doc_url: Some("https://www.sqlite.org/quirks.html#the_datatype_is_optional"),
             };
-            self.errors.push(err);
+            self.emit(err);
         }
 
         // column_constraint: https://www.sqlite.org/syntax/column-constraint.html
@@ -1551,15 +4595,24 @@ impl<'a> Parser<'a> {
                     | Type::Keyword(Keyword::REFERENCES)
                     | Type::Keyword(Keyword::GENERATED)
                     | Type::Keyword(Keyword::AS)
+                    | Type::Ident(_)
             )
         {
+            if self.foreign_dialect(Position::ColumnConstraint) {
+                self.advance();
+                continue;
+            } else if let Type::Ident(_) = self.cur().ttype {
+                break;
+            }
+            let mut name = None;
             if self.is_keyword(Keyword::CONSTRAINT) {
                 self.advance();
-                self.consume_ident(
+                name = self.consume_ident(
                     "https://www.sqlite.org/syntax/column-constraint.html",
                     "name",
                 );
             }
+            let constraint_start = self.cur().clone();
 
             let constraint = if self.is_keyword(Keyword::PRIMARY) {
                 self.advance();
@@ -1607,12 +4660,30 @@ impl<'a> Parser<'a> {
                 self.advance();
                 if self.is(Type::BraceLeft) {
                     self.advance();
-                    let expr = self.expr();
+                    let expr = self.expr()?;
                     self.consume(Type::BraceRight);
-                    Some(ColumnConstraint::Default {
-                        literal: None,
-                        expr,
-                    })
+                    // DEFAULT (5) is equivalent to DEFAULT 5, both are represented as a literal
+                    // to keep their ast comparable
+                    match expr {
+                        nodes::Expr {
+                            literal: Some(literal),
+                            bind: None,
+                            schema: None,
+                            table: None,
+                            column: None,
+                            compound: None,
+                            ..
+                        } => Some(ColumnConstraint::Default {
+                            literal: Some(nodes::Literal { t: literal }),
+                            expr: None,
+                            parenthesized: true,
+                        }),
+                        expr => Some(ColumnConstraint::Default {
+                            literal: None,
+                            expr: Some(expr),
+                            parenthesized: true,
+                        }),
+                    }
                 } else {
                     // this aint so pretty, but sometimes i do need literals as Option<Box<dyn
                     // Box>> and sometimes as Option<Literal>, it is what it is, Nodes sadly dont
@@ -1623,6 +4694,7 @@ impl<'a> Parser<'a> {
                             t: n.token().clone(),
                         }),
                         expr: None,
+                        parenthesized: false,
                     })
                 }
             } else if self.is_keyword(Keyword::COLLATE) {
@@ -1669,11 +4741,25 @@ impl<'a> Parser<'a> {
                     })
                 }
             } else {
+                if let Some(name) = &name {
+                    let mut err = self.err(
+                        "Missing constraint",
+                        &format!(
+                            "CONSTRAINT {name} has to be followed by a column constraint, got {:?}",
+                            constraint_start.ttype
+                        ),
+                        &constraint_start,
+                        Rule::Syntax,
+                    );
+                    err.doc_url = Some("https://www.sqlite.org/syntax/column-constraint.html");
+                    self.emit(err);
+                }
                 None
             };
 
             if let Some(constraint) = constraint {
-                def.constraints.push(constraint);
+                def.constraints
+                    .push(nodes::NamedColumnConstraint { name, constraint });
             }
         }
 
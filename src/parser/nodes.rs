@@ -1,8 +1,19 @@
+use crate::error::Error;
+use crate::parser::analyse::FieldAnalyse;
 use crate::parser::debug::FieldSerializable;
-use crate::types::{Keyword, Token, storage::SqliteStorageClass};
+use crate::parser::visit::FieldChildren;
+use crate::types::{Keyword, Token, ctx::Context, storage::SqliteStorageClass};
 
 macro_rules! node {
     ($node_name:ident,$documentation:literal,$($field_name:ident:$field_type:ty),*) => {
+        node!(@node $node_name, $documentation, |_node, _ctx| vec![], $($field_name:$field_type),*);
+    };
+    // $analyse is called with the node and the context after all fields were analysed, used for
+    // analysis concerning the node as a whole
+    ($node_name:ident,$documentation:literal,$($field_name:ident:$field_type:ty),*; analyse = $analyse:expr) => {
+        node!(@node $node_name, $documentation, $analyse, $($field_name:$field_type),*);
+    };
+    (@node $node_name:ident,$documentation:literal,$analyse:expr,$($field_name:ident:$field_type:ty),*) => {
         #[derive(Debug)]
         #[doc = $documentation]
         pub struct $node_name {
@@ -45,10 +56,39 @@ macro_rules! node {
             fn doc(&self) -> &str {
                 $documentation
             }
+
+            #[allow(unused_mut, unused_variables)]
+            fn analyse(&self, ctx: &mut Context) -> Vec<Error> {
+                let mut errors = vec![];
+                $(
+                    errors.append(&mut self.$field_name.field_analyse(ctx));
+                )*
+                let analyse: fn(&Self, &mut Context) -> Vec<Error> = $analyse;
+                errors.append(&mut analyse(self, ctx));
+                errors
+            }
+
+            #[allow(unused_mut)]
+            fn children(&self) -> Vec<&dyn Node> {
+                let mut children = vec![];
+                $(
+                    self.$field_name.field_children(&mut children);
+                )*
+                children
+            }
+
+            fn as_node(&self) -> &dyn Node {
+                self
+            }
+
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
+            }
         }
 
         #[cfg(test)]
         impl $node_name {
+            #[allow(clippy::too_many_arguments, clippy::new_without_default)]
             pub fn new($($field_name: $field_type,)*) -> Self {
                 Self {
                     // Type::InstructionExpect is always used in tests
@@ -63,6 +103,18 @@ macro_rules! node {
                 self.as_serializable()
             }
         }
+
+        impl FieldAnalyse for $node_name {
+            fn field_analyse(&self, ctx: &mut Context) -> Vec<Error> {
+                self.analyse(ctx)
+            }
+        }
+
+        impl FieldChildren for $node_name {
+            fn field_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+                children.push(self);
+            }
+        }
     };
 }
 
@@ -75,9 +127,17 @@ pub trait Node: std::fmt::Debug {
     fn as_serializable(&self) -> serde_json::Value;
     /// returns the documentation url for sefl
     fn doc(&self) -> &str;
-
-    // TODO: every node should analyse its own contents after the ast was build, to do so the Node
-    // trait should enforce a analyse(&self, ctx &types::ctx::Context) -> Vec<Error> function.
+    /// analyses the node and its children after the ast was build, returns all found semantic
+    /// errors
+    fn analyse(&self, ctx: &mut Context) -> Vec<Error>;
+    /// returns the nodes directly contained in the node, in source order, see
+    /// [crate::parser::visit::walk] for visiting all nested nodes
+    fn children(&self) -> Vec<&dyn Node>;
+    /// upcasts self to a trait object
+    fn as_node(&self) -> &dyn Node;
+    /// allows downcasting self to the concrete node, for analysis inspecting the fields of
+    /// nested nodes
+    fn as_any(&self) -> &dyn std::any::Any;
 }
 
 node!(
@@ -108,7 +168,8 @@ These can take several forms:
   with the name $AAAA. Sqlite allows everything to follow after '$()', sqleibniz forbids this via
   Rule::Quirks errors
 ",
-    counter: Option<Box<dyn Node>>,
+    counter: Option<usize>,
+    // including the prefix, such as `:AAAA`
     name: Option<String>
 );
 
@@ -119,7 +180,370 @@ node!(
     bind: Option<BindParameter>,
     schema: Option<String>,
     table: Option<String>,
-    column: Option<String>
+    column: Option<String>,
+    // holds expressions made up of other expressions, such as BinaryExpr
+    compound: Option<Box<dyn Node>>
+);
+
+#[cfg(test)]
+impl Expr {
+    /// shorthand for an Expr only holding a literal value
+    pub fn literal(ttype: crate::types::Type) -> Self {
+        Self::new(Some(Token::new(ttype)), None, None, None, None, None)
+    }
+
+    /// shorthand for an Expr only holding a column reference: [schema.][table.]column
+    pub fn column(schema: Option<&str>, table: Option<&str>, column: &str) -> Self {
+        Self::new(
+            None,
+            None,
+            schema.map(String::from),
+            table.map(String::from),
+            Some(column.into()),
+            None,
+        )
+    }
+
+    /// shorthand for an Expr only holding a compound expression
+    pub fn compound(node: impl Node + 'static) -> Self {
+        Self::new(None, None, None, None, None, Some(Box::new(node)))
+    }
+
+    /// shorthand for an Expr holding a BinaryExpr of op, left and right
+    pub fn binary(op: crate::types::Type, left: Expr, right: Expr) -> Self {
+        Self::compound(BinaryExpr::new(
+            Token::new(op),
+            Box::new(left),
+            Box::new(right),
+        ))
+    }
+}
+
+node!(
+    BinaryExpr,
+    r"Binary expression, see: https://www.sqlite.org/lang_expr.html#operators_and_parse_affecting_attributes
+
+A binary operator combines its left and right hand side expression, binding strength is determined by sqlites operator precedence, from highest to lowest:
+
+```text
+||   ->   ->>
+*    /    %
++    -
+&    |    <<   >>
+<    <=   >    >=
+=    ==   !=   <>
+AND
+OR
+```
+
+# Examples
+
+```sql
+1 + 2 * 3
+'a' || 'b'
+1 < 2 AND 3 != 4
+data ->> '$.name'
+```
+",
+    op: Token,
+    left: Box<Expr>,
+    right: Box<Expr>;
+    analyse = crate::parser::analyse::binary_expr
+);
+
+node!(
+    UnaryExpr,
+    r"Unary expression, see: https://www.sqlite.org/lang_expr.html#operators_and_parse_affecting_attributes
+
+The prefix operators - and + bind tighter than any binary operator, NOT binds tighter than AND but looser than the comparison operators, thus NOT a = b is NOT (a = b).
+
+# Examples
+
+```sql
+-42
++x
+NOT 1
+NOT a = b
+```
+",
+    op: Token,
+    operand: Box<Expr>
+);
+
+node!(
+    IsExpr,
+    r"Is expression, see: https://www.sqlite.org/lang_expr.html#the_is_and_is_not_operators
+
+The IS and IS NOT operators work like = and != except when one or both of the operands are NULL. In this case, if both operands are NULL, then the IS operator evaluates to 1 (true) and the IS NOT operator evaluates to 0 (false). The postfix operators ISNULL, NOTNULL and NOT NULL are shorthands for IS NULL and IS NOT NULL and are represented as such. IS DISTINCT FROM and IS NOT DISTINCT FROM (sqlite 3.39+) are the standard sql spellings of IS NOT and IS, distinct is set for them.
+
+# Examples
+
+```sql
+x IS 5
+x IS NOT NULL
+x NOTNULL
+x ISNULL
+x IS DISTINCT FROM y
+x IS NOT DISTINCT FROM NULL
+```
+",
+    negated: bool,
+    distinct: bool,
+    left: Box<Expr>,
+    right: Box<Expr>;
+    analyse = crate::parser::analyse::is_expr
+);
+
+node!(
+    ExistsExpr,
+    r"Exists expression, see: https://www.sqlite.org/lang_expr.html#the_exists_operator
+
+The EXISTS operator always evaluates to one of the integer values 0 and 1. If executing the SELECT statement specified as the right-hand operand of the EXISTS operator would return one or more rows, then the EXISTS operator evaluates to 1. If executing the SELECT would return no rows at all, then the EXISTS operator evaluates to 0. NOT EXISTS negates the result.
+
+# Examples
+
+```sql
+EXISTS (SELECT 1 FROM users)
+NOT EXISTS (SELECT * FROM users WHERE id = ?)
+```
+",
+    negated: bool,
+    query: Box<dyn Node>
+);
+
+node!(
+    SubqueryExpr,
+    r"Scalar subquery, see: https://www.sqlite.org/lang_expr.html#scalar_subqueries
+
+A SELECT statement enclosed in parentheses is a subquery. All types of SELECT statement, including aggregate and compound SELECT queries may be used as scalar subqueries. The value of a subquery expression is the first row of the result from the enclosed SELECT statement.
+
+# Examples
+
+```sql
+(SELECT max(id) FROM users)
+```
+",
+    query: Box<dyn Node>
+);
+
+node!(
+    CaseExpr,
+    r"Case expression, see: https://www.sqlite.org/lang_expr.html#the_case_expression
+
+A CASE expression serves a role similar to IF-THEN-ELSE in other programming languages. If the operand is set, each WHEN expression is compared to it, otherwise each WHEN expression is evaluated as a boolean. The result of the first matching WHEN is the result of the CASE, if none matches the ELSE result is used, or NULL if there is no ELSE.
+
+# Examples
+
+```sql
+CASE x WHEN 1 THEN 'one' WHEN 2 THEN 'two' ELSE 'many' END
+CASE WHEN x > 0 THEN 'positive' END
+```
+",
+    operand: Option<Expr>,
+    when_clauses: Vec<(Expr, Expr)>,
+    else_clause: Option<Expr>
+);
+
+node!(
+    CastExpr,
+    r"Cast expression, see: https://www.sqlite.org/lang_expr.html#castexpr
+
+Converts the value of expr to the storage class the type name resolves to, following the same affinity rules as column type names.
+
+# Examples
+
+```sql
+CAST(1 AS TEXT)
+CAST('123' AS INTEGER)
+CAST(x AS VARCHAR(255))
+```
+",
+    expr: Box<Expr>,
+    type_name: SqliteStorageClass
+);
+
+node!(
+    BetweenExpr,
+    r"Between expression, see: https://www.sqlite.org/lang_expr.html#the_between_operator
+
+The BETWEEN operator is logically equivalent to a pair of comparisons. `x BETWEEN y AND z` is equivalent to `x>=y AND x<=z` except that with BETWEEN, the x expression is only evaluated once.
+
+# Examples
+
+```sql
+x BETWEEN 1 AND 10
+y NOT BETWEEN 'a' AND 'z'
+```
+",
+    negated: bool,
+    operand: Box<Expr>,
+    low: Box<Expr>,
+    high: Box<Expr>
+);
+
+node!(
+    FunctionCall,
+    r"Function call expression, see: https://www.sqlite.org/lang_expr.html#functions
+
+A function invocation consists of the function name, followed by its arguments in parenthesis. Aggregate functions may be invoked with DISTINCT to only consider distinct values, or with * to consider all rows. Aggregate and window functions can be restricted via a FILTER clause and turned into window functions via an OVER clause.
+
+# Examples
+
+```sql
+COUNT(*)
+SUM(DISTINCT x)
+COALESCE(a, b, c)
+COUNT(*) FILTER (WHERE x > 5) OVER (PARTITION BY y ORDER BY z)
+main.count(*)
+```
+",
+    // set for schema qualified calls, such as main.count(*)
+    schema: Option<String>,
+    name: String,
+    distinct: bool,
+    args: Vec<Expr>,
+    star: bool,
+    filter: Option<Box<Expr>>,
+    over: Option<Box<WindowDefn>>
+);
+
+node!(
+    WindowDefn,
+    r"Window definition, see: https://www.sqlite.org/syntax/window-defn.html
+
+A window definition specifies the rows a window function operates on. For `OVER window_name` only the name is set, referencing a window defined via the WINDOW clause of a SELECT. For `OVER (...)` the name is the optional base window the definition extends.
+
+# Examples
+
+```sql
+OVER (PARTITION BY a ORDER BY b ROWS BETWEEN 1 PRECEDING AND CURRENT ROW)
+OVER win
+```
+",
+    name: Option<String>,
+    partition_by: Vec<Expr>,
+    order_by: Vec<OrderingTerm>,
+    frame: Option<FrameSpec>
+);
+
+node!(
+    NamedWindow,
+    r"Named window of the WINDOW clause of a SELECT, see: https://www.sqlite.org/lang_select.html#the_window_clause
+
+Function calls of the select may reference the window via `OVER name`, other windows of the clause may use it as their base window.
+
+# Examples
+
+```sql
+WINDOW win AS (PARTITION BY a ORDER BY b)
+```
+",
+    name: String,
+    window: WindowDefn
+);
+
+node!(
+    OrderingTerm,
+    r"Ordering term, see: https://www.sqlite.org/syntax/ordering-term.html
+
+# Examples
+
+```sql
+name COLLATE NOCASE DESC NULLS LAST
+```
+",
+    expr: Expr,
+    // set if expr is an integer literal referring to a result column by its position, in a
+    // SELECT ORDER BY clause
+    ordinal: Option<usize>,
+    collate: Option<String>,
+    // either ASC or DESC
+    order: Option<Keyword>,
+    // either FIRST or LAST
+    nulls: Option<Keyword>
+);
+
+#[derive(Debug)]
+/// https://www.sqlite.org/syntax/frame-spec.html
+pub enum FrameBound {
+    UnboundedPreceding,
+    Preceding(Expr),
+    CurrentRow,
+    Following(Expr),
+    UnboundedFollowing,
+}
+
+node!(
+    FrameSpec,
+    r"Frame specification of a window, see: https://www.sqlite.org/windowfunctions.html#frame_specifications
+
+# Examples
+
+```sql
+ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW
+RANGE 5 PRECEDING EXCLUDE TIES
+```
+",
+    // either RANGE, ROWS or GROUPS
+    unit: Keyword,
+    start: FrameBound,
+    end: Option<FrameBound>,
+    // NO (OTHERS), CURRENT (ROW), GROUP or TIES
+    exclude: Option<Keyword>
+);
+
+#[derive(Debug)]
+/// right hand side of an IN expression
+pub enum InBody {
+    /// (expr, expr, ...), may be empty
+    List(Vec<Expr>),
+    /// (select-stmt)
+    Subquery(Box<dyn Node>),
+    /// [schema-name.]table-name
+    Table(SchemaTableContainer),
+}
+
+node!(
+    InExpr,
+    r"In expression, see: https://www.sqlite.org/lang_expr.html#the_in_and_not_in_operators
+
+The IN and NOT IN operators take an expression on the left and a list of values or a subquery on the right. When the right operand of an IN or NOT IN operator is a subquery, the subquery must have the same number of columns as there are columns in the row value of the left operand.
+
+# Examples
+
+```sql
+x IN (1, 2, 3)
+x NOT IN (SELECT id FROM users)
+x IN schema.table
+```
+",
+    negated: bool,
+    operand: Box<Expr>,
+    body: InBody;
+    analyse = crate::parser::analyse::in_expr
+);
+
+node!(
+    LikeExpr,
+    r"Like, glob, regexp and match expression, see: https://www.sqlite.org/lang_expr.html#the_like_glob_regexp_match_and_extract_operators
+
+The LIKE operator does a pattern matching comparison. A percent symbol ('%') in the LIKE pattern matches any sequence of zero or more characters in the string. An underscore ('_') in the LIKE pattern matches any single character in the string. The optional ESCAPE clause sets the character used to escape '%' and '_'.
+
+The GLOB operator is similar to LIKE but uses the Unix file globbing syntax for its wildcards. REGEXP and MATCH are only usable if the application defines a regexp() or match() function.
+
+# Examples
+
+```sql
+name LIKE 'a%'
+name NOT LIKE 'a!%%' ESCAPE '!'
+path GLOB '*.sql'
+```
+",
+    negated: bool,
+    op: Keyword,
+    operand: Box<Expr>,
+    pattern: Box<Expr>,
+    escape: Option<Box<Expr>>
 );
 
 node!(
@@ -137,6 +561,7 @@ EXPLAIN VACUUM;
 EXPLAIN QUERY PLAN VACUUM;
 ```
 ",
+    query_plan: bool,
     child: Box<dyn Node>
 );
 
@@ -181,7 +606,8 @@ BEGIN IMMEDIATE TRANSACTION;
 BEGIN EXCLUSIVE TRANSACTION;
 ```
 ",
-    transaction_kind: Option<Keyword>
+    transaction_kind: Option<Keyword>;
+    analyse = crate::parser::analyse::begin
 );
 
 node!(
@@ -198,7 +624,8 @@ END;
 COMMIT TRANSACTION;
 END TRANSACTION;
 ```
-",
+",;
+    analyse = crate::parser::analyse::commit
 );
 
 node!(
@@ -218,7 +645,8 @@ ROLLBACK TRANSACTION TO save_point;
 ROLLBACK TRANSACTION TO SAVEPOINT save_point;
 ```
 ",
-    save_point: Option<String>
+    save_point: Option<String>;
+    analyse = crate::parser::analyse::rollback
 );
 
 node!(
@@ -264,6 +692,15 @@ pub enum SchemaTableContainer {
     Table(String),
 }
 
+impl SchemaTableContainer {
+    /// the table_name, without the schema_name
+    pub fn table(&self) -> &str {
+        match self {
+            Self::SchemaAndTable { table, .. } | Self::Table(table) => table,
+        }
+    }
+}
+
 node!(
     Drop,
     r"Drop stmt
@@ -314,7 +751,8 @@ DROP VIEW IF EXISTS schema_name.view_name;
 ",
     if_exists: bool,
     ttype: Keyword,
-    argument: SchemaTableContainer
+    argument: SchemaTableContainer;
+    analyse = crate::parser::analyse::drop
 );
 
 node!(
@@ -329,7 +767,8 @@ SAVEPOINTs are a method of creating transactions, similar to BEGIN and COMMIT, e
 SAVEPOINT savepoint_name;
 ```
 ",
-    savepoint_name: String
+    savepoint_name: String;
+    analyse = crate::parser::analyse::savepoint
 );
 
 node!(
@@ -345,7 +784,8 @@ RELEASE savepoint_name;
 RELEASE SAVEPOINT savepoint_name;
 ```
 ",
-    savepoint_name: String
+    savepoint_name: String;
+    analyse = crate::parser::analyse::release
 );
 
 node!(
@@ -407,7 +847,8 @@ ALTER TABLE schema.table_name DROP COLUMN column_name;
     rename_column_target: Option<String>,
     new_column_name: Option<String>,
     add_column: Option<ColumnDef>,
-    drop_column: Option<String>
+    drop_column: Option<String>;
+    analyse = crate::parser::analyse::alter
 );
 
 #[derive(Debug, serde::Serialize)]
@@ -442,6 +883,23 @@ pub struct ForeignKeyClause {
     pub initially_deferred: bool,
 }
 
+#[derive(Debug)]
+/// column constraint with its optional CONSTRAINT name label, see:
+/// https://www.sqlite.org/syntax/column-constraint.html
+pub struct NamedColumnConstraint {
+    pub name: Option<String>,
+    pub constraint: ColumnConstraint,
+}
+
+impl From<ColumnConstraint> for NamedColumnConstraint {
+    fn from(constraint: ColumnConstraint) -> Self {
+        Self {
+            name: None,
+            constraint,
+        }
+    }
+}
+
 #[derive(Debug)]
 /// https://www.sqlite.org/syntax/column-constraint.html
 pub enum ColumnConstraint {
@@ -458,9 +916,13 @@ pub enum ColumnConstraint {
         on_conflict: Option<Keyword>,
     },
     Check(Expr),
+    /// DEFAULT literal-value and DEFAULT (literal-value) both result in literal, with
+    /// parenthesized recording the parenthesis, only non literal expressions such as DEFAULT (1 +
+    /// 2) result in expr
     Default {
         expr: Option<Expr>,
         literal: Option<Literal>,
+        parenthesized: bool,
     },
     Collate(String),
     Generated {
@@ -482,9 +944,37 @@ node!(
     name: String,
     // equivalent to type_name: https://www.sqlite.org/syntax/type-name.html
     type_name: Option<SqliteStorageClass>,
-    constraints: Vec<ColumnConstraint>
+    constraints: Vec<NamedColumnConstraint>
 );
 
+#[derive(Debug)]
+/// https://www.sqlite.org/syntax/table-constraint.html
+pub enum TableConstraint {
+    PrimaryKey {
+        // indexed columns
+        columns: Vec<OrderingTerm>,
+        on_conflict: Option<Keyword>,
+    },
+    Unique {
+        // indexed columns
+        columns: Vec<OrderingTerm>,
+        on_conflict: Option<Keyword>,
+    },
+    Check(Box<Expr>),
+    ForeignKey {
+        columns: Vec<String>,
+        clause: ForeignKeyClause,
+    },
+}
+
+#[derive(Debug)]
+/// table constraint with its optional CONSTRAINT name label, see:
+/// https://www.sqlite.org/syntax/table-constraint.html
+pub struct NamedTableConstraint {
+    pub name: Option<String>,
+    pub constraint: TableConstraint,
+}
+
 #[derive(Debug, serde::Serialize)]
 pub enum PragmaInvocation {
     Query,
@@ -510,5 +1000,479 @@ PRAGMA database_list;
 ",
     // since pragma names can be schema.pragma_name, we encode it like this in the ast
     name: SchemaTableContainer,
-    invocation: PragmaInvocation
+    invocation: PragmaInvocation;
+    analyse = crate::parser::analyse::pragma
+);
+
+#[derive(Debug, serde::Serialize)]
+/// https://www.sqlite.org/lang_createtrigger.html, omitting the timing defaults to BEFORE
+pub enum TriggerTiming {
+    Before,
+    After,
+    InsteadOf,
+}
+
+#[derive(Debug, serde::Serialize)]
+/// https://www.sqlite.org/lang_createtrigger.html
+pub enum TriggerEvent {
+    Delete,
+    Insert,
+    Update,
+}
+
+node!(
+    CreateTrigger,
+    r"Create trigger stmt, see: https://www.sqlite.org/lang_createtrigger.html
+
+Triggers are database operations that are automatically performed when a specified database event occurs. Each trigger must specify that it will fire for one of the following operations: DELETE, INSERT, UPDATE. The trigger fires once for each row that is deleted, inserted, or updated. If the UPDATE OF clause is given, the trigger only fires for updates of the listed columns.
+
+The body of a trigger consists of one or more DELETE, INSERT, UPDATE or SELECT statements, the WHEN clause restricts the rows the trigger is executed for.
+
+# Examples
+
+```sql
+CREATE TRIGGER update_customer_address UPDATE OF address ON customers
+BEGIN
+    UPDATE orders SET address = new.address WHERE customer_name = old.name;
+END;
+
+CREATE TEMP TRIGGER IF NOT EXISTS schema_name.log_delete AFTER DELETE ON users FOR EACH ROW
+BEGIN
+    INSERT INTO log VALUES ('deleted');
+END;
+```
+",
+    temporary: bool,
+    if_not_exists: bool,
+    name: SchemaTableContainer,
+    timing: Option<TriggerTiming>,
+    event: TriggerEvent,
+    // columns specified via UPDATE OF <column_name>, ...
+    update_of: Option<Vec<String>>,
+    table: String,
+    for_each_row: bool,
+    when: Option<Expr>,
+    body: Vec<Box<dyn Node>>
+);
+
+node!(
+    CreateVirtualTable,
+    r"Create virtual table stmt, see: https://www.sqlite.org/lang_createvtab.html
+
+A virtual table is an interface to an external storage or computation engine that appears to be a table but does not actually store information in the database file. The module implements the virtual table, its arguments are passed to the module as is and are therefore stored as the raw tokens of each argument.
+
+# Examples
+
+```sql
+CREATE VIRTUAL TABLE posts USING fts5(title, body, tokenize = 'porter');
+CREATE VIRTUAL TABLE IF NOT EXISTS main.boxes USING rtree(id, min_x, max_x);
+```
+",
+    if_not_exists: bool,
+    name: SchemaTableContainer,
+    module: String,
+    // tokens of each comma separated module argument
+    arguments: Vec<Vec<Token>>;
+    analyse = crate::parser::analyse::create_virtual_table
+);
+
+node!(
+    CreateTable,
+    r"Create table stmt, see: https://www.sqlite.org/lang_createtable.html
+
+The CREATE TABLE command creates a new table, either from a list of column definitions and table constraints or from the result of a SELECT.
+
+# Examples
+
+```sql
+CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+CREATE TABLE IF NOT EXISTS tags (
+    user_id INTEGER NOT NULL REFERENCES users(id),
+    tag TEXT NOT NULL,
+    PRIMARY KEY (user_id, tag)
+) WITHOUT ROWID, STRICT;
+CREATE TEMP TABLE IF NOT EXISTS main.totals AS SELECT name, sum(amount) FROM orders;
+```
+",
+    temporary: bool,
+    if_not_exists: bool,
+    name: SchemaTableContainer,
+    // empty for CREATE TABLE ... AS SELECT
+    columns: Vec<ColumnDef>,
+    constraints: Vec<NamedTableConstraint>,
+    without_rowid: bool,
+    strict: bool,
+    // either Select, Values or a WithClause holding one of these
+    select: Option<Box<dyn Node>>;
+    analyse = crate::parser::analyse::create_table
+);
+
+node!(
+    CreateView,
+    r"Create view stmt, see: https://www.sqlite.org/lang_createview.html
+
+The CREATE VIEW command assigns a name to a pre-packaged SELECT statement. Once the view is created, it can be used in the FROM clause of another SELECT in place of a table name. If a column list follows the view name, it names the columns of the view, otherwise the column names are derived from the SELECT.
+
+# Examples
+
+```sql
+CREATE VIEW adults AS SELECT * FROM users WHERE age >= 18;
+CREATE TEMP VIEW IF NOT EXISTS main.totals(name, total) AS
+    SELECT name, sum(amount) FROM orders GROUP BY name;
+```
+",
+    temporary: bool,
+    if_not_exists: bool,
+    name: SchemaTableContainer,
+    columns: Vec<String>,
+    select: Box<dyn Node>;
+    analyse = crate::parser::analyse::create_view
+);
+
+#[derive(Debug)]
+/// https://www.sqlite.org/syntax/result-column.html
+pub enum ResultColumn {
+    /// *
+    Star,
+    /// table_name.*
+    TableStar(String),
+    /// expr [[AS] alias]
+    Expr {
+        expr: Box<Expr>,
+        alias: Option<String>,
+    },
+}
+
+#[derive(Debug)]
+/// https://www.sqlite.org/syntax/table-or-subquery.html
+pub enum TableOrSubquery {
+    /// [schema_name.]table_name [[AS] alias]
+    Table {
+        name: SchemaTableContainer,
+        alias: Option<String>,
+    },
+    /// (select-stmt) [[AS] alias]
+    Subquery {
+        select: Box<Select>,
+        alias: Option<String>,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, serde::Serialize)]
+/// https://www.sqlite.org/syntax/join-operator.html, [LEFT|RIGHT|FULL] OUTER JOIN is equivalent
+/// to [LEFT|RIGHT|FULL] JOIN and thus not represented
+pub enum JoinOperator {
+    /// ,
+    Comma,
+    /// JOIN
+    Join,
+    /// CROSS JOIN
+    Cross,
+    /// INNER JOIN
+    Inner,
+    /// LEFT [OUTER] JOIN
+    Left,
+    /// RIGHT [OUTER] JOIN
+    Right,
+    /// FULL [OUTER] JOIN
+    Full,
+}
+
+impl std::fmt::Display for JoinOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Comma => ",",
+            Self::Join => "JOIN",
+            Self::Cross => "CROSS JOIN",
+            Self::Inner => "INNER JOIN",
+            Self::Left => "LEFT JOIN",
+            Self::Right => "RIGHT JOIN",
+            Self::Full => "FULL JOIN",
+        })
+    }
+}
+
+#[derive(Debug)]
+/// https://www.sqlite.org/syntax/join-constraint.html
+pub enum JoinConstraint {
+    On(Box<Expr>),
+    Using(Vec<String>),
+}
+
+node!(
+    Join,
+    r"Join of a table or subquery, see: https://www.sqlite.org/lang_select.html#determination_of_input_data_from_clause_processing_
+
+A join combines the rows of its left hand side, all tables and joins before it in the FROM clause, with the rows of its table or subquery. The join constraint restricts the combined rows, NATURAL joins implicitly join on all columns both sides have in common.
+
+# Examples
+
+```sql
+LEFT OUTER JOIN b ON a.id = b.id
+NATURAL JOIN b
+, b
+CROSS JOIN b USING (id, name)
+```
+",
+    natural: bool,
+    operator: JoinOperator,
+    table: TableOrSubquery,
+    constraint: Option<JoinConstraint>
+);
+
+node!(
+    JoinClause,
+    r"Join clause, see: https://www.sqlite.org/syntax/join-clause.html
+
+The FROM clause of a SELECT, its first table or subquery is joined with the table or subquery of each join, in order.
+
+# Examples
+
+```sql
+FROM a
+FROM a, b
+FROM a LEFT JOIN b ON a.id = b.id NATURAL JOIN c
+```
+",
+    table: TableOrSubquery,
+    joins: Vec<Join>;
+    analyse = crate::parser::analyse::join_clause
+);
+
+#[derive(Debug, PartialEq, Eq, serde::Serialize)]
+/// https://www.sqlite.org/syntax/compound-operator.html
+pub enum CompoundOperator {
+    Union,
+    UnionAll,
+    Intersect,
+    Except,
+}
+
+node!(
+    CompoundSelect,
+    r"Compound select, see: https://www.sqlite.org/lang_select.html#compound_select_statements
+
+A compound operator combines the rows of the preceding select with the rows of its select. ORDER BY and LIMIT of a compound select apply to the combined result and are therefore stored on the outermost select.
+
+# Examples
+
+```sql
+UNION ALL SELECT x + 1 FROM c
+EXCEPT SELECT id FROM banned
+```
+",
+    operator: CompoundOperator,
+    select: Select
+);
+
+node!(
+    Select,
+    r"Select stmt, see: https://www.sqlite.org/lang_select.html
+
+The SELECT statement is used to query the database. The result of a SELECT is zero or more rows of data where each row has a fixed number of columns.
+
+# Examples
+
+```sql
+SELECT * FROM users;
+SELECT DISTINCT u.name, count(*) AS amount FROM main.users AS u
+    WHERE u.age > 18
+    GROUP BY u.name HAVING amount > 1
+    ORDER BY amount DESC
+    LIMIT 10 OFFSET 5;
+SELECT id FROM users UNION SELECT id FROM admins ORDER BY id;
+SELECT sum(x) OVER win FROM t WINDOW win AS (ORDER BY y);
+```
+",
+    distinct: bool,
+    columns: Vec<ResultColumn>,
+    from: Option<JoinClause>,
+    where_clause: Option<Expr>,
+    group_by: Vec<Expr>,
+    having: Option<Expr>,
+    windows: Vec<NamedWindow>,
+    compound: Vec<CompoundSelect>,
+    order_by: Vec<OrderingTerm>,
+    limit: Option<Expr>,
+    offset: Option<Expr>;
+    analyse = crate::parser::analyse::select
+);
+
+#[derive(Debug)]
+/// the rows inserted by an INSERT, see: https://www.sqlite.org/lang_insert.html
+pub enum InsertSource {
+    /// VALUES (expr, ...), ..., each row keeps its own arity, so rows differing from the column
+    /// list can be detected
+    Values(Vec<Vec<Expr>>),
+    /// select-stmt
+    Select(Box<Select>),
+    /// DEFAULT VALUES
+    DefaultValues,
+}
+
+node!(
+    Insert,
+    r"Insert stmt, see: https://www.sqlite.org/lang_insert.html
+
+The INSERT statement inserts rows into a table, either from a list of VALUES, the result of a SELECT or a single row consisting of the column defaults. REPLACE is an alias for INSERT OR REPLACE and represented as such.
+
+# Examples
+
+```sql
+INSERT INTO users (name, age) VALUES ('leibniz', 70), ('euler', 76);
+INSERT OR IGNORE INTO main.users SELECT * FROM old_users;
+REPLACE INTO users DEFAULT VALUES;
+INSERT INTO users (name) VALUES ('gauss') RETURNING id;
+INSERT INTO users (name) VALUES ('gauss') ON CONFLICT (name) DO NOTHING;
+```
+",
+    or: Option<Keyword>,
+    table: SchemaTableContainer,
+    alias: Option<String>,
+    columns: Option<Vec<String>>,
+    source: InsertSource,
+    upsert: Option<Upsert>,
+    returning: Option<Vec<ResultColumn>>;
+    analyse = crate::parser::analyse::insert
+);
+
+node!(
+    Assignment,
+    r"Assignment of an UPDATE SET or an upsert DO UPDATE SET, see: https://www.sqlite.org/lang_update.html
+
+Assigns the result of expr to a column, a parenthesized list of columns is assigned the values of a row value.
+
+# Examples
+
+```sql
+name = 'leibniz'
+(name, age) = ('euler', 76)
+```
+",
+    columns: Vec<String>,
+    expr: Expr
+);
+
+#[derive(Debug)]
+/// https://www.sqlite.org/syntax/upsert-clause.html
+pub enum UpsertAction {
+    Nothing,
+    Update {
+        set: Vec<Assignment>,
+        where_clause: Option<Box<Expr>>,
+    },
+}
+
+node!(
+    Upsert,
+    r"Upsert clause, see: https://www.sqlite.org/lang_upsert.html
+
+UPSERT is a clause added to INSERT that causes the INSERT to behave as an UPDATE or a no-op if the INSERT would violate a uniqueness constraint. The conflict target specifies the uniqueness constraint that triggers the upsert, it is optional for DO NOTHING and for the last upsert clause.
+
+# Examples
+
+```sql
+ON CONFLICT DO NOTHING
+ON CONFLICT (name) DO UPDATE SET age = excluded.age WHERE age < excluded.age
+```
+",
+    target: Vec<OrderingTerm>,
+    target_where: Option<Expr>,
+    action: UpsertAction
+);
+
+node!(
+    Delete,
+    r"Delete stmt, see: https://www.sqlite.org/lang_delete.html
+
+The DELETE command removes records from the table identified by the qualified-table-name. If the WHERE clause is omitted, all records in the table are deleted, otherwise only the rows for which the WHERE expression is true are deleted.
+
+# Examples
+
+```sql
+DELETE FROM users;
+DELETE FROM main.users WHERE age < 18;
+DELETE FROM users WHERE age < 18 RETURNING *;
+```
+",
+    table: SchemaTableContainer,
+    where_clause: Option<Expr>,
+    returning: Option<Vec<ResultColumn>>
+);
+
+node!(
+    Update,
+    r"Update stmt, see: https://www.sqlite.org/lang_update.html
+
+An UPDATE statement is used to modify a subset of the values stored in zero or more rows of the database table identified by the qualified-table-name. If the WHERE clause is omitted, all rows in the table are modified, otherwise only the rows for which the WHERE expression is true. The FROM clause joins other tables, their columns may be used in the WHERE clause and the assignments.
+
+# Examples
+
+```sql
+UPDATE users SET name = 'leibniz';
+UPDATE OR IGNORE main.users AS u SET name = 'euler', age = 76 WHERE u.id = 1;
+UPDATE users SET age = a.age FROM ages AS a WHERE users.id = a.id RETURNING *;
+```
+",
+    or: Option<Keyword>,
+    table: SchemaTableContainer,
+    alias: Option<String>,
+    set: Vec<Assignment>,
+    from: Option<JoinClause>,
+    where_clause: Option<Expr>,
+    returning: Option<Vec<ResultColumn>>
+);
+
+node!(
+    Values,
+    r"Values stmt, see: https://www.sqlite.org/lang_select.html#the_values_clause
+
+The phrase VALUES(expr-list) means the same thing as SELECT expr-list, the phrase VALUES(expr-list-1),...,(expr-list-N) means the same thing as SELECT expr-list-1 UNION ALL ... UNION ALL SELECT expr-list-N. All rows have to have the same amount of values, if they do not, the differing row lengths are recorded in arities.
+
+# Examples
+
+```sql
+VALUES (1, 'one'), (2, 'two');
+```
+",
+    rows: Vec<Vec<Expr>>,
+    arities: Vec<usize>
+);
+
+node!(
+    CommonTableExpression,
+    r"Common table expression, see: https://www.sqlite.org/syntax/common-table-expression.html
+
+A common table expression acts like a temporary view that exists only for the duration of the statement its WITH clause prefixes. The optional MATERIALIZED (true) or NOT MATERIALIZED (false) hint (sqlite 3.35+) instructs the query planner to either compute the cte once or to inline it.
+
+# Examples
+
+```sql
+adults(name) AS (SELECT name FROM users WHERE age > 17)
+c AS NOT MATERIALIZED (SELECT 1)
+```
+",
+    name: String,
+    columns: Vec<String>,
+    materialized: Option<bool>,
+    body: Box<dyn Node>;
+    analyse = crate::parser::analyse::common_table_expression
+);
+
+node!(
+    WithClause,
+    r"With clause, see: https://www.sqlite.org/lang_with.html
+
+Prefixes a SELECT, INSERT, UPDATE or DELETE with common table expressions, available as tables in the prefixed statement. RECURSIVE allows ctes to refer to themselves.
+
+# Examples
+
+```sql
+WITH adults AS (SELECT * FROM users WHERE age > 17) SELECT name FROM adults;
+WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c WHERE x < 10) SELECT x FROM c;
+```
+",
+    recursive: bool,
+    ctes: Vec<CommonTableExpression>,
+    child: Box<dyn Node>
 );
@@ -37,6 +37,34 @@ macro_rules! test_group_pass_assert {
 
 #[cfg(test)]
 mod should_pass {
+    use crate::parser::nodes::*;
+    use crate::types::{Keyword, Token, Type, storage::SqliteStorageClass};
+
+    /// ALTER TABLE t ADD COLUMN c TEXT CHECK (<expr>);
+    fn alter_check(expr: Expr) -> Vec<Alter> {
+        vec![Alter::new(
+            SchemaTableContainer::Table("t".into()),
+            None,
+            None,
+            None,
+            Some(ColumnDef::new(
+                "c".into(),
+                Some(SqliteStorageClass::Text),
+                vec![ColumnConstraint::Check(expr).into()],
+            )),
+            None,
+        )]
+    }
+
+    /// <literal> IS [NOT] NULL
+    fn is_null(literal: Type, negated: bool) -> Expr {
+        Expr::compound(IsExpr::new(
+            negated,
+            false,
+            Box::new(Expr::literal(literal)),
+            Box::new(Expr::literal(Type::Keyword(Keyword::NULL))),
+        ))
+    }
 
     test_group_pass_assert! {
         sqleibniz_instructions,
@@ -45,19 +73,24 @@ mod should_pass {
     VACUUM 25;
     -- the above is skipped
     EXPLAIN VACUUM;
-        "=vec![Explain::new(Box::new(Vacuum::new(None, None)))],
+        "=vec![Explain::new(false, Box::new(Vacuum::new(None, None)))],
 
         expect_with_semicolons_in_comment: r"
     -- @sqleibniz::expect lets skip this error;;;;;;;;
     VACUUM 25;
     EXPLAIN VACUUM;
-        "=vec![Explain::new(Box::new(Vacuum::new(None, None)))]
+        "=vec![Explain::new(false, Box::new(Vacuum::new(None, None)))],
+
+        node: r"
+    -- @sqleibniz::node(Explain)
+    EXPLAIN VACUUM;
+        "=vec![Explain::new(false, Box::new(Vacuum::new(None, None)))]
     }
 
     test_group_pass_assert! {
         sql_stmt_prefix,
-        explain: r#"EXPLAIN VACUUM;"#=vec![Explain::new(Box::new(Vacuum::new(None, None)))],
-        explain_query_plan: r#"EXPLAIN QUERY PLAN VACUUM;"#=vec![Explain::new(Box::new(Vacuum::new(None, None)))]
+        explain: r#"EXPLAIN VACUUM;"#=vec![Explain::new(false, Box::new(Vacuum::new(None, None)))],
+        explain_query_plan: r#"EXPLAIN QUERY PLAN VACUUM;"#=vec![Explain::new(true, Box::new(Vacuum::new(None, None)))]
     }
 
     test_group_pass_assert! {
@@ -194,6 +227,7 @@ mod should_pass {
                     None,
                     None,
                     None,
+                    None,
                 )
             ),
         ],
@@ -206,6 +240,7 @@ mod should_pass {
                     None,
                     None,
                     None,
+                    None,
                 )
             ),
         ]
@@ -297,6 +332,49 @@ mod should_pass {
         ]
     }
 
+    test_group_pass_assert! {
+        column_constraint_named,
+
+        primary_key:
+        r"ALTER TABLE t ADD COLUMN c INT CONSTRAINT pk PRIMARY KEY;"=
+        vec![Alter::new(
+            SchemaTableContainer::Table("t".into()),
+            None, None, None,
+            Some(ColumnDef::new(
+                "c".into(),
+                Some(SqliteStorageClass::Integer),
+                vec![NamedColumnConstraint {
+                    name: Some("pk".into()),
+                    constraint: ColumnConstraint::PrimaryKey {
+                        asc_desc: None,
+                        on_conflict: None,
+                        autoincrement: false,
+                    },
+                }],
+            )),
+            None,
+        )],
+
+        mixed:
+        r"ALTER TABLE t ADD COLUMN c INT NOT NULL CONSTRAINT u UNIQUE;"=
+        vec![Alter::new(
+            SchemaTableContainer::Table("t".into()),
+            None, None, None,
+            Some(ColumnDef::new(
+                "c".into(),
+                Some(SqliteStorageClass::Integer),
+                vec![
+                    ColumnConstraint::NotNull { on_conflict: None }.into(),
+                    NamedColumnConstraint {
+                        name: Some("u".into()),
+                        constraint: ColumnConstraint::Unique { on_conflict: None },
+                    },
+                ],
+            )),
+            None,
+        )]
+    }
+
     test_group_pass_assert! {
         column_constraint_primary_key,
 
@@ -312,7 +390,7 @@ mod should_pass {
                     asc_desc: None,
                     on_conflict: None,
                     autoincrement: false,
-                }],
+                }.into()],
             )),
             None,
         )],
@@ -329,7 +407,7 @@ mod should_pass {
                     asc_desc: Some(Keyword::ASC),
                     on_conflict: None,
                     autoincrement: false,
-                }],
+                }.into()],
             )),
             None,
         )],
@@ -346,7 +424,7 @@ mod should_pass {
                     asc_desc: Some(Keyword::DESC),
                     on_conflict: Some(Keyword::REPLACE),
                     autoincrement: true,
-                }],
+                }.into()],
             )),
             None,
         )]
@@ -363,7 +441,7 @@ mod should_pass {
             Some(ColumnDef::new(
                 "column_name".into(),
                 Some(SqliteStorageClass::Text),
-                vec![ColumnConstraint::NotNull { on_conflict: None }],
+                vec![ColumnConstraint::NotNull { on_conflict: None }.into()],
             )),
             None,
         )],
@@ -378,7 +456,7 @@ mod should_pass {
                 Some(SqliteStorageClass::Text),
                 vec![ColumnConstraint::Unique {
                     on_conflict: Some(Keyword::REPLACE),
-                }],
+                }.into()],
             )),
             None,
         )]
@@ -398,9 +476,9 @@ mod should_pass {
                 vec![ColumnConstraint::Check(
                     Expr::new(
                         Some(Token::new(Type::String("literal string lol".into()))),
-                        None, None, None, None
+                        None, None, None, None, None
                     )
-                )],
+                ).into()],
             )),
             None,
         )],
@@ -418,7 +496,59 @@ mod should_pass {
                     literal: Some(Literal {
                         t: Token::new(Type::String("literal".into()))
                     }),
-                }],
+                    parenthesized: false,
+                }.into()],
+            )),
+            None,
+        )],
+
+        default_number:
+        r"ALTER TABLE t ADD COLUMN c INT DEFAULT 5;"=
+        vec![Alter::new(
+            SchemaTableContainer::Table("t".into()),
+            None, None, None,
+            Some(ColumnDef::new(
+                "c".into(),
+                Some(SqliteStorageClass::Integer),
+                vec![ColumnConstraint::Default {
+                    expr: None,
+                    literal: Some(Literal { t: Token::new(Type::Integer(5)) }),
+                    parenthesized: false,
+                }.into()],
+            )),
+            None,
+        )],
+
+        default_parenthesized_number:
+        r"ALTER TABLE t ADD COLUMN c INT DEFAULT (5);"=
+        vec![Alter::new(
+            SchemaTableContainer::Table("t".into()),
+            None, None, None,
+            Some(ColumnDef::new(
+                "c".into(),
+                Some(SqliteStorageClass::Integer),
+                vec![ColumnConstraint::Default {
+                    expr: None,
+                    literal: Some(Literal { t: Token::new(Type::Integer(5)) }),
+                    parenthesized: true,
+                }.into()],
+            )),
+            None,
+        )],
+
+        default_expr:
+        r"ALTER TABLE t ADD COLUMN c INT DEFAULT (1 + 2);"=
+        vec![Alter::new(
+            SchemaTableContainer::Table("t".into()),
+            None, None, None,
+            Some(ColumnDef::new(
+                "c".into(),
+                Some(SqliteStorageClass::Integer),
+                vec![ColumnConstraint::Default {
+                    expr: Some(Expr::binary(Type::Plus, Expr::literal(Type::Integer(1)), Expr::literal(Type::Integer(2)))),
+                    literal: None,
+                    parenthesized: true,
+                }.into()],
             )),
             None,
         )],
@@ -431,7 +561,7 @@ mod should_pass {
             Some(ColumnDef::new(
                 "column_name".into(),
                 Some(SqliteStorageClass::Text),
-                vec![ColumnConstraint::Collate("collation_name".into())],
+                vec![ColumnConstraint::Collate("collation_name".into()).into()],
             )),
             None,
         )]
@@ -451,10 +581,10 @@ mod should_pass {
                 vec![ColumnConstraint::Generated {
                     expr: Expr::new(
                         Some(Token::new(Type::String("literal".into()))),
-                        None, None, None, None
+                        None, None, None, None, None
                     ),
                     stored_virtual: Some(Keyword::STORED),
-                }],
+                }.into()],
             )),
             None,
         )],
@@ -471,9 +601,9 @@ mod should_pass {
                     stored_virtual: None,
                     expr: Expr::new(
                         Some(Token::new(Type::String("literal".into()))),
-                        None, None, None, None
+                        None, None, None, None, None
                     )
-                }],
+                }.into()],
             )),
             None,
         )]
@@ -498,7 +628,7 @@ mod should_pass {
                     match_type: None,
                     deferrable: false,
                     initially_deferred: false,
-                })],
+                }).into()],
             )),
             None,
         )]
@@ -514,7 +644,7 @@ mod should_pass {
                     schema: "schema".into(),
                     table: "cache_size".into(),
                 },
-                PragmaInvocation::Assign { value: Token::new(Type::Number(5.0)) }
+                PragmaInvocation::Assign { value: Token::new(Type::Integer(5)) }
             )],
         assign_keyword:"PRAGMA schema.locking_mode = EXCLUSIVE;"=vec![
             Pragma::new(
@@ -530,57 +660,2803 @@ mod should_pass {
                 schema: "schema".into(),
                 table: "optimize".into(),
             },
-            PragmaInvocation::Call { value: Token::new(Type::Number(0xfffe as f64)) }
-            )]
+            PragmaInvocation::Call { value: Token::new(Type::Integer(0xfffe)) }
+            )],
+        boolean_yes:"PRAGMA foreign_keys = yes;"=vec![Pragma::new(
+            SchemaTableContainer::Table("foreign_keys".into()),
+            PragmaInvocation::Assign { value: Token::new(Type::Ident("yes".into())) }
+        )],
+        boolean_on:"PRAGMA main.recursive_triggers = ON;"=vec![Pragma::new(
+            SchemaTableContainer::SchemaAndTable{
+                schema: "main".into(),
+                table: "recursive_triggers".into(),
+            },
+            PragmaInvocation::Assign { value: Token::new(Type::Keyword(Keyword::ON)) }
+        )],
+        boolean_false:"PRAGMA FOREIGN_KEYS = false;"=vec![Pragma::new(
+            SchemaTableContainer::Table("FOREIGN_KEYS".into()),
+            PragmaInvocation::Assign { value: Token::new(Type::Boolean(false)) }
+        )],
+        boolean_call:"PRAGMA query_only(0);"=vec![Pragma::new(
+            SchemaTableContainer::Table("query_only".into()),
+            PragmaInvocation::Call { value: Token::new(Type::Integer(0)) }
+        )],
+        boolean_string:"PRAGMA query_only = 'off';"=vec![Pragma::new(
+            SchemaTableContainer::Table("query_only".into()),
+            PragmaInvocation::Assign { value: Token::new(Type::String("off".into())) }
+        )]
     }
-}
 
-#[allow(unused_macros)]
-macro_rules! test_group_fail {
-    ($group_name:ident,$($ident:ident:$input:literal),*) => {
-    mod $group_name {
-        use crate::{lexer, parser::Parser};
+    test_group_pass_assert! {
+        select_stmt,
 
-        $(
-            #[test]
-            fn $ident() {
-                let input = $input.as_bytes().to_vec();
-                let mut l = lexer::Lexer::new(&input, "parser_test_fail");
-                let toks = l.run();
-                assert_eq!(l.errors.len(), 0);
+        literal: r"SELECT 1;"=vec![Select::new(
+            false,
+            vec![ResultColumn::Expr { expr: Box::new(Expr::literal(Type::Integer(1))), alias: None }],
+            None, None, vec![], None, vec![], vec![], vec![], None, None,
+        )],
+        integer_beyond_f64_precision: r"SELECT 9223372036854775807, 1.5;"=vec![Select::new(
+            false,
+            vec![
+                ResultColumn::Expr { expr: Box::new(Expr::literal(Type::Integer(i64::MAX))), alias: None },
+                ResultColumn::Expr { expr: Box::new(Expr::literal(Type::Float(1.5))), alias: None },
+            ],
+            None, None, vec![], None, vec![], vec![], vec![], None, None,
+        )],
+        star: r"SELECT * FROM t;"=vec![Select::new(
+            false,
+            vec![ResultColumn::Star],
+            Some(JoinClause::new(TableOrSubquery::Table { name: SchemaTableContainer::Table("t".into()), alias: None }, vec![])),
+            None, vec![], None, vec![], vec![], vec![], None, None,
+        )],
+        explain: r"EXPLAIN SELECT all * FROM t;"=vec![Explain::new(false, Box::new(Select::new(
+            false,
+            vec![ResultColumn::Star],
+            Some(JoinClause::new(TableOrSubquery::Table { name: SchemaTableContainer::Table("t".into()), alias: None }, vec![])),
+            None, vec![], None, vec![], vec![], vec![], None, None,
+        )))],
+        all_clauses: r"SELECT DISTINCT u.*, a AS x, b 'y' FROM s.t AS u WHERE a > 1 GROUP BY a, b HAVING count(*) > 1 ORDER BY a DESC LIMIT 10 OFFSET 5;"=vec![Select::new(
+            true,
+            vec![
+                ResultColumn::TableStar("u".into()),
+                ResultColumn::Expr { expr: Box::new(Expr::column(None, None, "a")), alias: Some("x".into()) },
+                ResultColumn::Expr { expr: Box::new(Expr::column(None, None, "b")), alias: Some("y".into()) },
+            ],
+            Some(JoinClause::new(
+                TableOrSubquery::Table {
+                    name: SchemaTableContainer::SchemaAndTable { schema: "s".into(), table: "t".into() },
+                    alias: Some("u".into()),
+                },
+                vec![],
+            )),
+            Some(Expr::binary(Type::GreaterThan, Expr::column(None, None, "a"), Expr::literal(Type::Integer(1)))),
+            vec![Expr::column(None, None, "a"), Expr::column(None, None, "b")],
+            Some(Expr::binary(
+                Type::GreaterThan,
+                Expr::compound(FunctionCall::new(None, "count".into(), false, vec![], true, None, None)),
+                Expr::literal(Type::Integer(1)),
+            )), vec![], vec![],
+            vec![OrderingTerm::new(Expr::column(None, None, "a"), None, None, Some(Keyword::DESC), None)],
+            Some(Expr::literal(Type::Integer(10))),
+            Some(Expr::literal(Type::Integer(5))),
+        )],
+        subquery: r"SELECT * FROM (SELECT 1) sub;"=vec![Select::new(
+            false,
+            vec![ResultColumn::Star],
+            Some(JoinClause::new(
+                TableOrSubquery::Subquery {
+                    select: Box::new(Select::new(
+                        false,
+                        vec![ResultColumn::Expr { expr: Box::new(Expr::literal(Type::Integer(1))), alias: None }],
+                        None, None, vec![], None, vec![], vec![], vec![], None, None,
+                    )),
+                    alias: Some("sub".into()),
+                },
+                vec![],
+            )),
+            None, vec![], None, vec![], vec![], vec![], None, None,
+        )],
+        group_by_having: r"SELECT count(*) FROM t GROUP BY a HAVING count(*) > 1;"=vec![Select::new(
+            false,
+            vec![ResultColumn::Expr {
+                expr: Box::new(Expr::compound(FunctionCall::new(None, "count".into(), false, vec![], true, None, None))),
+                alias: None,
+            }],
+            Some(JoinClause::new(super::table("t"), vec![])),
+            None,
+            vec![Expr::column(None, None, "a")],
+            Some(Expr::binary(
+                Type::GreaterThan,
+                Expr::compound(FunctionCall::new(None, "count".into(), false, vec![], true, None, None)),
+                Expr::literal(Type::Integer(1)),
+            )),
+            vec![],
+            vec![], vec![], None, None,
+        )],
+        having_without_group_by: r"SELECT count(*) FROM t HAVING count(*) > 1;"=vec![Select::new(
+            false,
+            vec![ResultColumn::Expr {
+                expr: Box::new(Expr::compound(FunctionCall::new(None, "count".into(), false, vec![], true, None, None))),
+                alias: None,
+            }],
+            Some(JoinClause::new(super::table("t"), vec![])),
+            None,
+            vec![],
+            Some(Expr::binary(
+                Type::GreaterThan,
+                Expr::compound(FunctionCall::new(None, "count".into(), false, vec![], true, None, None)),
+                Expr::literal(Type::Integer(1)),
+            )),
+            vec![],
+            vec![], vec![], None, None,
+        )],
+        named_window: r"SELECT sum(a) OVER win FROM t WINDOW win AS (PARTITION BY b), other AS (win ORDER BY c);"=vec![Select::new(
+            false,
+            vec![ResultColumn::Expr {
+                expr: Box::new(Expr::compound(FunctionCall::new(
+                    None,
+                    "sum".into(),
+                    false,
+                    vec![Expr::column(None, None, "a")],
+                    false,
+                    None,
+                    Some(Box::new(WindowDefn::new(Some("win".into()), vec![], vec![], None))),
+                ))),
+                alias: None,
+            }],
+            Some(JoinClause::new(super::table("t"), vec![])),
+            None, vec![], None,
+            vec![
+                NamedWindow::new("win".into(), WindowDefn::new(None, vec![Expr::column(None, None, "b")], vec![], None)),
+                NamedWindow::new("other".into(), WindowDefn::new(
+                    Some("win".into()),
+                    vec![],
+                    vec![OrderingTerm::new(Expr::column(None, None, "c"), None, None, None, None)],
+                    None,
+                )),
+            ],
+            vec![], vec![], None, None,
+        )]
+    }
 
-                let mut parser = Parser::new(toks, "parser_test_fail");
-                let _ = parser.parse();
-                assert_ne!(parser.errors.len(), 0);
-            }
-        )*
+    fn table(name: &str) -> TableOrSubquery {
+        TableOrSubquery::Table {
+            name: SchemaTableContainer::Table(name.into()),
+            alias: None,
         }
-    };
-}
+    }
 
-#[cfg(test)]
-mod should_fail {
-    test_group_fail! {
-        negative_tests,
-        eof_semi: ";",
-        eof_literal: "'str'",
-        alter_no_table: "ALTER;",
-        alter_no_name: "ALTER TABLE;",
-        commit_no_semicolon: "COMMIT",
-        end_no_semicolon: "END",
-        rollback_no_semicolon: "ROLLBACK",
-        rollback_to_savepoint_no_name: "ROLLBACK TO SAVEPOINT",
-        begin_no_semicolon: "BEGIN",
-        begin_invalid_modifiers: "BEGIN DEFERRED IMMEDIATE EXCLUSIVE EXCLUSIVE;",
-        detach_no_name: "DETACH;",
-        detach_invalid_literal: "DETACH 'bad';",
-        drop_no_object: "DROP TABLE;",
-        drop_invalid_object: "DROP INDEX 5;",
-        savepoint_no_name: "SAVEPOINT;",
-        release_no_name: "RELEASE;",
-        reindex_no_name: "REINDEX",
-        reindex_invalid_literal: "REINDEX 25;",
-        vacuum_no_semicolon: "VACUUM",
-        vacuum_invalid_combined: "VACUUM 5 INTO 5;"
+    fn select_from(table: &str, joins: Vec<Join>) -> Vec<Select> {
+        vec![Select::new(
+            false,
+            vec![ResultColumn::Star],
+            Some(JoinClause::new(self::table(table), joins)),
+            None,
+            vec![],
+            None,
+            vec![],
+            vec![],
+            vec![],
+            None,
+            None,
+        )]
+    }
+
+    test_group_pass_assert! {
+        join_clause,
+
+        comma: r"SELECT * FROM a, b;"=super::select_from("a", vec![
+            Join::new(false, JoinOperator::Comma, super::table("b"), None),
+        ]),
+        plain: r"SELECT * FROM a JOIN b;"=super::select_from("a", vec![
+            Join::new(false, JoinOperator::Join, super::table("b"), None),
+        ]),
+        cross: r"SELECT * FROM a CROSS JOIN b;"=super::select_from("a", vec![
+            Join::new(false, JoinOperator::Cross, super::table("b"), None),
+        ]),
+        inner_on: r"SELECT * FROM a INNER JOIN b ON a.id = b.id;"=super::select_from("a", vec![
+            Join::new(false, JoinOperator::Inner, super::table("b"), Some(JoinConstraint::On(Box::new(Expr::binary(
+                Type::Equal,
+                Expr::column(None, Some("a"), "id"),
+                Expr::column(None, Some("b"), "id"),
+            ))))),
+        ]),
+        left_outer_using: r"SELECT * FROM a LEFT OUTER JOIN b USING (id, name);"=super::select_from("a", vec![
+            Join::new(false, JoinOperator::Left, super::table("b"), Some(JoinConstraint::Using(vec!["id".into(), "name".into()]))),
+        ]),
+        right: r"SELECT * FROM a RIGHT JOIN b;"=super::select_from("a", vec![
+            Join::new(false, JoinOperator::Right, super::table("b"), None),
+        ]),
+        full_outer: r"SELECT * FROM a FULL OUTER JOIN b;"=super::select_from("a", vec![
+            Join::new(false, JoinOperator::Full, super::table("b"), None),
+        ]),
+        natural: r"SELECT * FROM a NATURAL JOIN b;"=super::select_from("a", vec![
+            Join::new(true, JoinOperator::Join, super::table("b"), None),
+        ]),
+        natural_left: r"SELECT * FROM a NATURAL LEFT JOIN b;"=super::select_from("a", vec![
+            Join::new(true, JoinOperator::Left, super::table("b"), None),
+        ]),
+        chained: r"SELECT * FROM a, b JOIN c USING (id);"=super::select_from("a", vec![
+            Join::new(false, JoinOperator::Comma, super::table("b"), None),
+            Join::new(false, JoinOperator::Join, super::table("c"), Some(JoinConstraint::Using(vec!["id".into()]))),
+        ])
+    }
+
+    fn select_one() -> Select {
+        Select::new(
+            false,
+            vec![ResultColumn::Expr {
+                expr: Box::new(Expr::literal(Type::Integer(1))),
+                alias: None,
+            }],
+            None,
+            None,
+            vec![],
+            None,
+            vec![],
+            vec![],
+            vec![],
+            None,
+            None,
+        )
+    }
+
+    test_group_pass_assert! {
+        order_by_limit,
+
+        terms: r"SELECT * FROM t ORDER BY a ASC NULLS FIRST, b DESC NULLS LAST, c;"=vec![Select::new(
+            false,
+            vec![ResultColumn::Star],
+            Some(JoinClause::new(super::table("t"), vec![])),
+            None, vec![], None, vec![], vec![],
+            vec![
+                OrderingTerm::new(Expr::column(None, None, "a"), None, None, Some(Keyword::ASC), Some(Keyword::FIRST)),
+                OrderingTerm::new(Expr::column(None, None, "b"), None, None, Some(Keyword::DESC), Some(Keyword::LAST)),
+                OrderingTerm::new(Expr::column(None, None, "c"), None, None, None, None),
+            ],
+            None, None,
+        )],
+        limit: r"SELECT * FROM t LIMIT 10;"=vec![Select::new(
+            false,
+            vec![ResultColumn::Star],
+            Some(JoinClause::new(super::table("t"), vec![])),
+            None, vec![], None, vec![], vec![], vec![],
+            Some(Expr::literal(Type::Integer(10))),
+            None,
+        )],
+        limit_offset: r"SELECT * FROM t LIMIT 10 OFFSET 5;"=vec![Select::new(
+            false,
+            vec![ResultColumn::Star],
+            Some(JoinClause::new(super::table("t"), vec![])),
+            None, vec![], None, vec![], vec![], vec![],
+            Some(Expr::literal(Type::Integer(10))),
+            Some(Expr::literal(Type::Integer(5))),
+        )],
+        limit_comma_lists_offset_first: r"SELECT * FROM t LIMIT 5, 10;"=vec![Select::new(
+            false,
+            vec![ResultColumn::Star],
+            Some(JoinClause::new(super::table("t"), vec![])),
+            None, vec![], None, vec![], vec![], vec![],
+            Some(Expr::literal(Type::Integer(10))),
+            Some(Expr::literal(Type::Integer(5))),
+        )]
+    }
+
+    test_group_pass_assert! {
+        compound_select,
+
+        union: r"SELECT 1 UNION SELECT 1;"=vec![Select::new(
+            false,
+            vec![ResultColumn::Expr { expr: Box::new(Expr::literal(Type::Integer(1))), alias: None }],
+            None, None, vec![], None,
+            vec![],
+            vec![CompoundSelect::new(CompoundOperator::Union, super::select_one())],
+            vec![], None, None,
+        )],
+        chained: r"SELECT 1 UNION ALL SELECT 1 INTERSECT SELECT 1 EXCEPT SELECT 1;"=vec![Select::new(
+            false,
+            vec![ResultColumn::Expr { expr: Box::new(Expr::literal(Type::Integer(1))), alias: None }],
+            None, None, vec![], None,
+            vec![],
+            vec![
+                CompoundSelect::new(CompoundOperator::UnionAll, super::select_one()),
+                CompoundSelect::new(CompoundOperator::Intersect, super::select_one()),
+                CompoundSelect::new(CompoundOperator::Except, super::select_one()),
+            ],
+            vec![], None, None,
+        )],
+        trailing_limit: r"SELECT * FROM a UNION SELECT * FROM b LIMIT 1;"=vec![Select::new(
+            false,
+            vec![ResultColumn::Star],
+            Some(JoinClause::new(super::table("a"), vec![])),
+            None, vec![], None,
+            vec![],
+            vec![CompoundSelect::new(CompoundOperator::Union, super::select_from("b", vec![]).remove(0))],
+            vec![],
+            Some(Expr::literal(Type::Integer(1))),
+            None,
+        )]
+    }
+
+    test_group_pass_assert! {
+        with_clause,
+
+        simple: r"WITH c AS (SELECT 1) SELECT * FROM c;"=vec![WithClause::new(
+            false,
+            vec![CommonTableExpression::new("c".into(), vec![], None, Box::new(super::select_one()))],
+            Box::new(super::select_from("c", vec![]).remove(0)),
+        )],
+        columns_and_multiple: r"WITH a(x, y) AS (SELECT 1), b AS (SELECT 1) SELECT * FROM a;"=vec![WithClause::new(
+            false,
+            vec![
+                CommonTableExpression::new("a".into(), vec!["x".into(), "y".into()], None, Box::new(super::select_one())),
+                CommonTableExpression::new("b".into(), vec![], None, Box::new(super::select_one())),
+            ],
+            Box::new(super::select_from("a", vec![]).remove(0)),
+        )],
+        materialized: r"WITH c AS MATERIALIZED (SELECT 1) SELECT * FROM c;"=vec![WithClause::new(
+            false,
+            vec![CommonTableExpression::new("c".into(), vec![], Some(true), Box::new(super::select_one()))],
+            Box::new(super::select_from("c", vec![]).remove(0)),
+        )],
+        not_materialized: r"WITH c AS NOT MATERIALIZED (SELECT 1) SELECT * FROM c;"=vec![WithClause::new(
+            false,
+            vec![CommonTableExpression::new("c".into(), vec![], Some(false), Box::new(super::select_one()))],
+            Box::new(super::select_from("c", vec![]).remove(0)),
+        )],
+        recursive: r"WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c WHERE x < 10) SELECT * FROM c;"=vec![WithClause::new(
+            true,
+            vec![CommonTableExpression::new(
+                "c".into(),
+                vec!["x".into()],
+                None,
+                Box::new(Select::new(
+                    false,
+                    vec![ResultColumn::Expr { expr: Box::new(Expr::literal(Type::Integer(1))), alias: None }],
+                    None, None, vec![], None,
+                    vec![],
+                    vec![CompoundSelect::new(CompoundOperator::UnionAll, Select::new(
+                        false,
+                        vec![ResultColumn::Expr {
+                            expr: Box::new(Expr::binary(Type::Plus, Expr::column(None, None, "x"), Expr::literal(Type::Integer(1)))),
+                            alias: None,
+                        }],
+                        Some(JoinClause::new(super::table("c"), vec![])),
+                        Some(Expr::binary(Type::LessThan, Expr::column(None, None, "x"), Expr::literal(Type::Integer(10)))),
+                        vec![], None, vec![], vec![], vec![], None, None,
+                    ))],
+                    vec![], None, None,
+                )),
+            )],
+            Box::new(super::select_from("c", vec![]).remove(0)),
+        )],
+        recursive_multiple_not_materialized: r"WITH RECURSIVE a AS NOT MATERIALIZED (SELECT 1), b(x) AS (SELECT 1) SELECT * FROM a;"=vec![WithClause::new(
+            true,
+            vec![
+                CommonTableExpression::new("a".into(), vec![], Some(false), Box::new(super::select_one())),
+                CommonTableExpression::new("b".into(), vec!["x".into()], None, Box::new(super::select_one())),
+            ],
+            Box::new(super::select_from("a", vec![]).remove(0)),
+        )],
+        prefixes_delete: r"WITH c AS (SELECT 1) DELETE FROM t;"=vec![WithClause::new(
+            false,
+            vec![CommonTableExpression::new("c".into(), vec![], None, Box::new(super::select_one()))],
+            Box::new(Delete::new(SchemaTableContainer::Table("t".into()), None, None)),
+        )]
+    }
+
+    fn unary(op: Type, operand: Expr) -> Expr {
+        Expr::compound(UnaryExpr::new(Token::new(op), Box::new(operand)))
+    }
+
+    test_group_pass_assert! {
+        expr_unary,
+
+        not: r"ATTACH NOT 1 AS db;"=vec![Attach::new(
+            "db".into(),
+            super::unary(Type::Keyword(Keyword::NOT), Expr::literal(Type::Integer(1))),
+        )],
+        minus: r"ATTACH -42 AS db;"=vec![Attach::new(
+            "db".into(),
+            super::unary(Type::Minus, Expr::literal(Type::Integer(42))),
+        )],
+        plus: r"ATTACH +x AS db;"=vec![Attach::new(
+            "db".into(),
+            super::unary(Type::Plus, Expr::column(None, None, "x")),
+        )],
+        minus_binds_tighter_than_multiplication: r"ATTACH -a * b AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::Asterisk,
+                super::unary(Type::Minus, Expr::column(None, None, "a")),
+                Expr::column(None, None, "b"),
+            ),
+        )],
+        minus_as_rhs: r"ATTACH 1 - -2 AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::Minus,
+                Expr::literal(Type::Integer(1)),
+                super::unary(Type::Minus, Expr::literal(Type::Integer(2))),
+            ),
+        )],
+        bitwise_not: r"ATTACH ~a & b AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::Ampersand,
+                super::unary(Type::Tilde, Expr::column(None, None, "a")),
+                Expr::column(None, None, "b"),
+            ),
+        )],
+        not_binds_looser_than_comparison: r"ATTACH NOT a = b AS db;"=vec![Attach::new(
+            "db".into(),
+            super::unary(
+                Type::Keyword(Keyword::NOT),
+                Expr::binary(Type::Equal, Expr::column(None, None, "a"), Expr::column(None, None, "b")),
+            ),
+        )],
+        not_binds_tighter_than_and: r"ATTACH NOT a AND b AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::Keyword(Keyword::AND),
+                super::unary(Type::Keyword(Keyword::NOT), Expr::column(None, None, "a")),
+                Expr::column(None, None, "b"),
+            ),
+        )],
+        not_with_postfix_not_null: r"ATTACH NOT a NOT NULL AS db;"=vec![Attach::new(
+            "db".into(),
+            super::unary(
+                Type::Keyword(Keyword::NOT),
+                Expr::compound(IsExpr::new(
+                    true,
+                    false,
+                    Box::new(Expr::column(None, None, "a")),
+                    Box::new(Expr::literal(Type::Keyword(Keyword::NULL))),
+                )),
+            ),
+        )]
+    }
+
+    test_group_pass_assert! {
+        expr_paren,
+
+        grouped: r"ATTACH (1 + 2) AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(Type::Plus, Expr::literal(Type::Integer(1)), Expr::literal(Type::Integer(2))),
+        )],
+        overrides_precedence: r"ATTACH (1 + 2) * 3 AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::Asterisk,
+                Expr::binary(Type::Plus, Expr::literal(Type::Integer(1)), Expr::literal(Type::Integer(2))),
+                Expr::literal(Type::Integer(3)),
+            ),
+        )],
+        or_in_and: r"ATTACH (a OR b) AND c AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::Keyword(Keyword::AND),
+                Expr::binary(Type::Keyword(Keyword::OR), Expr::column(None, None, "a"), Expr::column(None, None, "b")),
+                Expr::column(None, None, "c"),
+            ),
+        )],
+        nested: r"ATTACH ((1)) AS db;"=vec![Attach::new("db".into(), Expr::literal(Type::Integer(1)))],
+        not_grouped: r"ATTACH NOT (a AND b) AS db;"=vec![Attach::new(
+            "db".into(),
+            super::unary(
+                Type::Keyword(Keyword::NOT),
+                Expr::binary(Type::Keyword(Keyword::AND), Expr::column(None, None, "a"), Expr::column(None, None, "b")),
+            ),
+        )],
+        scalar_subquery: r"ATTACH (SELECT max(id) FROM t) AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(SubqueryExpr::new(Box::new(Select::new(
+                false,
+                vec![ResultColumn::Expr {
+                    expr: Box::new(Expr::compound(FunctionCall::new(None, "max".into(), false, vec![Expr::column(None, None, "id")], false, None, None))),
+                    alias: None,
+                }],
+                Some(JoinClause::new(super::table("t"), vec![])),
+                None, vec![], None, vec![], vec![], vec![], None, None,
+            )))),
+        )]
+    }
+
+    test_group_pass_assert! {
+        expr_exists,
+
+        exists: r"SELECT * FROM t WHERE EXISTS (SELECT 1 FROM t);"=vec![Select::new(
+            false,
+            vec![ResultColumn::Star],
+            Some(JoinClause::new(super::table("t"), vec![])),
+            Some(Expr::compound(ExistsExpr::new(
+                false,
+                Box::new(Select::new(
+                    false,
+                    vec![ResultColumn::Expr { expr: Box::new(Expr::literal(Type::Integer(1))), alias: None }],
+                    Some(JoinClause::new(super::table("t"), vec![])),
+                    None, vec![], None, vec![], vec![], vec![], None, None,
+                )),
+            ))),
+            vec![], None, vec![], vec![], vec![], None, None,
+        )],
+        not_exists: r"SELECT * FROM t WHERE NOT EXISTS (SELECT * FROM t WHERE id = ?);"=vec![Select::new(
+            false,
+            vec![ResultColumn::Star],
+            Some(JoinClause::new(super::table("t"), vec![])),
+            Some(Expr::compound(ExistsExpr::new(
+                true,
+                Box::new(Select::new(
+                    false,
+                    vec![ResultColumn::Star],
+                    Some(JoinClause::new(super::table("t"), vec![])),
+                    Some(Expr::binary(
+                        Type::Equal,
+                        Expr::column(None, None, "id"),
+                        Expr::new(None, Some(BindParameter::new(None, None)), None, None, None, None),
+                    )),
+                    vec![], None, vec![], vec![], vec![], None, None,
+                )),
+            ))),
+            vec![], None, vec![], vec![], vec![], None, None,
+        )],
+        exists_in_and: r"ATTACH EXISTS (SELECT 1) AND 1 AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::Keyword(Keyword::AND),
+                Expr::compound(ExistsExpr::new(false, Box::new(super::select_one()))),
+                Expr::literal(Type::Integer(1)),
+            ),
+        )]
+    }
+
+    test_group_pass_assert! {
+        values_stmt,
+
+        single: r"VALUES (1);"=vec![Values::new(vec![vec![Expr::literal(Type::Integer(1))]], vec![])],
+        rows: r"VALUES (1, 'a'), (2, 'b');"=vec![Values::new(
+            vec![
+                vec![Expr::literal(Type::Integer(1)), Expr::literal(Type::String("a".into()))],
+                vec![Expr::literal(Type::Integer(2)), Expr::literal(Type::String("b".into()))],
+            ],
+            vec![],
+        )],
+        differing_arities: r"VALUES (1, 2), (3), (4, 5);"=vec![Values::new(
+            vec![
+                vec![Expr::literal(Type::Integer(1)), Expr::literal(Type::Integer(2))],
+                vec![Expr::literal(Type::Integer(3))],
+                vec![Expr::literal(Type::Integer(4)), Expr::literal(Type::Integer(5))],
+            ],
+            vec![2, 1],
+        )]
+    }
+
+    test_group_pass_assert! {
+        create_trigger_stmt,
+
+        minimal: r"CREATE TRIGGER trig DELETE ON users BEGIN SELECT 1; END;"=vec![CreateTrigger::new(
+            false,
+            false,
+            SchemaTableContainer::Table("trig".into()),
+            None,
+            TriggerEvent::Delete,
+            None,
+            "users".into(),
+            false,
+            None,
+            vec![Box::new(super::select_one())],
+        )],
+        all_clauses: r"CREATE TEMP TRIGGER IF NOT EXISTS s.trig INSTEAD OF UPDATE OF a, b ON users FOR EACH ROW WHEN 1 BEGIN SELECT 1; DELETE FROM t; UPDATE t SET a = 1; END;"=vec![CreateTrigger::new(
+            true,
+            true,
+            SchemaTableContainer::SchemaAndTable { schema: "s".into(), table: "trig".into() },
+            Some(TriggerTiming::InsteadOf),
+            TriggerEvent::Update,
+            Some(vec!["a".into(), "b".into()]),
+            "users".into(),
+            true,
+            Some(Expr::literal(Type::Integer(1))),
+            vec![
+                Box::new(super::select_one()),
+                Box::new(Delete::new(SchemaTableContainer::Table("t".into()), None, None)),
+                Box::new(Update::new(
+                    None,
+                    SchemaTableContainer::Table("t".into()),
+                    None,
+                    vec![Assignment::new(vec!["a".into()], Expr::literal(Type::Integer(1)))],
+                    None,
+                    None,
+                    None,
+                )),
+            ],
+        )],
+        after_insert: r"CREATE TRIGGER trig AFTER INSERT ON users BEGIN SELECT 1; END;"=vec![CreateTrigger::new(
+            false,
+            false,
+            SchemaTableContainer::Table("trig".into()),
+            Some(TriggerTiming::After),
+            TriggerEvent::Insert,
+            None,
+            "users".into(),
+            false,
+            None,
+            vec![Box::new(super::select_one())],
+        )]
+    }
+
+    test_group_pass_assert! {
+        create_virtual_table_stmt,
+
+        no_arguments: r"CREATE VIRTUAL TABLE t USING dbstat;"=vec![CreateVirtualTable::new(
+            false,
+            SchemaTableContainer::Table("t".into()),
+            "dbstat".into(),
+            vec![],
+        )],
+        single_argument: r"CREATE VIRTUAL TABLE t USING fts5(content);"=vec![CreateVirtualTable::new(
+            false,
+            SchemaTableContainer::Table("t".into()),
+            "fts5".into(),
+            vec![vec![Token::new(Type::Ident("content".into()))]],
+        )],
+        empty_arguments: r"CREATE VIRTUAL TABLE t USING dbstat();"=vec![CreateVirtualTable::new(
+            false,
+            SchemaTableContainer::Table("t".into()),
+            "dbstat".into(),
+            vec![],
+        )],
+        fts5: r"CREATE VIRTUAL TABLE IF NOT EXISTS s.posts USING fts5(title, body, tokenize = 'porter');"=vec![CreateVirtualTable::new(
+            true,
+            SchemaTableContainer::SchemaAndTable { schema: "s".into(), table: "posts".into() },
+            "fts5".into(),
+            vec![
+                vec![Token::new(Type::Ident("title".into()))],
+                vec![Token::new(Type::Ident("body".into()))],
+                vec![
+                    Token::new(Type::Ident("tokenize".into())),
+                    Token::new(Type::Equal),
+                    Token::new(Type::String("porter".into())),
+                ],
+            ],
+        )],
+        nested_parens: r"CREATE VIRTUAL TABLE t USING m(a(1, 2), b);"=vec![CreateVirtualTable::new(
+            false,
+            SchemaTableContainer::Table("t".into()),
+            "m".into(),
+            vec![
+                vec![
+                    Token::new(Type::Ident("a".into())),
+                    Token::new(Type::BraceLeft),
+                    Token::new(Type::Integer(1)),
+                    Token::new(Type::Comma),
+                    Token::new(Type::Integer(2)),
+                    Token::new(Type::BraceRight),
+                ],
+                vec![Token::new(Type::Ident("b".into()))],
+            ],
+        )]
+    }
+
+    test_group_pass_assert! {
+        create_view_stmt,
+
+        bare: r"CREATE VIEW v AS SELECT 1;"=vec![CreateView::new(
+            false,
+            false,
+            SchemaTableContainer::Table("v".into()),
+            vec![],
+            Box::new(super::select_one()),
+        )],
+        all_clauses: r"CREATE TEMP VIEW IF NOT EXISTS s.v (a, b) AS SELECT 1;"=vec![CreateView::new(
+            true,
+            true,
+            SchemaTableContainer::SchemaAndTable { schema: "s".into(), table: "v".into() },
+            vec!["a".into(), "b".into()],
+            Box::new(super::select_one()),
+        )],
+        values: r"CREATE VIEW v AS VALUES (1);"=vec![CreateView::new(
+            false,
+            false,
+            SchemaTableContainer::Table("v".into()),
+            vec![],
+            Box::new(Values::new(vec![vec![Expr::literal(Type::Integer(1))]], vec![])),
+        )],
+        with: r"CREATE VIEW v AS WITH c AS (SELECT 1) SELECT 1;"=vec![CreateView::new(
+            false,
+            false,
+            SchemaTableContainer::Table("v".into()),
+            vec![],
+            Box::new(WithClause::new(
+                false,
+                vec![CommonTableExpression::new("c".into(), vec![], None, Box::new(super::select_one()))],
+                Box::new(super::select_one()),
+            )),
+        )]
+    }
+
+    test_group_pass_assert! {
+        create_table_stmt,
+
+        bare: r"CREATE TABLE t (a TEXT);"=vec![CreateTable::new(
+            false,
+            false,
+            SchemaTableContainer::Table("t".into()),
+            vec![ColumnDef::new("a".into(), Some(SqliteStorageClass::Text), vec![])],
+            vec![],
+            false,
+            false,
+            None,
+        )],
+        type_names: r"CREATE TABLE t (a integer, b varchar(255));"=vec![CreateTable::new(
+            false,
+            false,
+            SchemaTableContainer::Table("t".into()),
+            vec![
+                ColumnDef::new("a".into(), Some(SqliteStorageClass::Integer), vec![]),
+                ColumnDef::new("b".into(), Some(SqliteStorageClass::Text), vec![]),
+            ],
+            vec![],
+            false,
+            false,
+            None,
+        )],
+        all_clauses: r"CREATE TEMP TABLE IF NOT EXISTS s.t (
+            id INTEGER NOT NULL,
+            b TEXT,
+            CONSTRAINT pk PRIMARY KEY (id, b DESC) ON CONFLICT ROLLBACK,
+            UNIQUE (b),
+            CHECK (id > 0),
+            FOREIGN KEY (b) REFERENCES u (name)
+        ) WITHOUT ROWID, STRICT;"=vec![CreateTable::new(
+            true,
+            true,
+            SchemaTableContainer::SchemaAndTable { schema: "s".into(), table: "t".into() },
+            vec![
+                ColumnDef::new(
+                    "id".into(),
+                    Some(SqliteStorageClass::Integer),
+                    vec![ColumnConstraint::NotNull { on_conflict: None }.into()],
+                ),
+                ColumnDef::new("b".into(), Some(SqliteStorageClass::Text), vec![]),
+            ],
+            vec![
+                NamedTableConstraint {
+                    name: Some("pk".into()),
+                    constraint: TableConstraint::PrimaryKey {
+                        columns: vec![
+                            OrderingTerm::new(Expr::column(None, None, "id"), None, None, None, None),
+                            OrderingTerm::new(Expr::column(None, None, "b"), None, None, Some(Keyword::DESC), None),
+                        ],
+                        on_conflict: Some(Keyword::ROLLBACK),
+                    },
+                },
+                NamedTableConstraint {
+                    name: None,
+                    constraint: TableConstraint::Unique {
+                        columns: vec![OrderingTerm::new(Expr::column(None, None, "b"), None, None, None, None)],
+                        on_conflict: None,
+                    },
+                },
+                NamedTableConstraint {
+                    name: None,
+                    constraint: TableConstraint::Check(Box::new(Expr::binary(
+                        Type::GreaterThan,
+                        Expr::column(None, None, "id"),
+                        Expr::literal(Type::Integer(0)),
+                    ))),
+                },
+                NamedTableConstraint {
+                    name: None,
+                    constraint: TableConstraint::ForeignKey {
+                        columns: vec!["b".into()],
+                        clause: ForeignKeyClause {
+                            foreign_table: "u".into(),
+                            references_columns: vec!["name".into()],
+                            on_delete: None,
+                            on_update: None,
+                            match_type: None,
+                            deferrable: false,
+                            initially_deferred: false,
+                        },
+                    },
+                },
+            ],
+            true,
+            true,
+            None,
+        )],
+        as_select: r"CREATE TABLE t AS SELECT 1;"=vec![CreateTable::new(
+            false,
+            false,
+            SchemaTableContainer::Table("t".into()),
+            vec![],
+            vec![],
+            false,
+            false,
+            Some(Box::new(super::select_one())),
+        )]
+    }
+
+    test_group_pass_assert! {
+        delete_stmt,
+
+        bare: r"DELETE FROM t;"=vec![Delete::new(SchemaTableContainer::Table("t".into()), None, None)],
+        schema: r"DELETE FROM s.t;"=vec![Delete::new(
+            SchemaTableContainer::SchemaAndTable { schema: "s".into(), table: "t".into() },
+            None,
+            None,
+        )],
+        filtered: r"DELETE FROM t WHERE a < 18;"=vec![Delete::new(
+            SchemaTableContainer::Table("t".into()),
+            Some(Expr::binary(Type::LessThan, Expr::column(None, None, "a"), Expr::literal(Type::Integer(18)))),
+            None,
+        )],
+        returning: r"DELETE FROM t RETURNING id;"=vec![Delete::new(
+            SchemaTableContainer::Table("t".into()),
+            None,
+            Some(vec![ResultColumn::Expr { expr: Box::new(Expr::column(None, None, "id")), alias: None }]),
+        )]
+    }
+
+    test_group_pass_assert! {
+        update_stmt,
+
+        bare: r"UPDATE t SET a = 1;"=vec![Update::new(
+            None,
+            SchemaTableContainer::Table("t".into()),
+            None,
+            vec![Assignment::new(vec!["a".into()], Expr::literal(Type::Integer(1)))],
+            None,
+            None,
+            None,
+        )],
+        all_clauses: r"UPDATE OR IGNORE s.t AS u SET (a, b) = x.a, c = 3 FROM x WHERE u.id < 18 RETURNING id;"=vec![Update::new(
+            Some(Keyword::IGNORE),
+            SchemaTableContainer::SchemaAndTable { schema: "s".into(), table: "t".into() },
+            Some("u".into()),
+            vec![
+                Assignment::new(vec!["a".into(), "b".into()], Expr::column(None, Some("x"), "a")),
+                Assignment::new(vec!["c".into()], Expr::literal(Type::Integer(3))),
+            ],
+            Some(JoinClause::new(super::table("x"), vec![])),
+            Some(Expr::binary(Type::LessThan, Expr::column(None, Some("u"), "id"), Expr::literal(Type::Integer(18)))),
+            Some(vec![ResultColumn::Expr { expr: Box::new(Expr::column(None, None, "id")), alias: None }]),
+        )]
+    }
+
+    test_group_pass_assert! {
+        insert_stmt,
+
+        values: r"INSERT INTO t (a, b) VALUES (1, 2), (3, 4);"=vec![Insert::new(
+            None,
+            SchemaTableContainer::Table("t".into()),
+            None,
+            Some(vec!["a".into(), "b".into()]),
+            InsertSource::Values(vec![
+                vec![Expr::literal(Type::Integer(1)), Expr::literal(Type::Integer(2))],
+                vec![Expr::literal(Type::Integer(3)), Expr::literal(Type::Integer(4))],
+            ]),
+            None,
+            None,
+        )],
+        differing_arities_are_kept: r"INSERT INTO t (a) VALUES (1), (2, 3);"=vec![Insert::new(
+            None,
+            SchemaTableContainer::Table("t".into()),
+            None,
+            Some(vec!["a".into()]),
+            InsertSource::Values(vec![
+                vec![Expr::literal(Type::Integer(1))],
+                vec![Expr::literal(Type::Integer(2)), Expr::literal(Type::Integer(3))],
+            ]),
+            None,
+            None,
+        )],
+        or_ignore_schema_alias: r"INSERT OR IGNORE INTO s.t AS u (a) VALUES (1);"=vec![Insert::new(
+            Some(Keyword::IGNORE),
+            SchemaTableContainer::SchemaAndTable { schema: "s".into(), table: "t".into() },
+            Some("u".into()),
+            Some(vec!["a".into()]),
+            InsertSource::Values(vec![vec![Expr::literal(Type::Integer(1))]]),
+            None,
+            None,
+        )],
+        replace: r"REPLACE INTO t (a) VALUES (1);"=vec![Insert::new(
+            Some(Keyword::REPLACE),
+            SchemaTableContainer::Table("t".into()),
+            None,
+            Some(vec!["a".into()]),
+            InsertSource::Values(vec![vec![Expr::literal(Type::Integer(1))]]),
+            None,
+            None,
+        )],
+        select: r"INSERT OR ROLLBACK INTO t (a) SELECT 1;"=vec![Insert::new(
+            Some(Keyword::ROLLBACK),
+            SchemaTableContainer::Table("t".into()),
+            None,
+            Some(vec!["a".into()]),
+            InsertSource::Select(Box::new(Select::new(
+                false,
+                vec![ResultColumn::Expr { expr: Box::new(Expr::literal(Type::Integer(1))), alias: None }],
+                None, None, vec![], None, vec![], vec![], vec![], None, None,
+            ))),
+            None,
+            None,
+        )],
+        default_values: r"INSERT INTO t DEFAULT VALUES;"=vec![Insert::new(
+            None,
+            SchemaTableContainer::Table("t".into()),
+            None,
+            None,
+            InsertSource::DefaultValues,
+            None,
+            None,
+        )],
+        returning: r"INSERT INTO t DEFAULT VALUES RETURNING *, a + 1 AS b;"=vec![Insert::new(
+            None,
+            SchemaTableContainer::Table("t".into()),
+            None,
+            None,
+            InsertSource::DefaultValues,
+            None,
+            Some(vec![
+                ResultColumn::Star,
+                ResultColumn::Expr {
+                    expr: Box::new(Expr::binary(Type::Plus, Expr::column(None, None, "a"), Expr::literal(Type::Integer(1)))),
+                    alias: Some("b".into()),
+                },
+            ]),
+        )],
+        upsert_nothing: r"INSERT INTO t (a) VALUES (1) ON CONFLICT DO NOTHING;"=vec![Insert::new(
+            None,
+            SchemaTableContainer::Table("t".into()),
+            None,
+            Some(vec!["a".into()]),
+            InsertSource::Values(vec![vec![Expr::literal(Type::Integer(1))]]),
+            Some(Upsert::new(vec![], None, UpsertAction::Nothing)),
+            None,
+        )],
+        upsert_update: r"INSERT INTO t (a, b) VALUES (1, 2) ON CONFLICT (a) WHERE b > 0 DO UPDATE SET b = excluded.b, c = 3 WHERE b < 5 RETURNING *;"=vec![Insert::new(
+            None,
+            SchemaTableContainer::Table("t".into()),
+            None,
+            Some(vec!["a".into(), "b".into()]),
+            InsertSource::Values(vec![vec![Expr::literal(Type::Integer(1)), Expr::literal(Type::Integer(2))]]),
+            Some(Upsert::new(
+                vec![OrderingTerm::new(Expr::column(None, None, "a"), None, None, None, None)],
+                Some(Expr::binary(Type::GreaterThan, Expr::column(None, None, "b"), Expr::literal(Type::Integer(0)))),
+                UpsertAction::Update {
+                    set: vec![
+                        Assignment::new(vec!["b".into()], Expr::column(None, Some("excluded"), "b")),
+                        Assignment::new(vec!["c".into()], Expr::literal(Type::Integer(3))),
+                    ],
+                    where_clause: Some(Box::new(Expr::binary(Type::LessThan, Expr::column(None, None, "b"), Expr::literal(Type::Integer(5))))),
+                },
+            )),
+            Some(vec![ResultColumn::Star]),
+        )],
+        upsert_multiple_columns: r"INSERT INTO t (a, b) VALUES (1, 2) ON CONFLICT (a, b) DO UPDATE SET (a, b) = excluded.a;"=vec![Insert::new(
+            None,
+            SchemaTableContainer::Table("t".into()),
+            None,
+            Some(vec!["a".into(), "b".into()]),
+            InsertSource::Values(vec![vec![Expr::literal(Type::Integer(1)), Expr::literal(Type::Integer(2))]]),
+            Some(Upsert::new(
+                vec![
+                    OrderingTerm::new(Expr::column(None, None, "a"), None, None, None, None),
+                    OrderingTerm::new(Expr::column(None, None, "b"), None, None, None, None),
+                ],
+                None,
+                UpsertAction::Update {
+                    set: vec![Assignment::new(vec!["a".into(), "b".into()], Expr::column(None, Some("excluded"), "a"))],
+                    where_clause: None,
+                },
+            )),
+            None,
+        )]
+    }
+
+    // expressions are tested via ATTACH, since its the smallest statement accepting an expr
+    test_group_pass_assert! {
+        expr_binary,
+
+        addition: r"ATTACH 1 + 2 AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(Type::Plus, Expr::literal(Type::Integer(1)), Expr::literal(Type::Integer(2))),
+        )],
+        left_associative: r"ATTACH 1 - 2 - 3 AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::Minus,
+                Expr::binary(Type::Minus, Expr::literal(Type::Integer(1)), Expr::literal(Type::Integer(2))),
+                Expr::literal(Type::Integer(3)),
+            ),
+        )],
+        bitwise_binds_looser_than_addition: r"ATTACH 1 | 2 + 3 AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::Pipe,
+                Expr::literal(Type::Integer(1)),
+                Expr::binary(Type::Plus, Expr::literal(Type::Integer(2)), Expr::literal(Type::Integer(3))),
+            ),
+        )],
+        bitwise_left_associative: r"ATTACH 1 << 2 & 3 >> 4 AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::ShiftRight,
+                Expr::binary(
+                    Type::Ampersand,
+                    Expr::binary(Type::ShiftLeft, Expr::literal(Type::Integer(1)), Expr::literal(Type::Integer(2))),
+                    Expr::literal(Type::Integer(3)),
+                ),
+                Expr::literal(Type::Integer(4)),
+            ),
+        )],
+        bitwise_binds_tighter_than_comparison: r"ATTACH a & 1 = 1 AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::Equal,
+                Expr::binary(Type::Ampersand, Expr::column(None, None, "a"), Expr::literal(Type::Integer(1))),
+                Expr::literal(Type::Integer(1)),
+            ),
+        )],
+        json_arrow: r"ATTACH data -> '$.name' AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(Type::Arrow, Expr::column(None, None, "data"), Expr::literal(Type::String("$.name".into()))),
+        )],
+        json_double_arrow: r"ATTACH data ->> '$.age' AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(Type::DoubleArrow, Expr::column(None, None, "data"), Expr::literal(Type::String("$.age".into()))),
+        )],
+        json_arrow_binds_tighter_than_comparison: r"ATTACH data ->> '$.age' > 18 AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::GreaterThan,
+                Expr::binary(Type::DoubleArrow, Expr::column(None, None, "data"), Expr::literal(Type::String("$.age".into()))),
+                Expr::literal(Type::Integer(18)),
+            ),
+        )],
+        json_arrow_chain: r"ATTACH data -> 'a' ->> 'b' AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::DoubleArrow,
+                Expr::binary(Type::Arrow, Expr::column(None, None, "data"), Expr::literal(Type::String("a".into()))),
+                Expr::literal(Type::String("b".into())),
+            ),
+        )],
+        multiplication_binds_tighter: r"ATTACH 1 + 2 * 3 AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::Plus,
+                Expr::literal(Type::Integer(1)),
+                Expr::binary(Type::Asterisk, Expr::literal(Type::Integer(2)), Expr::literal(Type::Integer(3))),
+            ),
+        )],
+        concat_binds_tighter_than_division: r"ATTACH 'a' || 'b' / 2 AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::Slash,
+                Expr::binary(Type::Concat, Expr::literal(Type::String("a".into())), Expr::literal(Type::String("b".into()))),
+                Expr::literal(Type::Integer(2)),
+            ),
+        )],
+        comparison_and_or: r"ATTACH 1 < 2 OR 3 >= 4 AND 5 != 6 AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::Keyword(Keyword::OR),
+                Expr::binary(Type::LessThan, Expr::literal(Type::Integer(1)), Expr::literal(Type::Integer(2))),
+                Expr::binary(
+                    Type::Keyword(Keyword::AND),
+                    Expr::binary(Type::GreaterEq, Expr::literal(Type::Integer(3)), Expr::literal(Type::Integer(4))),
+                    Expr::binary(Type::NotEqual, Expr::literal(Type::Integer(5)), Expr::literal(Type::Integer(6))),
+                ),
+            ),
+        )],
+        equality_binds_looser_than_relational: r"ATTACH 1 == 2 <> 3 < 4 AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::NotEqual,
+                Expr::binary(Type::DoubleEqual, Expr::literal(Type::Integer(1)), Expr::literal(Type::Integer(2))),
+                Expr::binary(Type::LessThan, Expr::literal(Type::Integer(3)), Expr::literal(Type::Integer(4))),
+            ),
+        )]
+    }
+
+    test_group_pass_assert! {
+        expr_column,
+
+        column: r"ATTACH c AS db;"=vec![Attach::new("db".into(), Expr::column(None, None, "c"))],
+        table_column: r"ATTACH t.c AS db;"=vec![Attach::new("db".into(), Expr::column(None, Some("t"), "c"))],
+        schema_table_column: r"ATTACH s.t.c AS db;"=vec![Attach::new("db".into(), Expr::column(Some("s"), Some("t"), "c"))],
+        column_in_binary: r"ATTACH t.a + b AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(Type::Plus, Expr::column(None, Some("t"), "a"), Expr::column(None, None, "b")),
+        )],
+        sum_distinct_column: r"ATTACH SUM(DISTINCT x) AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(FunctionCall::new(None, "SUM".into(), true, vec![Expr::column(None, None, "x")], false, None, None)),
+        )],
+        coalesce_columns: r"ATTACH COALESCE(a, b, c) AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(FunctionCall::new(
+                None,
+                "COALESCE".into(),
+                false,
+                vec![Expr::column(None, None, "a"), Expr::column(None, None, "b"), Expr::column(None, None, "c")],
+                false,
+                None,
+                None,
+            )),
+        )]
+    }
+
+    test_group_pass_assert! {
+        expr_is,
+
+        is: r"ALTER TABLE t ADD COLUMN c TEXT CHECK (1 IS 2);"=super::alter_check(Expr::compound(IsExpr::new(
+            false,
+            false,
+            Box::new(Expr::literal(Type::Integer(1))),
+            Box::new(Expr::literal(Type::Integer(2))),
+        ))),
+        is_null: r"ALTER TABLE t ADD COLUMN c TEXT CHECK (1 IS NULL);"=super::alter_check(super::is_null(Type::Integer(1), false)),
+        isnull: r"ALTER TABLE t ADD COLUMN c TEXT CHECK (1 ISNULL);"=super::alter_check(super::is_null(Type::Integer(1), false)),
+        is_not_null: r"ALTER TABLE t ADD COLUMN c TEXT CHECK (1 IS NOT NULL);"=super::alter_check(super::is_null(Type::Integer(1), true)),
+        notnull: r"ALTER TABLE t ADD COLUMN c TEXT CHECK (1 NOTNULL);"=super::alter_check(super::is_null(Type::Integer(1), true)),
+        not_null: r"ALTER TABLE t ADD COLUMN c TEXT CHECK (1 NOT NULL);"=super::alter_check(super::is_null(Type::Integer(1), true)),
+        is_binds_looser_than_addition: r"ALTER TABLE t ADD COLUMN c TEXT CHECK (1 + 2 IS NOT 3 AND 4 NOTNULL);"=super::alter_check(Expr::binary(
+            Type::Keyword(Keyword::AND),
+            Expr::compound(IsExpr::new(
+                true,
+                false,
+                Box::new(Expr::binary(Type::Plus, Expr::literal(Type::Integer(1)), Expr::literal(Type::Integer(2)))),
+                Box::new(Expr::literal(Type::Integer(3))),
+            )),
+            super::is_null(Type::Integer(4), true),
+        )),
+        is_distinct_from: r"ALTER TABLE t ADD COLUMN c TEXT CHECK (1 IS DISTINCT FROM 2);"=super::alter_check(Expr::compound(IsExpr::new(
+            false,
+            true,
+            Box::new(Expr::literal(Type::Integer(1))),
+            Box::new(Expr::literal(Type::Integer(2))),
+        ))),
+        is_not_distinct_from: r"ALTER TABLE t ADD COLUMN c TEXT CHECK (1 IS NOT DISTINCT FROM NULL AND 1);"=super::alter_check(Expr::binary(
+            Type::Keyword(Keyword::AND),
+            Expr::compound(IsExpr::new(
+                true,
+                true,
+                Box::new(Expr::literal(Type::Integer(1))),
+                Box::new(Expr::literal(Type::Keyword(Keyword::NULL))),
+            )),
+            Expr::literal(Type::Integer(1)),
+        ))
+    }
+
+    test_group_pass_assert! {
+        expr_case,
+
+        simple: r"ATTACH CASE a WHEN 1 THEN 'one' WHEN 2 THEN 'two' END AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(CaseExpr::new(
+                Some(Expr::column(None, None, "a")),
+                vec![
+                    (Expr::literal(Type::Integer(1)), Expr::literal(Type::String("one".into()))),
+                    (Expr::literal(Type::Integer(2)), Expr::literal(Type::String("two".into()))),
+                ],
+                None,
+            )),
+        )],
+        simple_else: r"ATTACH CASE a WHEN 1 THEN 'one' ELSE 'many' END AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(CaseExpr::new(
+                Some(Expr::column(None, None, "a")),
+                vec![(Expr::literal(Type::Integer(1)), Expr::literal(Type::String("one".into())))],
+                Some(Expr::literal(Type::String("many".into()))),
+            )),
+        )],
+        searched: r"ATTACH CASE WHEN a > 0 THEN 1 END AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(CaseExpr::new(
+                None,
+                vec![(
+                    Expr::binary(Type::GreaterThan, Expr::column(None, None, "a"), Expr::literal(Type::Integer(0))),
+                    Expr::literal(Type::Integer(1)),
+                )],
+                None,
+            )),
+        )],
+        searched_else: r"ATTACH CASE WHEN a THEN 1 ELSE 0 END + 1 AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::Plus,
+                Expr::compound(CaseExpr::new(
+                    None,
+                    vec![(Expr::column(None, None, "a"), Expr::literal(Type::Integer(1)))],
+                    Some(Expr::literal(Type::Integer(0))),
+                )),
+                Expr::literal(Type::Integer(1)),
+            ),
+        )]
+    }
+
+    test_group_pass_assert! {
+        expr_cast,
+
+        text: r"ATTACH CAST(1 AS TEXT) AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(CastExpr::new(Box::new(Expr::literal(Type::Integer(1))), SqliteStorageClass::Text)),
+        )],
+        integer: r"ATTACH CAST('123' AS INTEGER) AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(CastExpr::new(Box::new(Expr::literal(Type::String("123".into()))), SqliteStorageClass::Integer)),
+        )],
+        type_name_with_size: r"ATTACH CAST(a AS VARCHAR(255)) AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(CastExpr::new(Box::new(Expr::column(None, None, "a")), SqliteStorageClass::Text)),
+        )],
+        in_binary: r"ATTACH CAST(1 AS REAL) + 2 AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::Plus,
+                Expr::compound(CastExpr::new(Box::new(Expr::literal(Type::Integer(1))), SqliteStorageClass::Real)),
+                Expr::literal(Type::Integer(2)),
+            ),
+        )]
+    }
+
+    test_group_pass_assert! {
+        expr_function_call,
+
+        count_star: r"ATTACH COUNT(*) AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(FunctionCall::new(None, "COUNT".into(), false, vec![], true, None, None)),
+        )],
+        no_arguments: r"ATTACH random() AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(FunctionCall::new(None, "random".into(), false, vec![], false, None, None)),
+        )],
+        schema_qualified: r"ATTACH ext.my_func(1) AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(FunctionCall::new(
+                Some("ext".into()),
+                "my_func".into(),
+                false,
+                vec![Expr::literal(Type::Integer(1))],
+                false,
+                None,
+                None,
+            )),
+        )],
+        sum_distinct: r"ATTACH SUM(DISTINCT 1) AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(FunctionCall::new(
+                None,
+                "SUM".into(),
+                true,
+                vec![Expr::literal(Type::Integer(1))],
+                false,
+                None,
+                None,
+            )),
+        )],
+        count_distinct: r"ATTACH count(DISTINCT x) AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(FunctionCall::new(
+                None,
+                "count".into(),
+                true,
+                vec![Expr::column(None, None, "x")],
+                false,
+                None,
+                None,
+            )),
+        )],
+        count_all: r"ATTACH count(ALL x) AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(FunctionCall::new(
+                None,
+                "count".into(),
+                false,
+                vec![Expr::column(None, None, "x")],
+                false,
+                None,
+                None,
+            )),
+        )],
+        coalesce: r"ATTACH COALESCE(NULL, 1, 'a') AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(FunctionCall::new(
+                None,
+                "COALESCE".into(),
+                false,
+                vec![
+                    Expr::literal(Type::Keyword(Keyword::NULL)),
+                    Expr::literal(Type::Integer(1)),
+                    Expr::literal(Type::String("a".into())),
+                ],
+                false,
+                None,
+                None,
+            )),
+        )],
+        nested_in_binary: r"ATTACH length('a') + 1 AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::Plus,
+                Expr::compound(FunctionCall::new(
+                    None,
+                    "length".into(),
+                    false,
+                    vec![Expr::literal(Type::String("a".into()))],
+                    false,
+                    None,
+                    None,
+                )),
+                Expr::literal(Type::Integer(1)),
+            ),
+        )],
+        filter_and_over_name: r"ATTACH COUNT(*) FILTER (WHERE 1) OVER win AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(FunctionCall::new(
+                None,
+                "COUNT".into(),
+                false,
+                vec![],
+                true,
+                Some(Box::new(Expr::literal(Type::Integer(1)))),
+                Some(Box::new(WindowDefn::new(Some("win".into()), vec![], vec![], None))),
+            )),
+        )],
+        over_window_defn: r"ATTACH SUM(1) OVER (base PARTITION BY 1 ORDER BY 2 DESC NULLS LAST ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW EXCLUDE TIES) AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(FunctionCall::new(
+                None,
+                "SUM".into(),
+                false,
+                vec![Expr::literal(Type::Integer(1))],
+                false,
+                None,
+                Some(Box::new(WindowDefn::new(
+                    Some("base".into()),
+                    vec![Expr::literal(Type::Integer(1))],
+                    vec![OrderingTerm::new(
+                        Expr::literal(Type::Integer(2)),
+                        None,
+                        None,
+                        Some(Keyword::DESC),
+                        Some(Keyword::LAST),
+                    )],
+                    Some(FrameSpec::new(
+                        Keyword::ROWS,
+                        FrameBound::UnboundedPreceding,
+                        Some(FrameBound::CurrentRow),
+                        Some(Keyword::TIES),
+                    )),
+                ))),
+            )),
+        )],
+        over_empty_window_defn: r"ATTACH SUM(1) OVER () AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(FunctionCall::new(
+                None,
+                "SUM".into(),
+                false,
+                vec![Expr::literal(Type::Integer(1))],
+                false,
+                None,
+                Some(Box::new(WindowDefn::new(None, vec![], vec![], None))),
+            )),
+        )]
+    }
+
+    test_group_pass_assert! {
+        expr_between,
+
+        column: r"ATTACH x BETWEEN 1 AND 10 AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(BetweenExpr::new(
+                false,
+                Box::new(Expr::column(None, None, "x")),
+                Box::new(Expr::literal(Type::Integer(1))),
+                Box::new(Expr::literal(Type::Integer(10))),
+            )),
+        )],
+        not_strings: r"ATTACH y NOT BETWEEN 'a' AND 'z' AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(BetweenExpr::new(
+                true,
+                Box::new(Expr::column(None, None, "y")),
+                Box::new(Expr::literal(Type::String("a".into()))),
+                Box::new(Expr::literal(Type::String("z".into()))),
+            )),
+        )],
+        arithmetic_bounds: r"ATTACH x BETWEEN 1 + 1 AND 10 * 2 AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(BetweenExpr::new(
+                false,
+                Box::new(Expr::column(None, None, "x")),
+                Box::new(Expr::binary(Type::Plus, Expr::literal(Type::Integer(1)), Expr::literal(Type::Integer(1)))),
+                Box::new(Expr::binary(Type::Asterisk, Expr::literal(Type::Integer(10)), Expr::literal(Type::Integer(2)))),
+            )),
+        )],
+        surrounding_and: r"ATTACH a AND x BETWEEN 1 AND 10 AND b AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::Keyword(Keyword::AND),
+                Expr::binary(
+                    Type::Keyword(Keyword::AND),
+                    Expr::column(None, None, "a"),
+                    Expr::compound(BetweenExpr::new(
+                        false,
+                        Box::new(Expr::column(None, None, "x")),
+                        Box::new(Expr::literal(Type::Integer(1))),
+                        Box::new(Expr::literal(Type::Integer(10))),
+                    )),
+                ),
+                Expr::column(None, None, "b"),
+            ),
+        )]
+    }
+
+    test_group_pass_assert! {
+        expr_keyword_operators,
+
+        between: r"ATTACH 5 BETWEEN 1 AND 10 AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(BetweenExpr::new(
+                false,
+                Box::new(Expr::literal(Type::Integer(5))),
+                Box::new(Expr::literal(Type::Integer(1))),
+                Box::new(Expr::literal(Type::Integer(10))),
+            )),
+        )],
+        between_in_and_chain: r"ATTACH 5 NOT BETWEEN 1 AND 10 AND 1 AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::Keyword(Keyword::AND),
+                Expr::compound(BetweenExpr::new(
+                    true,
+                    Box::new(Expr::literal(Type::Integer(5))),
+                    Box::new(Expr::literal(Type::Integer(1))),
+                    Box::new(Expr::literal(Type::Integer(10))),
+                )),
+                Expr::literal(Type::Integer(1)),
+            ),
+        )],
+        in_list: r"ATTACH 1 IN (1, 2) AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(InExpr::new(
+                false,
+                Box::new(Expr::literal(Type::Integer(1))),
+                InBody::List(vec![Expr::literal(Type::Integer(1)), Expr::literal(Type::Integer(2))]),
+            )),
+        )],
+        not_in_empty_list: r"ATTACH 1 NOT IN () AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(InExpr::new(
+                true,
+                Box::new(Expr::literal(Type::Integer(1))),
+                InBody::List(vec![]),
+            )),
+        )],
+        in_table: r"ATTACH 1 IN schema.table_name AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(InExpr::new(
+                false,
+                Box::new(Expr::literal(Type::Integer(1))),
+                InBody::Table(SchemaTableContainer::SchemaAndTable { schema: "schema".into(), table: "table_name".into() }),
+            )),
+        )],
+        in_subquery: r"ATTACH a IN (SELECT b FROM t) AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(InExpr::new(
+                false,
+                Box::new(Expr::column(None, None, "a")),
+                InBody::Subquery(Box::new(Select::new(
+                    false,
+                    vec![ResultColumn::Expr { expr: Box::new(Expr::column(None, None, "b")), alias: None }],
+                    Some(JoinClause::new(
+                        TableOrSubquery::Table { name: SchemaTableContainer::Table("t".into()), alias: None },
+                        vec![],
+                    )),
+                    None, vec![], None, vec![], vec![], vec![], None, None,
+                ))),
+            )),
+        )],
+        not_in_subquery_where: r"SELECT * FROM t WHERE a NOT IN (SELECT 1);"=vec![Select::new(
+            false,
+            vec![ResultColumn::Star],
+            Some(JoinClause::new(
+                TableOrSubquery::Table { name: SchemaTableContainer::Table("t".into()), alias: None },
+                vec![],
+            )),
+            Some(Expr::compound(InExpr::new(
+                true,
+                Box::new(Expr::column(None, None, "a")),
+                InBody::Subquery(Box::new(Select::new(
+                    false,
+                    vec![ResultColumn::Expr { expr: Box::new(Expr::literal(Type::Integer(1))), alias: None }],
+                    None, None, vec![], None, vec![], vec![], vec![], None, None,
+                ))),
+            ))),
+            vec![], None, vec![], vec![], vec![], None, None,
+        )],
+        in_plain_table: r"ATTACH 1 NOT IN t AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(InExpr::new(
+                true,
+                Box::new(Expr::literal(Type::Integer(1))),
+                InBody::Table(SchemaTableContainer::Table("t".into())),
+            )),
+        )],
+        like: r"ATTACH 'abc' LIKE 'a%' AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(LikeExpr::new(
+                false,
+                Keyword::LIKE,
+                Box::new(Expr::literal(Type::String("abc".into()))),
+                Box::new(Expr::literal(Type::String("a%".into()))),
+                None,
+            )),
+        )],
+        not_like_escape: r"ATTACH 'abc' NOT LIKE 'a!%' ESCAPE '!' AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(LikeExpr::new(
+                true,
+                Keyword::LIKE,
+                Box::new(Expr::literal(Type::String("abc".into()))),
+                Box::new(Expr::literal(Type::String("a!%".into()))),
+                Some(Box::new(Expr::literal(Type::String("!".into())))),
+            )),
+        )],
+        glob: r"ATTACH 'a.sql' GLOB '*.sql' AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::compound(LikeExpr::new(
+                false,
+                Keyword::GLOB,
+                Box::new(Expr::literal(Type::String("a.sql".into()))),
+                Box::new(Expr::literal(Type::String("*.sql".into()))),
+                None,
+            )),
+        )],
+        glob_and_match: r"ATTACH 'a' NOT GLOB 'b' AND 'c' NOT MATCH 'd' AS db;"=vec![Attach::new(
+            "db".into(),
+            Expr::binary(
+                Type::Keyword(Keyword::AND),
+                Expr::compound(LikeExpr::new(
+                    true,
+                    Keyword::GLOB,
+                    Box::new(Expr::literal(Type::String("a".into()))),
+                    Box::new(Expr::literal(Type::String("b".into()))),
+                    None,
+                )),
+                Expr::compound(LikeExpr::new(
+                    true,
+                    Keyword::MATCH,
+                    Box::new(Expr::literal(Type::String("c".into()))),
+                    Box::new(Expr::literal(Type::String("d".into()))),
+                    None,
+                )),
+            ),
+        )]
+    }
+}
+
+#[allow(unused_macros)]
+macro_rules! test_group_fail {
+    ($group_name:ident,$($ident:ident:$input:literal),*) => {
+    mod $group_name {
+        use crate::{lexer, parser::Parser};
+
+        $(
+            #[test]
+            fn $ident() {
+                let input = $input.as_bytes().to_vec();
+                let mut l = lexer::Lexer::new(&input, "parser_test_fail");
+                let toks = l.run();
+                assert_eq!(l.errors.len(), 0);
+
+                let mut parser = Parser::new(toks, "parser_test_fail");
+                let _ = parser.parse();
+                assert_ne!(parser.errors.len(), 0);
+            }
+        )*
+        }
+    };
+}
+
+#[cfg(test)]
+mod should_fail {
+    test_group_fail! {
+        negative_tests,
+        eof_semi: ";",
+        eof_literal: "'str'",
+        alter_no_table: "ALTER;",
+        alter_no_name: "ALTER TABLE;",
+        commit_no_semicolon: "COMMIT",
+        end_no_semicolon: "END",
+        rollback_no_semicolon: "ROLLBACK",
+        rollback_to_savepoint_no_name: "ROLLBACK TO SAVEPOINT",
+        begin_no_semicolon: "BEGIN",
+        begin_invalid_modifiers: "BEGIN DEFERRED IMMEDIATE EXCLUSIVE EXCLUSIVE;",
+        detach_no_name: "DETACH;",
+        detach_invalid_literal: "DETACH 'bad';",
+        drop_no_object: "DROP TABLE;",
+        drop_invalid_object: "DROP INDEX 5;",
+        savepoint_no_name: "SAVEPOINT;",
+        release_no_name: "RELEASE;",
+        reindex_no_name: "REINDEX",
+        reindex_invalid_literal: "REINDEX 25;",
+        vacuum_no_semicolon: "VACUUM",
+        vacuum_invalid_combined: "VACUUM 5 INTO 5;"
+    }
+
+    test_group_fail! {
+        select_stmt,
+        no_columns: "SELECT;",
+        no_from_table: "SELECT * FROM;",
+        no_where_expr: "SELECT * FROM t WHERE;",
+        no_alias: "SELECT a AS FROM t;",
+        unterminated_subquery: "SELECT * FROM (SELECT 1;",
+        order_without_by: "SELECT * FROM t ORDER a;",
+        trailing_comma: "SELECT a, FROM t;",
+        no_semicolon: "SELECT 1",
+        standalone_nulls: "SELECT * FROM t NULLS FIRST;",
+        nulls_after_limit: "SELECT * FROM t ORDER BY a LIMIT 1 NULLS LAST;",
+        nulls_without_first_last: "SELECT * FROM t ORDER BY a NULLS;",
+        limit_no_expr: "SELECT * FROM t LIMIT;",
+        limit_comma_no_expr: "SELECT * FROM t LIMIT 1,;",
+        offset_no_expr: "SELECT * FROM t LIMIT 1 OFFSET;",
+        group_without_by: "SELECT a FROM t GROUP a;",
+        empty_group_by: "SELECT a FROM t GROUP BY;",
+        empty_group_by_before_having: "SELECT a FROM t GROUP BY HAVING a > 1;",
+        group_by_trailing_comma: "SELECT a FROM t GROUP BY a,;",
+        having_no_expr: "SELECT a FROM t GROUP BY a HAVING;",
+        window_without_name: "SELECT a FROM t WINDOW AS (ORDER BY a);",
+        window_without_as: "SELECT a FROM t WINDOW win (ORDER BY a);",
+        window_trailing_comma: "SELECT a FROM t WINDOW win AS (ORDER BY a),;"
+    }
+
+    test_group_fail! {
+        with_clause,
+        empty_column_list: "WITH c() AS (SELECT 1) SELECT * FROM c;",
+        no_as: "WITH c (SELECT 1) SELECT * FROM c;",
+        materialized_no_paren: "WITH c AS MATERIALIZED SELECT 1 SELECT * FROM c;",
+        body_not_select: "WITH c AS (VACUUM) SELECT * FROM c;",
+        no_statement: "WITH c AS (SELECT 1);",
+        unsupported_statement: "WITH c AS (SELECT 1) VACUUM;",
+        no_cte: "WITH SELECT 1;",
+        recursive_no_cte: "WITH RECURSIVE SELECT 1;",
+        trailing_comma: "WITH a AS (SELECT 1), SELECT * FROM a;",
+        unterminated_body: "WITH c AS (SELECT 1 SELECT * FROM c;"
+    }
+
+    test_group_fail! {
+        compound_select,
+        no_select: "SELECT 1 UNION;",
+        union_all_no_select: "SELECT 1 UNION ALL;"
+    }
+
+    test_group_fail! {
+        expr_unary,
+        minus_no_operand: "ATTACH - AS db;",
+        not_no_operand: "ATTACH NOT AS db;"
+    }
+
+    test_group_fail! {
+        expr_paren,
+        unterminated: "ATTACH (1 + 2 AS db;",
+        empty: "ATTACH () AS db;",
+        unterminated_subquery: "ATTACH (SELECT 1 AS db;"
+    }
+
+    test_group_fail! {
+        expr_exists,
+        no_paren: "ATTACH EXISTS SELECT 1 AS db;",
+        no_select: "ATTACH EXISTS (1) AS db;",
+        unterminated: "ATTACH NOT EXISTS (SELECT 1 AS db;"
+    }
+
+    test_group_fail! {
+        values_stmt,
+        no_rows: "VALUES;",
+        trailing_comma: "VALUES (1), (2),;",
+        empty_row: "VALUES ();",
+        no_semicolon: "VALUES (1)"
+    }
+
+    test_group_fail! {
+        column_constraint_named,
+        no_name: "ALTER TABLE t ADD COLUMN c INT CONSTRAINT PRIMARY KEY;",
+        no_constraint: "ALTER TABLE t ADD COLUMN c INT CONSTRAINT pk;"
+    }
+
+    test_group_fail! {
+        foreign_key_clause,
+        bad_match_kind: "ALTER TABLE t ADD COLUMN c INT REFERENCES f MATCH BOGUS;",
+        match_no_kind: "ALTER TABLE t ADD COLUMN c INT REFERENCES f MATCH;",
+        bad_on_action: "ALTER TABLE t ADD COLUMN c INT REFERENCES f ON DELETE BOGUS;"
+    }
+
+    test_group_fail! {
+        delete_stmt,
+        no_from: "DELETE t;",
+        no_table: "DELETE FROM;",
+        no_where_expr: "DELETE FROM t WHERE;",
+        no_semicolon: "DELETE FROM t",
+        returning_no_columns: "DELETE FROM t RETURNING;"
+    }
+
+    test_group_fail! {
+        update_stmt,
+        no_table: "UPDATE SET a = 1;",
+        no_set: "UPDATE t WHERE a = 1;",
+        bad_or: "UPDATE OR BOGUS t SET a = 1;",
+        no_where_expr: "UPDATE t SET a = 1 WHERE;",
+        no_semicolon: "UPDATE t SET a = 1"
+    }
+
+    test_group_fail! {
+        upsert,
+        no_conflict: "INSERT INTO t (a) VALUES (1) ON DO NOTHING;",
+        no_do: "INSERT INTO t (a) VALUES (1) ON CONFLICT NOTHING;",
+        no_action: "INSERT INTO t (a) VALUES (1) ON CONFLICT DO;",
+        update_without_set: "INSERT INTO t (a) VALUES (1) ON CONFLICT DO UPDATE a = 1;",
+        update_no_assignment: "INSERT INTO t (a) VALUES (1) ON CONFLICT DO UPDATE SET;",
+        assignment_no_equal: "INSERT INTO t (a) VALUES (1) ON CONFLICT DO UPDATE SET a 1;",
+        unterminated_target: "INSERT INTO t (a) VALUES (1) ON CONFLICT (a DO NOTHING;"
+    }
+
+    test_group_fail! {
+        insert_stmt,
+        no_into: "INSERT t (a) VALUES (1);",
+        no_table: "INSERT INTO (a) VALUES (1);",
+        bad_or: "INSERT OR UPDATE INTO t (a) VALUES (1);",
+        no_source: "INSERT INTO t (a);",
+        empty_columns: "INSERT INTO t () VALUES (1);",
+        empty_row: "INSERT INTO t (a) VALUES ();",
+        trailing_row_comma: "INSERT INTO t (a) VALUES (1),;",
+        default_without_values: "INSERT INTO t DEFAULT;",
+        no_semicolon: "INSERT INTO t (a) VALUES (1)"
+    }
+
+    test_group_fail! {
+        join_clause,
+        no_table: "SELECT * FROM a JOIN;",
+        no_table_after_comma: "SELECT * FROM a, ;",
+        no_join_keyword: "SELECT * FROM a LEFT b;",
+        natural_without_join: "SELECT * FROM a NATURAL b;",
+        empty_using: "SELECT * FROM a JOIN b USING ();",
+        unterminated_using: "SELECT * FROM a JOIN b USING (id;",
+        no_on_expr: "SELECT * FROM a JOIN b ON;",
+        natural_using: "SELECT * FROM a NATURAL JOIN b USING (id);",
+        natural_on: "SELECT * FROM a NATURAL LEFT JOIN b ON a.id = b.id;"
+    }
+
+    test_group_fail! {
+        expr_binary,
+        missing_rhs: "ATTACH 1 + AS db;",
+        missing_lhs: "ATTACH * 2 AS db;",
+        dangling_operator: "ATTACH 1 < AS db;"
+    }
+
+    test_group_fail! {
+        expr_function_call,
+        missing_closing_paren: "ATTACH COUNT(* AS db;",
+        trailing_comma: "ATTACH COALESCE(1, ) AS db;",
+        filter_without_where: "ATTACH COUNT(*) FILTER (1) AS db;",
+        over_without_window: "ATTACH COUNT(*) OVER AS db;",
+        frame_bound_without_direction: "ATTACH SUM(1) OVER (ROWS 5) AS db;",
+        nulls_without_first_or_last: "ATTACH SUM(1) OVER (ORDER BY 1 NULLS) AS db;",
+        distinct_star: "ATTACH count(DISTINCT *) AS db;",
+        distinct_without_arguments: "ATTACH count(DISTINCT) AS db;",
+        all_star: "ATTACH count(ALL *) AS db;"
+    }
+
+    test_group_fail! {
+        expr_column,
+        keyword_after_table: "ATTACH t.SELECT AS db;",
+        keyword_after_schema_table: "ATTACH s.t.SELECT AS db;",
+        missing_column: "ATTACH s.t. AS db;",
+        too_many_levels: "ATTACH a.b.c.d AS db;"
+    }
+
+    test_group_fail! {
+        expr_is,
+        is_missing_rhs: "ATTACH 1 IS AS db;",
+        is_not_missing_rhs: "ATTACH 1 IS NOT AS db;",
+        null_after_expr: "ATTACH 1 NULL AS db;",
+        distinct_missing_from: "ATTACH 1 IS DISTINCT 2 AS db;",
+        distinct_missing_rhs: "ATTACH 1 IS NOT DISTINCT FROM AS db;"
+    }
+
+    test_group_fail! {
+        expr_case,
+        missing_end: "ATTACH CASE WHEN 1 THEN 2 AS db;",
+        missing_when: "ATTACH CASE a END AS db;",
+        missing_then: "ATTACH CASE WHEN 1 2 END AS db;",
+        missing_else_expr: "ATTACH CASE WHEN 1 THEN 2 ELSE END AS db;"
+    }
+
+    test_group_fail! {
+        expr_cast,
+        missing_type_name: "ATTACH CAST(1 AS) AS db;",
+        missing_as: "ATTACH CAST(1 TEXT) AS db;",
+        missing_closing_paren: "ATTACH CAST(1 AS TEXT AS db;",
+        missing_expr: "ATTACH CAST(AS TEXT) AS db;"
+    }
+
+    test_group_fail! {
+        expr_between,
+        missing_low: "ATTACH x BETWEEN AND 10 AS db;",
+        not_missing_between: "ATTACH x NOT 1 AND 10 AS db;",
+        not_between_missing_and: "ATTACH x NOT BETWEEN 1 10 AS db;"
+    }
+
+    test_group_fail! {
+        expr_keyword_operators,
+        between_missing_and: "ATTACH 1 BETWEEN 0 5 AS db;",
+        between_missing_high: "ATTACH 1 BETWEEN 0 AND AS db;",
+        in_missing_closing_paren: "ATTACH 1 IN (1, 2 AS db;",
+        in_trailing_comma: "ATTACH 1 IN (1, ) AS db;",
+        in_unterminated_subquery: "ATTACH 1 IN (SELECT 1 AS db;",
+        in_missing_body: "ATTACH 1 IN AS db;",
+        like_missing_pattern: "ATTACH 'a' LIKE AS db;",
+        like_missing_escape: "ATTACH 'a' LIKE 'b' ESCAPE AS db;",
+        glob_escape: "ATTACH 'a' GLOB 'b' ESCAPE '!' AS db;",
+        match_escape: "ATTACH 'a' MATCH 'b' ESCAPE '!' AS db;",
+        not_missing_operator: "ATTACH 'a' NOT 'b' AS db;"
+    }
+
+    test_group_fail! {
+        sql_create_trigger,
+        create_no_object: "CREATE;",
+        create_invalid_object: "CREATE 5;",
+        create_trigger_no_name: "CREATE TRIGGER;",
+        create_trigger_no_event: "CREATE TRIGGER trig ON users BEGIN END;",
+        create_trigger_bad_event: "CREATE TRIGGER trig BEFORE VACUUM ON users BEGIN END;",
+        create_trigger_no_on: "CREATE TRIGGER trig DELETE users BEGIN END;",
+        create_trigger_no_table: "CREATE TRIGGER trig DELETE ON BEGIN END;",
+        create_trigger_update_of_no_column: "CREATE TRIGGER trig UPDATE OF ON users BEGIN END;",
+        create_trigger_no_begin: "CREATE TRIGGER trig DELETE ON users END;",
+        create_trigger_no_end: "CREATE TRIGGER trig DELETE ON users BEGIN",
+        create_trigger_bad_body_stmt: "CREATE TRIGGER trig DELETE ON users BEGIN VACUUM; END;",
+        create_trigger_empty_body: "CREATE TRIGGER trig DELETE ON users BEGIN END;",
+        create_trigger_body_missing_semicolon: "CREATE TRIGGER trig DELETE ON users BEGIN SELECT 1 END;"
+    }
+
+    test_group_fail! {
+        sql_create_virtual_table,
+        create_virtual_table_no_table: "CREATE VIRTUAL posts USING fts5(title);",
+        create_virtual_table_no_name: "CREATE VIRTUAL TABLE USING fts5(title);",
+        create_virtual_table_no_using: "CREATE VIRTUAL TABLE posts fts5(title);",
+        create_virtual_table_no_module: "CREATE VIRTUAL TABLE posts USING;",
+        create_virtual_table_module_arguments_without_module: "CREATE VIRTUAL TABLE posts USING (title);",
+        create_virtual_table_unterminated_arguments: "CREATE VIRTUAL TABLE posts USING fts5(title, body;",
+        create_virtual_table_temporary: "CREATE TEMP VIRTUAL TABLE posts USING fts5(title);"
+    }
+
+    test_group_fail! {
+        sql_create_table,
+        create_table_no_name: "CREATE TABLE (a TEXT);",
+        create_table_no_columns: "CREATE TABLE t;",
+        create_table_empty_columns: "CREATE TABLE t ();",
+        create_table_trailing_comma: "CREATE TABLE t (a TEXT,);",
+        create_table_unterminated_columns: "CREATE TABLE t (a TEXT;",
+        create_table_column_after_constraint: "CREATE TABLE t (a TEXT, PRIMARY KEY (a), b TEXT);",
+        create_table_constraint_without_kind: "CREATE TABLE t (a TEXT, CONSTRAINT c);",
+        create_table_foreign_key_without_references: "CREATE TABLE t (a TEXT, FOREIGN KEY (a));",
+        create_table_without_rowid_missing: "CREATE TABLE t (a TEXT) WITHOUT;",
+        create_table_unknown_option: "CREATE TABLE t (a TEXT) TEMPORARY;",
+        create_table_as_expr: "CREATE TABLE t AS 5;"
+    }
+
+    test_group_fail! {
+        sql_create_view,
+        create_view_no_name: "CREATE VIEW AS SELECT 1;",
+        create_view_no_as: "CREATE VIEW v SELECT 1;",
+        create_view_empty_columns: "CREATE VIEW v () AS SELECT 1;",
+        create_view_trailing_comma: "CREATE VIEW v (a,) AS SELECT 1;",
+        create_view_expr_body: "CREATE VIEW v AS 5;",
+        create_view_no_body: "CREATE VIEW v AS;",
+        create_view_with_insert: "CREATE VIEW v AS WITH c AS (SELECT 1) INSERT INTO t VALUES (1);",
+        create_view_if_without_exists: "CREATE VIEW IF NOT v AS SELECT 1;"
+    }
+}
+
+#[cfg(test)]
+mod analyse {
+    use crate::{
+        lexer,
+        parser::{Parser, analyse::statements},
+        types::{ctx::Context, rules::Rule, version::Version},
+    };
+
+    fn analyse(input: &str) -> Vec<Rule> {
+        analyse_with(input, Context::new("parser_test_analyse"))
+    }
+
+    fn analyse_with(input: &str, mut ctx: Context) -> Vec<Rule> {
+        let input = input.as_bytes().to_vec();
+        let mut l = lexer::Lexer::new(&input, "parser_test_analyse");
+        let toks = l.run();
+        assert_eq!(l.errors.len(), 0);
+
+        let mut parser = Parser::new(toks, "parser_test_analyse");
+        let ast = parser.parse();
+        assert_eq!(parser.errors.len(), 0);
+
+        statements(&ast, &mut ctx)
+            .into_iter()
+            .map(|e| e.rule)
+            .collect()
+    }
+
+    #[test]
+    fn check_constraint_referencing_other_table() {
+        assert_eq!(
+            analyse("ALTER TABLE t ADD COLUMN c TEXT CHECK ('a' IN other_table);"),
+            vec![Rule::SqliteUnsupported]
+        );
+    }
+
+    #[test]
+    fn check_constraint_with_subquery() {
+        assert_eq!(
+            analyse("ALTER TABLE t ADD COLUMN c TEXT CHECK (c IN (SELECT a FROM other_table));"),
+            vec![Rule::SqliteUnsupported]
+        );
+    }
+
+    #[test]
+    fn check_constraint_with_scalar_and_exists_subquery() {
+        assert_eq!(
+            analyse("ALTER TABLE t ADD COLUMN c INT CHECK ((SELECT max(x) FROM other.y) > c);"),
+            vec![Rule::SqliteUnsupported]
+        );
+        assert_eq!(
+            analyse(
+                "ALTER TABLE t ADD COLUMN c INT CHECK (NOT EXISTS (SELECT 1 WHERE c IN (SELECT 1)));"
+            ),
+            vec![Rule::SqliteUnsupported]
+        );
+    }
+
+    #[test]
+    fn check_constraint_in_create_table() {
+        assert_eq!(
+            analyse("CREATE TABLE t (a INT CHECK (a > (SELECT 1)));"),
+            vec![Rule::SqliteUnsupported]
+        );
+        assert_eq!(
+            analyse("CREATE TABLE t (a INT, CHECK (a IN other_table));"),
+            vec![Rule::SqliteUnsupported]
+        );
+        assert_eq!(
+            analyse("CREATE TABLE t (a INT CHECK (t.a > 0), CHECK (a < 10));"),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn check_constraint_with_qualified_column() {
+        assert_eq!(
+            analyse("ALTER TABLE t ADD COLUMN c INT CHECK (other.x > 0);"),
+            vec![Rule::SqliteUnsupported]
+        );
+        assert_eq!(
+            analyse("ALTER TABLE t ADD COLUMN c INT CHECK (c > 0 AND 1 IN (main.other.x, 2));"),
+            vec![Rule::SqliteUnsupported]
+        );
+        // the columns of the current row may be qualified by its table
+        assert_eq!(
+            analyse("ALTER TABLE main.t ADD COLUMN c INT CHECK (T.c > 0 AND main.t.c < 10);"),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn check_constraint_with_value_list() {
+        assert_eq!(
+            analyse("ALTER TABLE t ADD COLUMN c TEXT CHECK ('a' IN ('a', 'b'));"),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn generated_column_is_not_a_check_constraint() {
+        assert_eq!(
+            analyse("ALTER TABLE t ADD COLUMN c TEXT AS ('a' IN other_table);"),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn rename_to_existing_table() {
+        assert_eq!(
+            analyse("CREATE TABLE users (id INTEGER); ALTER TABLE accounts RENAME TO USERS;"),
+            vec![Rule::Quirk]
+        );
+        assert_eq!(
+            analyse("CREATE TABLE users (id INTEGER); ALTER TABLE a RENAME TO users;"),
+            vec![Rule::Quirk]
+        );
+        assert_eq!(
+            analyse(
+                "CREATE TABLE users (id INTEGER); DROP TABLE users; ALTER TABLE a RENAME TO users;"
+            ),
+            vec![]
+        );
+        assert_eq!(
+            analyse(
+                "CREATE VIEW v AS SELECT 1; DROP VIEW v; ALTER TABLE t RENAME TO v; ALTER TABLE v RENAME TO t;"
+            ),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn insert_default_values() {
+        assert_eq!(analyse("INSERT INTO t DEFAULT VALUES;"), vec![]);
+        assert_eq!(
+            analyse(
+                "CREATE TABLE t (id INTEGER PRIMARY KEY NOT NULL, name TEXT NOT NULL, CHECK (length(name) > 0)); INSERT INTO t DEFAULT VALUES;"
+            ),
+            vec![Rule::Quirk]
+        );
+        assert_eq!(
+            analyse(
+                "CREATE TABLE IF NOT EXISTS main.t (id INTEGER PRIMARY KEY NOT NULL, name TEXT NOT NULL DEFAULT '', created TEXT NOT NULL AS (datetime()), note TEXT); INSERT INTO t DEFAULT VALUES;"
+            ),
+            vec![]
+        );
+        assert_eq!(
+            analyse(
+                "CREATE TABLE t (a TEXT NOT NULL); DROP TABLE t; INSERT INTO t DEFAULT VALUES;"
+            ),
+            vec![]
+        );
+        assert_eq!(
+            analyse(
+                "CREATE TABLE t (id INTEGER PRIMARY KEY); ALTER TABLE t ADD COLUMN c TEXT NOT NULL DEFAULT 'a'; INSERT INTO t DEFAULT VALUES;"
+            ),
+            vec![]
+        );
+        assert_eq!(
+            analyse(
+                "CREATE TABLE t (id INTEGER PRIMARY KEY, c TEXT NOT NULL); ALTER TABLE t DROP COLUMN c; INSERT INTO t DEFAULT VALUES;"
+            ),
+            vec![]
+        );
+        assert_eq!(
+            analyse(
+                "CREATE TABLE t (c TEXT NOT NULL); ALTER TABLE t RENAME TO u; INSERT INTO u DEFAULT VALUES;"
+            ),
+            vec![Rule::Quirk]
+        );
+        assert_eq!(
+            analyse("CREATE TABLE t (c TEXT NOT NULL); INSERT INTO t (c) VALUES ('a');"),
+            vec![]
+        );
+        // keywords inside of expressions do not make a column optional
+        assert_eq!(
+            analyse(
+                "CREATE TABLE t (a INT NOT NULL CHECK (CAST(a AS INT) > 0)); INSERT INTO t DEFAULT VALUES;"
+            ),
+            vec![Rule::Quirk]
+        );
+        // a single INTEGER column PRIMARY KEY table constraint aliases the rowid
+        assert_eq!(
+            analyse(
+                "CREATE TABLE t (id INTEGER NOT NULL, PRIMARY KEY (id)); INSERT INTO t DEFAULT VALUES;"
+            ),
+            vec![]
+        );
+        assert_eq!(
+            analyse(
+                "CREATE TABLE t (id INTEGER NOT NULL, b INTEGER NOT NULL, PRIMARY KEY (id, b)); INSERT INTO t DEFAULT VALUES;"
+            ),
+            vec![Rule::Quirk]
+        );
+    }
+
+    #[test]
+    fn statement_after_commit() {
+        assert_eq!(
+            analyse("BEGIN; DROP TABLE a; COMMIT; DROP TABLE b;"),
+            vec![Rule::Quirk]
+        );
+        assert_eq!(
+            analyse("BEGIN; DELETE FROM a; ROLLBACK; INSERT INTO a (x) VALUES (1);"),
+            vec![Rule::Quirk]
+        );
+    }
+
+    #[test]
+    fn statements_inside_transactions() {
+        // no explicit transactions at all
+        assert_eq!(analyse("DROP TABLE a; DROP TABLE b;"), vec![]);
+        assert_eq!(
+            analyse("BEGIN; DROP TABLE a; COMMIT; BEGIN; DROP TABLE b; END;"),
+            vec![]
+        );
+        // ROLLBACK TO keeps the transaction open
+        assert_eq!(
+            analyse("BEGIN; SAVEPOINT s; ROLLBACK TO s; DROP TABLE a; RELEASE s; COMMIT;"),
+            vec![]
+        );
+        // queries and transaction control do not modify anything
+        assert_eq!(analyse("BEGIN; COMMIT; SELECT 1; VACUUM;"), vec![]);
+    }
+
+    #[test]
+    fn nested_savepoints() {
+        assert_eq!(
+            analyse(
+                "BEGIN; SAVEPOINT a; SAVEPOINT b; RELEASE b; SAVEPOINT c; ROLLBACK TO a; RELEASE a; COMMIT;"
+            ),
+            vec![]
+        );
+        // releasing an outer savepoint releases the inner ones too
+        assert_eq!(
+            analyse("SAVEPOINT a; SAVEPOINT b; RELEASE SAVEPOINT A; BEGIN; COMMIT;"),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn mismatched_savepoints() {
+        assert_eq!(analyse("BEGIN; RELEASE a; COMMIT;"), vec![Rule::Quirk]);
+        assert_eq!(analyse("RELEASE a;"), vec![Rule::Quirk]);
+        assert_eq!(
+            analyse("SAVEPOINT a; RELEASE a; RELEASE a;"),
+            vec![Rule::Quirk]
+        );
+        // ROLLBACK cancels all savepoints
+        assert_eq!(
+            analyse("BEGIN; SAVEPOINT a; ROLLBACK; BEGIN; RELEASE a; COMMIT;"),
+            vec![Rule::Quirk]
+        );
+        assert_eq!(analyse("BEGIN; SAVEPOINT a; COMMIT;"), vec![Rule::Quirk]);
+        assert_eq!(
+            analyse("BEGIN; SAVEPOINT a; SAVEPOINT b; RELEASE b; END;"),
+            vec![Rule::Quirk]
+        );
+    }
+
+    #[test]
+    fn empty_in_list() {
+        assert_eq!(analyse("SELECT 1 WHERE x IN ();"), vec![Rule::Quirk]);
+        assert_eq!(analyse("SELECT 1 WHERE x NOT IN ();"), vec![Rule::Quirk]);
+        assert_eq!(analyse("SELECT 1 WHERE x IN (1);"), vec![]);
+        assert_eq!(analyse("SELECT 1 WHERE x IN (SELECT 1);"), vec![]);
+    }
+
+    #[test]
+    fn named_window() {
+        assert_eq!(
+            analyse("SELECT sum(a) OVER win FROM t WINDOW win AS (ORDER BY b);"),
+            vec![]
+        );
+        assert_eq!(
+            analyse("SELECT sum(a) OVER Win FROM t WINDOW win AS (ORDER BY b), w2 AS (win);"),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn undefined_window() {
+        assert_eq!(
+            analyse("SELECT sum(a) OVER win FROM t;"),
+            vec![Rule::Syntax]
+        );
+        assert_eq!(
+            analyse("SELECT sum(a) OVER (base ORDER BY b) FROM t WINDOW win AS ();"),
+            vec![Rule::Syntax]
+        );
+        // windows are scoped to their select
+        assert_eq!(
+            analyse("SELECT (SELECT sum(a) OVER win FROM u) FROM t WINDOW win AS ();"),
+            vec![Rule::Syntax]
+        );
+    }
+
+    #[test]
+    fn rename_to_reserved_name() {
+        assert_eq!(
+            analyse("ALTER TABLE t RENAME TO sqlite_t;"),
+            vec![Rule::ReservedTableName]
+        );
+        assert_eq!(analyse("ALTER TABLE t RENAME TO sqlitet;"), vec![]);
+    }
+
+    /// SELECT * FROM t0 JOIN t1 ... JOIN t{joins}
+    fn select_with_joins(joins: usize) -> String {
+        let mut input = String::from("SELECT * FROM t0");
+        for i in 1..=joins {
+            input.push_str(&format!(" JOIN t{i}"));
+        }
+        input.push(';');
+        input
+    }
+
+    #[test]
+    fn too_many_joins() {
+        let mut ctx = Context::new("parser_test_analyse");
+        ctx.max_joins = 2;
+        assert_eq!(analyse_with(&select_with_joins(2), ctx), vec![]);
+
+        let mut ctx = Context::new("parser_test_analyse");
+        ctx.max_joins = 2;
+        assert_eq!(
+            analyse_with(&select_with_joins(3), ctx),
+            vec![Rule::TooManyJoins]
+        );
+    }
+
+    #[test]
+    fn too_many_joins_in_subquery() {
+        let mut ctx = Context::new("parser_test_analyse");
+        ctx.max_joins = 1;
+        assert_eq!(
+            analyse_with(
+                "SELECT * FROM a JOIN (SELECT * FROM b JOIN c JOIN d) AS e;",
+                ctx
+            ),
+            vec![Rule::TooManyJoins]
+        );
+    }
+
+    #[test]
+    fn json_operators_require_sqlite_3_38() {
+        let input = "SELECT data -> '$.a', data ->> '$.b' FROM t;";
+        assert_eq!(analyse(input), vec![]);
+
+        let mut ctx = Context::new("parser_test_analyse");
+        ctx.sqlite_version = Some(Version::new(3, 37, 2));
+        assert_eq!(
+            analyse_with(input, ctx),
+            vec![Rule::SqliteUnsupported, Rule::SqliteUnsupported]
+        );
+
+        let mut ctx = Context::new("parser_test_analyse");
+        ctx.sqlite_version = Some(Version::new(3, 38, 0));
+        assert_eq!(analyse_with(input, ctx), vec![]);
+    }
+
+    #[test]
+    fn materialization_hints_require_sqlite_3_35() {
+        let input = "WITH a AS MATERIALIZED (SELECT 1), b AS NOT MATERIALIZED (SELECT 1), c AS (SELECT 1) SELECT 1;";
+        assert_eq!(analyse(input), vec![]);
+
+        let mut ctx = Context::new("parser_test_analyse");
+        ctx.sqlite_version = Some(Version::new(3, 34, 1));
+        assert_eq!(
+            analyse_with(input, ctx),
+            vec![Rule::SqliteUnsupported, Rule::SqliteUnsupported]
+        );
+
+        let mut ctx = Context::new("parser_test_analyse");
+        ctx.sqlite_version = Some(Version::new(3, 35, 0));
+        assert_eq!(analyse_with(input, ctx), vec![]);
+    }
+
+    #[test]
+    fn is_distinct_from_requires_sqlite_3_39() {
+        let input = "SELECT a IS DISTINCT FROM b, a IS NOT DISTINCT FROM b, a IS NOT b FROM t;";
+        assert_eq!(analyse(input), vec![]);
+
+        let mut ctx = Context::new("parser_test_analyse");
+        ctx.sqlite_version = Some(Version::new(3, 38, 0));
+        assert_eq!(
+            analyse_with(input, ctx),
+            vec![Rule::SqliteUnsupported, Rule::SqliteUnsupported]
+        );
+
+        let mut ctx = Context::new("parser_test_analyse");
+        ctx.sqlite_version = Some(Version::new(3, 39, 0));
+        assert_eq!(analyse_with(input, ctx), vec![]);
+    }
+
+    #[test]
+    fn runtime_pragma_in_migration() {
+        let input = "PRAGMA optimize; PRAGMA main.integrity_check; PRAGMA user_version = 2;";
+        assert_eq!(analyse(input), vec![]);
+
+        let mut ctx = Context::new("parser_test_analyse");
+        ctx.migration = true;
+        assert_eq!(analyse_with(input, ctx), vec![Rule::Quirk, Rule::Quirk]);
+    }
+
+    #[test]
+    fn join_exceeding_sqlite_table_limit() {
+        assert_eq!(analyse(&select_with_joins(63)), vec![Rule::TooManyJoins]);
+        assert_eq!(
+            analyse(&select_with_joins(64)),
+            vec![Rule::SqliteUnsupported]
+        );
+    }
+}
+
+#[cfg(test)]
+mod lints {
+    use crate::{lexer, parser::Parser, types::rules::Rule};
+
+    /// returns the rules of all errors the parser emitted and the serialized ast
+    fn parse(input: &str) -> (Vec<Rule>, serde_json::Value) {
+        let input = input.as_bytes().to_vec();
+        let mut l = lexer::Lexer::new(&input, "parser_test_lints");
+        let toks = l.run();
+        assert_eq!(l.errors.len(), 0);
+
+        let mut parser = Parser::new(toks, "parser_test_lints");
+        let ast = parser.parse();
+        (
+            parser.errors.into_iter().map(|e| e.rule).collect(),
+            serde_json::Value::Array(ast.iter().map(|n| n.as_serializable()).collect()),
+        )
+    }
+
+    #[test]
+    fn data_modifying_cte() {
+        for input in [
+            "WITH x AS (DELETE FROM t RETURNING *) SELECT * FROM x;",
+            "WITH x AS (INSERT INTO t VALUES (1) RETURNING id) SELECT * FROM x;",
+            "WITH a AS (SELECT 1), x AS (UPDATE t SET a = 1 RETURNING a) SELECT * FROM x;",
+        ] {
+            let (rules, ast) = parse(input);
+            assert_eq!(rules, vec![Rule::SqliteUnsupported], "{input}");
+            assert_eq!(ast.as_array().unwrap().len(), 0);
+        }
+        // the following statement is parsed as usual
+        let (rules, ast) = parse("WITH x AS (DELETE FROM t RETURNING *) SELECT * FROM x; VACUUM;");
+        assert_eq!(rules, vec![Rule::SqliteUnsupported]);
+        assert_eq!(ast.as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn instruction_node_mismatch() {
+        let (rules, ast) = parse("-- @sqleibniz::node(Select)\nVACUUM;\nVACUUM;");
+        assert_eq!(rules, vec![Rule::BadSqleibnizInstruction]);
+        assert_eq!(ast.as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn instruction_node_without_statement() {
+        let (rules, _) = parse("VACUUM;\n-- @sqleibniz::node(Vacuum)");
+        assert_eq!(rules, vec![Rule::BadSqleibnizInstruction]);
+    }
+
+    #[test]
+    fn instruction_disable() {
+        let (rules, _) = parse("-- @sqleibniz::disable:Quirk\nPRAGMA foreign_keys = 2;");
+        assert_eq!(rules, vec![]);
+        // only the statement directly after the instruction is affected
+        let (rules, _) = parse("-- @sqleibniz::disable:Quirk\nVACUUM;\nPRAGMA foreign_keys = 2;");
+        assert_eq!(rules, vec![Rule::Quirk]);
+        // other rules are still reported
+        let (rules, _) = parse("-- @sqleibniz::disable:Syntax\nSELECT a, b FROM t ORDER BY 1;\n");
+        assert_eq!(rules, vec![Rule::Quirk]);
+        let (rules, _) = parse(
+            "-- @sqleibniz::disable:Syntax\n-- @sqleibniz::disable:Quirk\nSELECT a, b FROM t ORDER BY 1;",
+        );
+        assert_eq!(rules, vec![]);
+    }
+
+    #[test]
+    fn bind_parameters() {
+        let (rules, ast) = parse("ATTACH ?12 AS db; ATTACH ? AS db; ATTACH $name AS db;");
+        assert_eq!(rules, vec![]);
+        assert_eq!(ast[0]["expr"]["bind"]["counter"], 12);
+        assert_eq!(ast[1]["expr"]["bind"]["counter"], serde_json::Value::Null);
+        assert_eq!(ast[2]["expr"]["bind"]["name"], "$name");
+    }
+
+    #[test]
+    fn bind_parameter_without_name() {
+        let (rules, _) = parse("ATTACH : AS db;");
+        assert_eq!(rules[0], Rule::Syntax);
+    }
+
+    #[test]
+    fn schema_qualified_builtin_function() {
+        let (rules, ast) = parse("ATTACH main.count(*) AS db;");
+        assert_eq!(rules, vec![Rule::Quirk]);
+        assert_eq!(ast[0]["expr"]["compound"]["schema"], "main");
+        assert_eq!(ast[0]["expr"]["compound"]["name"], "count");
+        assert_eq!(ast[0]["expr"]["compound"]["star"], true);
+    }
+
+    #[test]
+    fn regexp_is_unsupported() {
+        let (rules, ast) = parse("ATTACH 'a' NOT REGEXP 'b' AS db;");
+        assert_eq!(rules, vec![Rule::SqliteUnsupported]);
+        assert_eq!(ast[0]["expr"]["compound"]["op"], "REGEXP");
+        assert_eq!(ast[0]["expr"]["compound"]["negated"], true);
+    }
+
+    #[test]
+    fn returning_on_select_is_unsupported() {
+        let (rules, ast) = parse("SELECT * FROM t RETURNING *; VACUUM;");
+        assert_eq!(rules, vec![Rule::SqliteUnsupported]);
+        assert_eq!(ast.as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn deeply_nested_expr_errors_instead_of_overflowing() {
+        // runs on the default test thread stack, each (open, close) pair nests a single level
+        let depth = crate::parser::Parser::MAX_EXPR_DEPTH * 25;
+        for (open, close) in [
+            ("(", ")"),
+            ("abs(", ")"),
+            ("-(", ")"),
+            ("NOT (", ")"),
+            ("1 + (", ")"),
+            ("(SELECT ", ")"),
+            ("x IN (SELECT ", ")"),
+            ("EXISTS (SELECT ", ")"),
+            ("CASE WHEN 1 THEN ", " END"),
+        ] {
+            let input = format!(
+                "SELECT {}1{}; VACUUM;",
+                open.repeat(depth),
+                close.repeat(depth)
+            );
+            let (rules, ast) = parse(&input);
+            assert_eq!(rules, vec![Rule::Syntax], "{open}");
+            assert_eq!(ast.as_array().unwrap().len(), 1, "{open}");
+        }
+    }
+
+    #[test]
+    fn nested_expr_below_the_limit_is_analysed() {
+        // every level counts twice: the subquery and the expression it selects
+        let depth = crate::parser::Parser::MAX_EXPR_DEPTH / 2 - 1;
+        let input = format!(
+            "SELECT {}1{};",
+            "EXISTS (SELECT ".repeat(depth),
+            ")".repeat(depth)
+        );
+        let input = input.as_bytes().to_vec();
+        let toks = lexer::Lexer::new(&input, "parser_test_lints").run();
+        let mut parser = Parser::new(toks, "parser_test_lints");
+        let ast = parser.parse();
+        assert_eq!(parser.errors.len(), 0);
+        let mut ctx = crate::types::ctx::Context::new("parser_test_lints");
+        assert_eq!(crate::parser::analyse::statements(&ast, &mut ctx).len(), 0);
+    }
+
+    #[test]
+    fn invalid_clause_reports_a_single_error() {
+        for input in [
+            "WITH c() AS (SELECT 1) SELECT * FROM c;",
+            "WITH c AS MATERIALIZED SELECT 1;",
+            "INSERT INTO t (a) VALUES (1) ON CONFLICT DO UPDATE WHERE 1;",
+            "SELECT a FROM t GROUP BY HAVING 1;",
+            "CREATE VIEW v () AS SELECT 1;",
+        ] {
+            let (rules, ast) = parse(&format!("{input} VACUUM;"));
+            assert_eq!(rules, vec![Rule::Syntax], "{input}");
+            assert_eq!(ast[0]["type"], "Vacuum", "{input}");
+        }
+    }
+
+    #[test]
+    fn invalid_bind_parameter_reports_a_single_error() {
+        let input = "SELECT ?0; VACUUM;".as_bytes().to_vec();
+        let mut l = lexer::Lexer::new(&input, "parser_test_lints");
+        let toks = l.run();
+        assert_eq!(
+            l.errors.iter().map(|e| e.rule.clone()).collect::<Vec<_>>(),
+            vec![Rule::InvalidNumericLiteral]
+        );
+        let mut parser = Parser::new(toks, "parser_test_lints");
+        assert_eq!(parser.parse().len(), 2);
+        assert_eq!(parser.errors.len(), 0);
+    }
+
+    #[test]
+    fn join_errors() {
+        let input = "SELECT * FROM a NATURAL JOIN b USING (id); SELECT * FROM a NATURAL LEFT OUTER JOIN; VACUUM;"
+            .as_bytes()
+            .to_vec();
+        let toks = lexer::Lexer::new(&input, "parser_test_lints").run();
+        let mut parser = Parser::new(toks, "parser_test_lints");
+        assert_eq!(parser.parse().len(), 2);
+        assert_eq!(
+            parser
+                .errors
+                .iter()
+                .map(|e| e.msg.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "Constraint on NATURAL join",
+                "Missing table or subquery to join"
+            ]
+        );
+        assert!(
+            parser.errors[1]
+                .note
+                .starts_with("NATURAL LEFT JOIN requires a table or subquery")
+        );
+    }
+
+    #[test]
+    fn trigger_body_accepts_all_statements_it_names() {
+        // the statements the "Empty trigger body" note lists
+        for stmt in [
+            "DELETE FROM t;",
+            "INSERT INTO t (a) VALUES (1);",
+            "UPDATE t SET a = new.a WHERE id = old.id;",
+            "SELECT 1;",
+        ] {
+            let (rules, ast) = parse(&format!(
+                "CREATE TRIGGER tr AFTER INSERT ON t BEGIN {stmt} END;"
+            ));
+            assert_eq!(rules, vec![], "{stmt}");
+            assert_eq!(ast[0]["body"].as_array().unwrap().len(), 1, "{stmt}");
+        }
+    }
+
+    #[test]
+    fn mysql_auto_increment() {
+        let (rules, ast) = parse("ALTER TABLE t ADD COLUMN id INTEGER PRIMARY KEY AUTO_INCREMENT;");
+        assert_eq!(rules, vec![Rule::SqliteUnsupported]);
+        assert_eq!(
+            ast[0]["add_column"]["constraints"][0]["constraint"]["primary_key"]["autoincrement"],
+            false
+        );
+    }
+
+    #[test]
+    fn mysql_engine_table_option() {
+        let input = "CREATE TABLE t (id INTEGER) ENGINE=InnoDB;"
+            .as_bytes()
+            .to_vec();
+        let mut l = lexer::Lexer::new(&input, "parser_test_lints");
+        let toks = l.run();
+        let mut parser = Parser::new(toks, "parser_test_lints");
+        let _ = parser.parse();
+        let engine = parser
+            .errors
+            .iter()
+            .find(|e| e.rule == Rule::SqliteUnsupported)
+            .expect("ENGINE=InnoDB should be flagged");
+        assert_eq!(engine.msg, "ENGINE is MySQL syntax");
+        assert!(engine.note.contains("single storage engine"));
+    }
+
+    #[test]
+    fn mysql_table_without_syntax_errors() {
+        let (rules, _) = parse(
+            "CREATE TABLE t (id INTEGER AUTO_INCREMENT, name TEXT) ENGINE=InnoDB DEFAULT CHARSET=utf8;",
+        );
+        assert_eq!(
+            rules,
+            vec![
+                Rule::SqliteUnsupported,
+                Rule::SqliteUnsupported,
+                Rule::SqliteUnsupported
+            ]
+        );
+    }
+
+    #[test]
+    fn postgres_serial_replaces_unknown_type_quirk() {
+        let (rules, _) = parse("ALTER TABLE t ADD COLUMN id SERIAL;");
+        assert_eq!(rules, vec![Rule::SqliteUnsupported]);
+    }
+
+    #[test]
+    fn column_named_like_table_option() {
+        let (rules, _) = parse("ALTER TABLE t ADD COLUMN engine TEXT; SELECT engine FROM t;");
+        assert_eq!(rules, vec![]);
+    }
+
+    #[test]
+    fn delete_without_from() {
+        let (rules, ast) = parse("DELETE t WHERE a = 1;");
+        assert_eq!(rules, vec![Rule::Syntax]);
+        assert_eq!(ast[0]["table"], serde_json::json!({ "Table": "t" }));
+    }
+
+    #[test]
+    fn explain_query_plan_flag() {
+        let (_, ast) = parse("EXPLAIN VACUUM; EXPLAIN QUERY PLAN VACUUM;");
+        assert_eq!(ast[0]["query_plan"], false);
+        assert_eq!(ast[1]["query_plan"], true);
+    }
+
+    #[test]
+    fn pragma_boolean_invalid() {
+        let (rules, _) = parse("PRAGMA foreign_keys = maybe;");
+        assert_eq!(rules, vec![Rule::Syntax]);
+    }
+
+    #[test]
+    fn pragma_boolean_unusual_integer() {
+        let (rules, _) = parse("PRAGMA foreign_keys = 2;");
+        assert_eq!(rules, vec![Rule::Quirk]);
+    }
+
+    #[test]
+    fn pragma_unknown_name() {
+        let (rules, _) = parse("PRAGMA forein_keys = ON;");
+        assert_eq!(rules, vec![Rule::UnknownPragma]);
+        assert_eq!(
+            crate::types::pragma::suggestion("forein_keys"),
+            "foreign_keys"
+        );
+        let (rules, _) = parse("PRAGMA main.Journal_Mode;");
+        assert_eq!(rules, vec![]);
+    }
+
+    #[test]
+    fn pragma_configured_name() {
+        let input = "PRAGMA cipher = 'aes'; PRAGMA Cipher_Page_Size; PRAGMA ciphr;"
+            .as_bytes()
+            .to_vec();
+        let toks = lexer::Lexer::new(&input, "parser_test_lints").run();
+        let mut parser = Parser::new(toks, "parser_test_lints");
+        parser.pragmas = vec!["cipher".into(), "cipher_page_size".into()];
+        parser.parse();
+        assert_eq!(
+            parser
+                .errors
+                .iter()
+                .map(|e| e.rule.clone())
+                .collect::<Vec<_>>(),
+            vec![Rule::UnknownPragma]
+        );
+    }
+
+    #[test]
+    fn pragma_quoted_name_is_not_validated() {
+        let (rules, _) = parse("PRAGMA 'custom_pragma'; PRAGMA main.'custom_pragma';");
+        assert_eq!(rules, vec![]);
+    }
+
+    #[test]
+    fn pragma_non_ascii_name() {
+        let (rules, _) = parse("PRAGMA fremdschlüssel;");
+        assert_eq!(rules, vec![Rule::UnknownPragma]);
+        // the lexer warns about the quoting, the parser must not validate the name
+        let input = "PRAGMA \"fremdschlüssel\"; PRAGMA [fremdschlüssel];"
+            .as_bytes()
+            .to_vec();
+        let toks = lexer::Lexer::new(&input, "parser_test_lints").run();
+        let mut parser = Parser::new(toks, "parser_test_lints");
+        parser.parse();
+        assert_eq!(parser.errors.len(), 0);
+    }
+
+    #[test]
+    fn pragma_non_boolean_is_not_validated() {
+        let (rules, _) = parse("PRAGMA journal_mode = maybe;");
+        assert_eq!(rules, vec![]);
+    }
+
+    #[test]
+    fn insert_without_columns() {
+        let (rules, ast) = parse("INSERT INTO t VALUES (1);");
+        assert_eq!(rules, vec![Rule::ImplicitInsertColumns]);
+        assert_eq!(ast[0]["columns"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn insert_with_columns() {
+        let (rules, _) = parse("INSERT INTO t (a) VALUES (1);");
+        assert_eq!(rules, vec![]);
+    }
+
+    #[test]
+    fn cast_to_unknown_type() {
+        let (rules, ast) = parse("ATTACH CAST(1 AS STRING) AS db;");
+        assert_eq!(rules, vec![Rule::Quirk]);
+        assert_eq!(ast[0]["expr"]["compound"]["type_name"], "Integer");
+    }
+
+    #[test]
+    fn order_by_ordinal() {
+        let (rules, ast) = parse("SELECT a, b FROM t ORDER BY 1;");
+        assert_eq!(rules, vec![Rule::Quirk]);
+        assert_eq!(ast[0]["order_by"][0]["ordinal"], 1);
+    }
+
+    #[test]
+    fn order_by_ordinal_out_of_range() {
+        let (rules, _) = parse("SELECT a, b FROM t ORDER BY 3, 0;");
+        assert_eq!(rules, vec![Rule::Syntax, Rule::Syntax]);
+    }
+
+    #[test]
+    fn order_by_ordinal_with_star_is_not_range_checked() {
+        let (rules, _) = parse("SELECT * FROM t ORDER BY 3;");
+        assert_eq!(rules, vec![Rule::Quirk]);
+    }
+
+    #[test]
+    fn order_by_expression_is_not_an_ordinal() {
+        let (rules, ast) = parse("SELECT a, b FROM t ORDER BY a, 1.5;");
+        assert_eq!(rules, vec![]);
+        assert!(ast[0]["order_by"][0]["ordinal"].is_null());
+        assert!(ast[0]["order_by"][1]["ordinal"].is_null());
+    }
+}
+
+#[cfg(test)]
+mod visit {
+    use crate::{lexer, parser::Parser, parser::visit::walk};
+
+    /// returns the names of all nodes of the first statement of input, in visiting order
+    fn walked(input: &str) -> Vec<String> {
+        let input = input.as_bytes().to_vec();
+        let toks = lexer::Lexer::new(&input, "parser_test_visit").run();
+        let mut parser = Parser::new(toks, "parser_test_visit");
+        let ast = parser.parse();
+        assert_eq!(parser.errors.len(), 0);
+        let mut names = vec![];
+        walk(ast[0].as_ref(), &mut |n| names.push(n.name().to_string()));
+        names
+    }
+
+    #[test]
+    fn parents_before_children() {
+        assert_eq!(
+            walked("SELECT 1 + 2;"),
+            vec!["Select", "Expr", "BinaryExpr", "Expr", "Expr"]
+        );
+    }
+
+    #[test]
+    fn insert_returning() {
+        assert_eq!(
+            walked("INSERT INTO t (a) VALUES (1) RETURNING a, lower(a);"),
+            vec!["Insert", "Expr", "Expr", "Expr", "FunctionCall", "Expr"]
+        );
+    }
+
+    #[test]
+    fn delete_returning() {
+        assert_eq!(
+            walked("DELETE FROM t WHERE a RETURNING a * 2;"),
+            vec!["Delete", "Expr", "Expr", "BinaryExpr", "Expr", "Expr"]
+        );
+    }
+}
+
+#[cfg(test)]
+mod on_error {
+    use std::cell::Cell;
+
+    use crate::{lexer, parser::Parser};
+
+    #[test]
+    fn counts_errors_as_they_arrive() {
+        let input = "VACUUM 5; SELECT; VACUUM;".as_bytes().to_vec();
+        let toks = lexer::Lexer::new(&input, "parser_test_on_error").run();
+        let count = Cell::new(0);
+        let mut parser = Parser::new(toks, "parser_test_on_error").on_error(|_| {
+            count.set(count.get() + 1);
+        });
+        parser.parse();
+        let collected = parser.errors.len();
+        drop(parser);
+        assert_ne!(collected, 0);
+        assert_eq!(count.get(), collected);
+    }
+
+    #[test]
+    fn lexer_and_parser_share_sink() {
+        let input = "VACUUM 5; 'unterminated".as_bytes().to_vec();
+        let mut messages = vec![];
+        let mut l = lexer::Lexer::new(&input, "parser_test_on_error")
+            .on_error(|e| messages.push(e.msg.clone()));
+        let toks = l.run();
+        drop(l);
+        let mut parser =
+            Parser::new(toks, "parser_test_on_error").on_error(|e| messages.push(e.msg.clone()));
+        parser.parse();
+        drop(parser);
+        assert!(messages.len() >= 2);
     }
 }
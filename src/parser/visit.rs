@@ -0,0 +1,191 @@
+use crate::{
+    parser::nodes::*,
+    types::{Keyword, Token, Type, storage::SqliteStorageClass},
+};
+
+/// impl FieldChildren for $tt as a noop, for types not holding any nodes
+macro_rules! impl_field_children_noop {
+    ($($tt:tt),*) => {
+        $(
+            impl FieldChildren for $tt {
+                fn field_children<'a>(&'a self, _children: &mut Vec<&'a dyn Node>) {}
+            }
+        )*
+    };
+}
+
+/// FieldChildren is implemented for all types used as fields of nodes, the node! macro calls
+/// [FieldChildren::field_children] for each field of a node in [Node::children]
+pub trait FieldChildren {
+    /// appends all nodes directly contained in self to children, nodes nested in these nodes are
+    /// not appended
+    fn field_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>);
+}
+
+impl_field_children_noop!(
+    String,
+    bool,
+    usize,
+    Keyword,
+    Token,
+    SqliteStorageClass,
+    SchemaTableContainer,
+    Type,
+    PragmaInvocation,
+    TriggerTiming,
+    TriggerEvent,
+    JoinOperator,
+    CompoundOperator
+);
+
+impl<T: Node + ?Sized> FieldChildren for Box<T> {
+    fn field_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        children.push(self.as_ref().as_node());
+    }
+}
+
+impl<T: FieldChildren> FieldChildren for Option<T> {
+    fn field_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        if let Some(n) = self {
+            n.field_children(children);
+        }
+    }
+}
+
+impl<T: FieldChildren> FieldChildren for Vec<T> {
+    fn field_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        for n in self {
+            n.field_children(children);
+        }
+    }
+}
+
+impl<A: FieldChildren, B: FieldChildren> FieldChildren for (A, B) {
+    fn field_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        self.0.field_children(children);
+        self.1.field_children(children);
+    }
+}
+
+impl FieldChildren for ColumnConstraint {
+    fn field_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        match self {
+            ColumnConstraint::Check(expr) => expr.field_children(children),
+            ColumnConstraint::Default { expr, literal, .. } => {
+                expr.field_children(children);
+                literal.field_children(children);
+            }
+            ColumnConstraint::Generated { expr, .. } | ColumnConstraint::As { expr, .. } => {
+                expr.field_children(children)
+            }
+            ColumnConstraint::PrimaryKey { .. }
+            | ColumnConstraint::NotNull { .. }
+            | ColumnConstraint::Unique { .. }
+            | ColumnConstraint::Collate(_)
+            | ColumnConstraint::ForeignKey(_) => {}
+        }
+    }
+}
+
+impl FieldChildren for NamedColumnConstraint {
+    fn field_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        self.constraint.field_children(children);
+    }
+}
+
+impl FieldChildren for TableConstraint {
+    fn field_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        match self {
+            TableConstraint::PrimaryKey { columns, .. }
+            | TableConstraint::Unique { columns, .. } => columns.field_children(children),
+            TableConstraint::Check(expr) => expr.field_children(children),
+            TableConstraint::ForeignKey { .. } => {}
+        }
+    }
+}
+
+impl FieldChildren for NamedTableConstraint {
+    fn field_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        self.constraint.field_children(children);
+    }
+}
+
+impl FieldChildren for InBody {
+    fn field_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        match self {
+            InBody::List(list) => list.field_children(children),
+            InBody::Subquery(subquery) => subquery.field_children(children),
+            InBody::Table(_) => {}
+        }
+    }
+}
+
+impl FieldChildren for FrameBound {
+    fn field_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        match self {
+            FrameBound::Preceding(expr) | FrameBound::Following(expr) => {
+                expr.field_children(children)
+            }
+            FrameBound::UnboundedPreceding
+            | FrameBound::CurrentRow
+            | FrameBound::UnboundedFollowing => {}
+        }
+    }
+}
+
+impl FieldChildren for ResultColumn {
+    fn field_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        match self {
+            ResultColumn::Expr { expr, .. } => expr.field_children(children),
+            ResultColumn::Star | ResultColumn::TableStar(_) => {}
+        }
+    }
+}
+
+impl FieldChildren for TableOrSubquery {
+    fn field_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        match self {
+            TableOrSubquery::Subquery { select, .. } => select.field_children(children),
+            TableOrSubquery::Table { .. } => {}
+        }
+    }
+}
+
+impl FieldChildren for InsertSource {
+    fn field_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        match self {
+            InsertSource::Values(rows) => rows.field_children(children),
+            InsertSource::Select(select) => select.field_children(children),
+            InsertSource::DefaultValues => {}
+        }
+    }
+}
+
+impl FieldChildren for UpsertAction {
+    fn field_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        match self {
+            UpsertAction::Update { set, where_clause } => {
+                set.field_children(children);
+                where_clause.field_children(children);
+            }
+            UpsertAction::Nothing => {}
+        }
+    }
+}
+
+impl FieldChildren for JoinConstraint {
+    fn field_children<'a>(&'a self, children: &mut Vec<&'a dyn Node>) {
+        match self {
+            JoinConstraint::On(expr) => expr.field_children(children),
+            JoinConstraint::Using(_) => {}
+        }
+    }
+}
+
+/// calls f for node and all nodes nested in it, parents before their children
+pub fn walk<'a>(node: &'a dyn Node, f: &mut impl FnMut(&'a dyn Node)) {
+    f(node);
+    for child in node.children() {
+        walk(child, f);
+    }
+}
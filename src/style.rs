@@ -0,0 +1,83 @@
+use crate::{error::Error, types::rules::Rule};
+
+/// default for [crate::types::config::Config::max_line_length]
+pub const DEFAULT_MAX_LINE_LENGTH: usize = 120;
+/// default for [crate::types::config::Config::tab_width]
+pub const DEFAULT_TAB_WIDTH: usize = 4;
+
+/// emits [Rule::LineLength] for each line of source wider than max, tabs advance the width to the
+/// next multiple of tab_width. The error starts at the first character exceeding max and spans the
+/// rest of the line.
+pub fn line_length(file: &str, source: &[u8], max: usize, tab_width: usize) -> Vec<Error> {
+    let mut errors = vec![];
+    for (line, content) in source.split(|c| *c == b'\n').enumerate() {
+        let content = content.strip_suffix(b"\r").unwrap_or(content);
+        let mut width = 0;
+        let mut exceeding = None;
+        let content = String::from_utf8_lossy(content);
+        for (column, c) in content.chars().enumerate() {
+            width = if c == '\t' {
+                (width / tab_width.max(1) + 1) * tab_width.max(1)
+            } else {
+                width + 1
+            };
+            if width > max && exceeding.is_none() {
+                exceeding = Some(column);
+            }
+        }
+        if let Some(start) = exceeding {
+            errors.push(Error {
+                improved_line: None,
+                file: file.to_string(),
+                line,
+                rule: Rule::LineLength,
+                severity: Rule::LineLength.severity(),
+                note: format!("line is {width} columns wide, the configured maximum is {max}"),
+                msg: "Line too long".into(),
+                start,
+                end: content.chars().count(),
+                doc_url: None,
+            });
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::line_length;
+
+    /// returns the (line, start, end) of each line length error in input
+    fn errors(input: &str, max: usize) -> Vec<(usize, usize, usize)> {
+        line_length("style_test", input.as_bytes(), max, 4)
+            .into_iter()
+            .map(|e| (e.line, e.start, e.end))
+            .collect()
+    }
+
+    #[test]
+    fn one_long_line() {
+        assert_eq!(
+            errors("VACUUM;\nSELECT 1, 2, 3;\nVACUUM;", 10),
+            vec![(1, 10, 15)]
+        );
+    }
+
+    #[test]
+    fn exactly_max_is_allowed() {
+        assert_eq!(errors("VACUUM;\r\n", 7), vec![]);
+    }
+
+    #[test]
+    fn tabs_expand_to_tab_width() {
+        // the tab expands to 4 columns, a advances to 5 and b to 6
+        assert_eq!(errors("\tab", 5), vec![(0, 2, 3)]);
+        // tabs align to the next tab stop
+        assert_eq!(errors("ab\tc", 4), vec![(0, 3, 4)]);
+    }
+
+    #[test]
+    fn multi_byte_characters_are_one_column() {
+        assert_eq!(errors("SELECT 'äöü';", 13), vec![]);
+    }
+}
@@ -1,24 +1,76 @@
-use mlua::{FromLua, Function, Table, UserData};
+use std::{
+    fs,
+    io::Write,
+    time::{Duration, Instant},
+};
 
-use super::{ctx::HookContext, rules::Rule};
+use mlua::{FromLua, Function, HookTriggers, Table, UserData, VmState};
 
-#[derive(Debug)]
-/// Configuration is expected to be at ./leibniz.lua - its existence is not required for the program invocation
+use super::{
+    ctx::HookContext,
+    rules::{Rule, Severity},
+    version::Version,
+};
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Configuration is expected to be at ./leibniz.lua - its existence is not required for the program invocation.
+/// Alternatively it is read from a toml file mirroring the `leibniz` table, see [load_toml]
 pub struct Config {
     /// holds the rules that the user wants to not see errors for.
+    #[serde(default)]
     pub disabled_rules: Vec<Rule>,
-    /// holds the hooks the user wants to execute
+    /// holds the opt-in rules, see [Rule::opt_in], the user wants to see errors for.
+    #[serde(default)]
+    pub enabled_rules: Vec<Rule>,
+    /// errors with a severity below are not reported, defaults to reporting all severities
+    pub min_severity: Option<Severity>,
+    /// holds the hooks the user wants to execute, hooks are lua functions and thus not available
+    /// in toml configurations
+    #[serde(skip)]
     pub hooks: Option<Vec<Hook>>,
+    /// amount of joins a join clause may contain before [Rule::TooManyJoins] is emitted,
+    /// defaults to [crate::types::ctx::Context::DEFAULT_MAX_JOINS]
+    pub max_joins: Option<usize>,
+    /// sqlite version the analysed sql targets, such as "3.37.2", features introduced in later
+    /// versions are reported as [Rule::SqliteUnsupported]
+    pub sqlite_version: Option<Version>,
+    /// width a line may have before [Rule::LineLength] is emitted, defaults to
+    /// [crate::style::DEFAULT_MAX_LINE_LENGTH]
+    pub max_line_length: Option<usize>,
+    /// width of a tab when measuring lines, defaults to [crate::style::DEFAULT_TAB_WIDTH]
+    pub tab_width: Option<usize>,
+    /// pragma names known in addition to the pragmas of sqlite itself, such as pragmas of
+    /// extensions or custom builds, these are not reported as [Rule::UnknownPragma]
+    #[serde(default)]
+    pub pragmas: Vec<String>,
 }
 
 impl FromLua for Config {
     fn from_lua(value: mlua::Value, lua: &mlua::Lua) -> mlua::Result<Self> {
         let table: Table = lua.unpack(value)?;
         let disabled_rules: Vec<Rule> = table.get("disabled_rules").unwrap_or_else(|_| vec![]);
+        let enabled_rules: Vec<Rule> = table.get("enabled_rules").unwrap_or_else(|_| vec![]);
+        let min_severity: Option<Severity> = table.get("min_severity")?;
         let hooks: Option<Vec<Hook>> = table.get("hooks").ok();
+        let max_joins: Option<usize> = table.get("max_joins").ok();
+        let max_line_length: Option<usize> = table.get("max_line_length").ok();
+        let tab_width: Option<usize> = table.get("tab_width").ok();
+        let pragmas: Vec<String> = table.get("pragmas").unwrap_or_else(|_| vec![]);
+        let sqlite_version = match table.get::<Option<String>>("sqlite_version")? {
+            Some(version) => Some(version.parse::<Version>().map_err(mlua::Error::runtime)?),
+            None => None,
+        };
         Ok(Self {
             disabled_rules,
+            enabled_rules,
+            min_severity,
             hooks,
+            max_joins,
+            sqlite_version,
+            max_line_length,
+            tab_width,
+            pragmas,
         })
     }
 }
@@ -63,3 +115,258 @@ impl FromLua for Hook {
 
 impl UserData for Config {}
 impl UserData for Rule {}
+
+/// default of the `--config-timeout` flag, in milliseconds
+pub const DEFAULT_TIMEOUT_MS: u64 = 1000;
+/// amount of memory a configuration may allocate, in bytes
+pub const MEMORY_LIMIT: usize = 64 * 1024 * 1024;
+/// amount of lua vm instructions between checks of the timeout
+const TIMEOUT_CHECK_INTERVAL: u32 = 10_000;
+
+/// commented default configuration written by `--config-init`
+pub const TEMPLATE: &str = include_str!("leibniz.lua");
+
+/// writes [TEMPLATE] to file_name, refuses to overwrite an already existing file
+pub fn init(file_name: &str) -> Result<(), String> {
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(file_name)
+        .map_err(|err| match err.kind() {
+            std::io::ErrorKind::AlreadyExists => {
+                format!("'{}' already exists, refusing to overwrite it", file_name)
+            }
+            _ => format!("Issue trying to create '{}': [{}]", file_name, err),
+        })?;
+    file.write_all(TEMPLATE.as_bytes())
+        .map_err(|err| format!("Issue trying to write '{}': [{}]", file_name, err))
+}
+
+/// reads the toml configuration at file_name, its top level keys mirror the fields of the
+/// `leibniz` table of a lua configuration, except hooks
+pub fn load_toml(file_name: &str) -> Result<Config, String> {
+    let conf_str = fs::read_to_string(file_name).map_err(|err| {
+        format!(
+            "Issue trying to read configuration from '{}': [{}], falling back to default configuration",
+            file_name, err
+        )
+    })?;
+    toml::from_str(&conf_str).map_err(|err| {
+        format!(
+            "{}: falling back to default configuration, {}",
+            file_name, err
+        )
+    })
+}
+
+/// executes the configuration at file_name and extracts its `leibniz` table, aborts if executing
+/// the configuration takes longer than timeout or allocates more than [MEMORY_LIMIT] bytes, so a
+/// runaway configuration does not hang sqleibniz
+pub fn load(lua: &mlua::Lua, file_name: &str, timeout: Duration) -> Result<Config, String> {
+    let conf_str = fs::read_to_string(file_name).map_err(|err| {
+        format!(
+            "Issue trying to read configuration from '{}': [{}], falling back to default configuration",
+            file_name, err
+        )
+    })?;
+
+    lua.set_memory_limit(MEMORY_LIMIT)
+        .map_err(|err| format!("{}: {}", file_name, err))?;
+    let start = Instant::now();
+    lua.set_hook(
+        HookTriggers::new().every_nth_instruction(TIMEOUT_CHECK_INTERVAL),
+        move |_, _| {
+            if start.elapsed() > timeout {
+                Err(mlua::Error::runtime(format!(
+                    "configuration exceeded the timeout of {}ms",
+                    timeout.as_millis()
+                )))
+            } else {
+                Ok(VmState::Continue)
+            }
+        },
+    );
+    let result = lua.load(conf_str).set_name(file_name).exec();
+    // hooks defined in the configuration are executed after loading, thus the timeout only
+    // applies to executing the configuration itself
+    lua.remove_hook();
+    result.map_err(|err| {
+        format!(
+            "{}: falling back to default configuration, {}",
+            file_name, err
+        )
+    })?;
+
+    let raw_conf = lua
+        .globals()
+        .get::<mlua::Value>("leibniz")
+        .map_err(|err| format!("{}: {}", file_name, err))?;
+    if raw_conf.is_nil() {
+        return Err(format!(
+            "{}: leibniz table is missing from configuration",
+            file_name
+        ));
+    }
+    lua.unpack(raw_conf)
+        .map_err(|err| format!("{}: {}", file_name, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    /// writes input to a temporary configuration file and loads it
+    fn load(name: &str, input: &str) -> Result<super::Config, String> {
+        let path = std::env::temp_dir().join(format!("sqleibniz_config_test_{name}.lua"));
+        std::fs::write(&path, input).unwrap();
+        let lua = mlua::Lua::new();
+        let result = super::load(&lua, path.to_str().unwrap(), Duration::from_millis(100));
+        std::fs::remove_file(&path).unwrap();
+        result
+    }
+
+    /// writes input to a temporary toml configuration file and loads it
+    fn load_toml(name: &str, input: &str) -> Result<super::Config, String> {
+        let path = std::env::temp_dir().join(format!("sqleibniz_config_test_{name}.toml"));
+        std::fs::write(&path, input).unwrap();
+        let result = super::load_toml(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+        result
+    }
+
+    #[test]
+    fn loads_config() {
+        let config = load(
+            "loads_config",
+            r#"leibniz = { disabled_rules = { "Quirk" }, min_severity = "warning", max_joins = 4, sqlite_version = "3.37.2", max_line_length = 80, tab_width = 8, pragmas = { "cipher" } }"#,
+        )
+        .unwrap();
+        assert_eq!(config.disabled_rules, vec![super::Rule::Quirk]);
+        assert_eq!(config.min_severity, Some(super::Severity::Warning));
+        assert_eq!(config.max_joins, Some(4));
+        assert_eq!(config.sqlite_version, Some(super::Version::new(3, 37, 2)));
+        assert_eq!(config.max_line_length, Some(80));
+        assert_eq!(config.tab_width, Some(8));
+        assert_eq!(config.pragmas, vec!["cipher"]);
+    }
+
+    #[test]
+    fn toml_mirrors_lua() {
+        let lua = load(
+            "toml_mirrors_lua",
+            r#"leibniz = { disabled_rules = { "Quirk", "Unimplemented" }, enabled_rules = { "LineLength" }, min_severity = "warning", max_joins = 4, sqlite_version = "3.37.2", max_line_length = 80, tab_width = 8, pragmas = { "cipher" } }"#,
+        )
+        .unwrap();
+        let toml = load_toml(
+            "toml_mirrors_lua",
+            r#"
+disabled_rules = ["Quirk", "Unimplemented"]
+enabled_rules = ["LineLength"]
+min_severity = "warning"
+max_joins = 4
+sqlite_version = "3.37.2"
+max_line_length = 80
+tab_width = 8
+pragmas = ["cipher"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(lua.disabled_rules, toml.disabled_rules);
+        assert_eq!(lua.enabled_rules, toml.enabled_rules);
+        assert_eq!(lua.min_severity, toml.min_severity);
+        assert_eq!(lua.max_joins, toml.max_joins);
+        assert_eq!(lua.sqlite_version, toml.sqlite_version);
+        assert_eq!(lua.max_line_length, toml.max_line_length);
+        assert_eq!(lua.tab_width, toml.tab_width);
+        assert_eq!(lua.pragmas, toml.pragmas);
+    }
+
+    #[test]
+    fn empty_toml_is_default() {
+        let lua = load("empty_toml_is_default", "leibniz = {}").unwrap();
+        let toml = load_toml("empty_toml_is_default", "").unwrap();
+        assert_eq!(lua.disabled_rules, toml.disabled_rules);
+        assert_eq!(lua.enabled_rules, toml.enabled_rules);
+        assert_eq!(lua.min_severity, toml.min_severity);
+        assert_eq!(lua.max_joins, toml.max_joins);
+        assert_eq!(lua.sqlite_version, toml.sqlite_version);
+    }
+
+    #[test]
+    fn toml_errors() {
+        let err = load_toml("toml_unknown_rule", r#"disabled_rules = ["Quirks"]"#).unwrap_err();
+        assert!(
+            err.contains("falling back to default configuration"),
+            "{err}"
+        );
+        let err = load_toml("toml_bad_version", r#"sqlite_version = "3.x""#).unwrap_err();
+        assert!(err.contains("not a valid sqlite version"), "{err}");
+        let err = load_toml("toml_hooks", "hooks = []").unwrap_err();
+        assert!(err.contains("unknown field"), "{err}");
+    }
+
+    #[test]
+    fn invalid_sqlite_version() {
+        let err = load(
+            "invalid_sqlite_version",
+            r#"leibniz = { sqlite_version = "3.x" }"#,
+        )
+        .unwrap_err();
+        assert!(err.contains("not a valid sqlite version"), "{err}");
+    }
+
+    #[test]
+    fn invalid_min_severity() {
+        let err = load(
+            "invalid_min_severity",
+            r#"leibniz = { min_severity = "fatal" }"#,
+        )
+        .unwrap_err();
+        assert!(err.contains("Unknown severity"), "{err}");
+    }
+
+    #[test]
+    fn infinite_loop_times_out() {
+        let err = load("infinite_loop_times_out", "while true do end leibniz = {}").unwrap_err();
+        assert!(err.contains("exceeded the timeout of 100ms"), "{err}");
+        assert!(
+            err.contains("falling back to default configuration"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn memory_limit() {
+        let err = load(
+            "memory_limit",
+            "local t = {} while true do t[#t + 1] = string.rep('a', 1024) end",
+        )
+        .unwrap_err();
+        assert!(err.contains("memory"), "{err}");
+    }
+
+    #[test]
+    fn template_loads() {
+        let config = load("template_loads", super::TEMPLATE).unwrap();
+        assert_eq!(config.disabled_rules, vec![]);
+        assert_eq!(config.enabled_rules, vec![]);
+        assert_eq!(config.hooks.map(|h| h.len()), Some(0));
+    }
+
+    #[test]
+    fn init_refuses_to_overwrite() {
+        let path = std::env::temp_dir().join("sqleibniz_config_test_init.lua");
+        let path = path.to_str().unwrap();
+        let _ = std::fs::remove_file(path);
+        super::init(path).unwrap();
+        let err = super::init(path).unwrap_err();
+        std::fs::remove_file(path).unwrap();
+        assert!(err.contains("already exists"), "{err}");
+    }
+
+    #[test]
+    fn missing_leibniz_table() {
+        let err = load("missing_leibniz_table", "x = 1").unwrap_err();
+        assert!(err.contains("leibniz table is missing"), "{err}");
+    }
+}
@@ -1,17 +1,119 @@
 use std::collections::HashSet;
 
-use super::storage::SqliteStorageClass;
+use super::{Token, rules::Rule, version::Version};
+use crate::error::Error;
 
 pub struct Table {
     pub name: String,
-    pub columns: Vec<SqliteStorageClass>,
+    /// names of the columns known to exist in the table
+    pub columns: Vec<String>,
+    /// names of the columns declared NOT NULL without a DEFAULT, inserting a row without a value
+    /// for these fails
+    pub required: Vec<String>,
+}
+
+/// a savepoint on the transaction stack, see [Context::save_points]
+pub struct SavePoint {
+    pub name: String,
+    /// token of the SAVEPOINT that opened the savepoint
+    pub t: Token,
+}
+
+/// state of the explicit transaction at the currently analysed statement, see:
+/// https://www.sqlite.org/lang_transaction.html
+#[derive(Default)]
+pub enum Transaction {
+    /// no transaction was started via BEGIN yet
+    #[default]
+    None,
+    /// holds the token of the BEGIN that started the transaction
+    Open(Token),
+    /// holds the token of the COMMIT, END or ROLLBACK that ended the last transaction
+    Ended(Token),
 }
 
 /// Context holds information necessary for the analysis of sql statements.
+#[derive(Default)]
 pub struct Context {
+    /// name of the file currently analysed, used for emitting errors
+    pub file: String,
+    /// tables and views known to exist at the currently analysed statement, see
+    /// [Context::table]
     pub tables: Vec<Table>,
-    pub save_points: HashSet<String>,
+    /// savepoints opened via SAVEPOINT and not yet released, the innermost last, see:
+    /// https://www.sqlite.org/lang_savepoint.html
+    pub save_points: Vec<SavePoint>,
     pub databases: HashSet<String>,
+    /// amount of joins a single join clause may contain before [Rule::TooManyJoins] is emitted
+    pub max_joins: usize,
+    /// sqlite version the analysed sql targets, features introduced after it are reported
+    pub sqlite_version: Option<Version>,
+    /// the analysed file is a migration, see `--migration`
+    pub migration: bool,
+    pub transaction: Transaction,
+}
+
+impl Context {
+    /// default for [Context::max_joins]
+    pub const DEFAULT_MAX_JOINS: usize = 16;
+    /// sqlite refuses to join more than 64 tables, see:
+    /// https://www.sqlite.org/limits.html#max_join
+    pub const SQLITE_MAX_JOIN_TABLES: usize = 64;
+
+    pub fn new(file: &str) -> Self {
+        Self {
+            file: file.to_string(),
+            max_joins: Self::DEFAULT_MAX_JOINS,
+            ..Default::default()
+        }
+    }
+
+    /// looks up a known table or view, sqlite compares names case insensitively
+    pub fn table(&self, name: &str) -> Option<&Table> {
+        self.tables
+            .iter()
+            .find(|t| t.name.eq_ignore_ascii_case(name))
+    }
+
+    /// registers name as a known table or view, if not already known, and returns it
+    pub fn add_table(&mut self, name: &str) -> &mut Table {
+        let index = match self
+            .tables
+            .iter()
+            .position(|t| t.name.eq_ignore_ascii_case(name))
+        {
+            Some(index) => index,
+            None => {
+                self.tables.push(Table {
+                    name: name.to_string(),
+                    columns: vec![],
+                    required: vec![],
+                });
+                self.tables.len() - 1
+            }
+        };
+        &mut self.tables[index]
+    }
+
+    /// forgets the table or view name, if known
+    pub fn remove_table(&mut self, name: &str) {
+        self.tables.retain(|t| !t.name.eq_ignore_ascii_case(name));
+    }
+
+    pub fn err(&self, msg: impl Into<String>, note: &str, start: &Token, rule: Rule) -> Error {
+        Error {
+            improved_line: None,
+            file: self.file.clone(),
+            line: start.line,
+            severity: rule.severity(),
+            rule,
+            note: note.into(),
+            msg: msg.into(),
+            start: start.start,
+            end: start.end,
+            doc_url: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
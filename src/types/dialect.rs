@@ -0,0 +1,83 @@
+/// where a foreign dialect construct is expected to occur
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Position {
+    /// used as the type of a column: `id SERIAL`
+    Type,
+    /// used as a column constraint: `id INTEGER AUTO_INCREMENT`
+    ColumnConstraint,
+    /// used as a table option after the column definitions, always followed by `=`:
+    /// `ENGINE=InnoDB`
+    TableOption,
+}
+
+/// construct of another sql dialect sqlite does not support, commonly found in schemas migrated
+/// from MySQL or PostgreSQL
+#[derive(Debug)]
+pub struct Foreign {
+    pub name: &'static str,
+    pub dialect: &'static str,
+    pub position: Position,
+    /// the sqlite equivalent of the construct
+    pub note: &'static str,
+    pub doc: &'static str,
+}
+
+pub const FOREIGN: &[Foreign] = &[
+    Foreign {
+        name: "AUTO_INCREMENT",
+        dialect: "MySQL",
+        position: Position::ColumnConstraint,
+        note: "sqlite assigns a unique rowid to every row, declare the column as INTEGER PRIMARY KEY to alias it, add AUTOINCREMENT only if rowids must never be reused",
+        doc: "https://www.sqlite.org/autoinc.html",
+    },
+    Foreign {
+        name: "IDENTITY",
+        dialect: "SQL Server",
+        position: Position::ColumnConstraint,
+        note: "sqlite assigns a unique rowid to every row, declare the column as INTEGER PRIMARY KEY to alias it, add AUTOINCREMENT only if rowids must never be reused",
+        doc: "https://www.sqlite.org/autoinc.html",
+    },
+    Foreign {
+        name: "SERIAL",
+        dialect: "PostgreSQL",
+        position: Position::Type,
+        note: "sqlite has no serial types, declare the column as INTEGER PRIMARY KEY to alias the automatically assigned rowid",
+        doc: "https://www.sqlite.org/autoinc.html",
+    },
+    Foreign {
+        name: "BIGSERIAL",
+        dialect: "PostgreSQL",
+        position: Position::Type,
+        note: "sqlite has no serial types, declare the column as INTEGER PRIMARY KEY to alias the automatically assigned rowid, sqlite integers are always 64 bit",
+        doc: "https://www.sqlite.org/autoinc.html",
+    },
+    Foreign {
+        name: "SMALLSERIAL",
+        dialect: "PostgreSQL",
+        position: Position::Type,
+        note: "sqlite has no serial types, declare the column as INTEGER PRIMARY KEY to alias the automatically assigned rowid",
+        doc: "https://www.sqlite.org/autoinc.html",
+    },
+    Foreign {
+        name: "ENGINE",
+        dialect: "MySQL",
+        position: Position::TableOption,
+        note: "sqlite has a single storage engine, remove the ENGINE table option",
+        doc: "https://www.sqlite.org/lang_createtable.html",
+    },
+    Foreign {
+        name: "CHARSET",
+        dialect: "MySQL",
+        position: Position::TableOption,
+        note: "sqlite stores all text in the database encoding, remove the CHARSET table option and use PRAGMA encoding if needed",
+        doc: "https://www.sqlite.org/pragma.html#pragma_encoding",
+    },
+];
+
+/// returns the foreign dialect construct named name expected at position, compared case
+/// insensitive
+pub fn lookup(name: &str, position: Position) -> Option<&'static Foreign> {
+    FOREIGN
+        .iter()
+        .find(|f| f.position == position && f.name.eq_ignore_ascii_case(name))
+}
@@ -0,0 +1,83 @@
+/// A sqlite built-in function, see: https://www.sqlite.org/lang_corefunc.html and
+/// https://www.sqlite.org/lang_aggfunc.html
+pub struct Function {
+    pub name: &'static str,
+    /// the parameters in call order, the last parameter repeats if the function is variadic
+    pub params: &'static [Param],
+    pub variadic: bool,
+    pub doc: &'static str,
+}
+
+pub struct Param {
+    pub name: &'static str,
+    pub doc: &'static str,
+}
+
+macro_rules! function {
+    ($name:literal, $doc:literal, [$($param:literal: $param_doc:literal),*]) => {
+        function!($name, $doc, [$($param: $param_doc),*], false)
+    };
+    ($name:literal, $doc:literal, [$($param:literal: $param_doc:literal),*], $variadic:literal) => {
+        Function {
+            name: $name,
+            params: &[$(Param { name: $param, doc: $param_doc }),*],
+            variadic: $variadic,
+            doc: $doc,
+        }
+    };
+}
+
+/// curated list of the commonly used sqlite built-in scalar and aggregate functions
+pub const BUILTINS: &[Function] = &[
+    function!("abs", "Returns the absolute value of the numeric argument X.", ["X": "numeric value"]),
+    function!("avg", "Returns the average value of all non-NULL X within a group.", ["X": "value to average"]),
+    function!("char", "Returns a string composed of characters having the unicode code point values of its integer arguments.", ["X1": "unicode code point"], true),
+    function!("coalesce", "Returns a copy of its first non-NULL argument, or NULL if all arguments are NULL.", ["X": "first candidate", "Y": "further candidates"], true),
+    function!("count", "Returns the number of times that X is not NULL in a group, count(*) returns the total number of rows in the group.", ["X": "value to count, or *"]),
+    function!("format", "Works like the printf() function from the standard C library.", ["FORMAT": "format string", "ARGS": "values substituted into FORMAT"], true),
+    function!("glob", "Equivalent to the expression Y GLOB X.", ["X": "pattern", "Y": "string to match"]),
+    function!("group_concat", "Returns a string which is the concatenation of all non-NULL values of X, separated by Y or a comma.", ["X": "value to concatenate", "Y": "separator, defaults to ','"]),
+    function!("hex", "Interprets its argument as a BLOB and returns a string which is the upper-case hexadecimal rendering of the content of that blob.", ["X": "value to render"]),
+    function!("ifnull", "Returns a copy of its first non-NULL argument, or NULL if both arguments are NULL.", ["X": "value", "Y": "fallback if X is NULL"]),
+    function!("iif", "Returns Y if X is true, and Z otherwise.", ["X": "condition", "Y": "result if X is true", "Z": "result if X is false"]),
+    function!("instr", "Finds the first occurrence of string Y within string X and returns the number of prior characters plus 1, or 0 if Y is nowhere found within X.", ["X": "string to search in", "Y": "string to search for"]),
+    function!("julianday", "Returns the fractional number of days since noon in Greenwich on November 24, 4714 B.C.", ["TIME": "time value", "MODIFIERS": "time modifiers"], true),
+    function!(
+        "last_insert_rowid",
+        "Returns the ROWID of the last row insert from the database connection which invoked the function.",
+        []
+    ),
+    function!("length", "For a string value X, returns the number of characters prior to the first NUL character, for a blob value X the number of bytes.", ["X": "string or blob"]),
+    function!("like", "Equivalent to the expression Y LIKE X [ESCAPE Z].", ["X": "pattern", "Y": "string to match", "Z": "escape character"]),
+    function!("lower", "Returns a copy of string X with all ASCII characters converted to lower case.", ["X": "string"]),
+    function!("ltrim", "Returns a string formed by removing any and all characters that appear in Y from the left side of X.", ["X": "string", "Y": "characters to remove, defaults to spaces"]),
+    function!("max", "The multi-argument max() returns the argument with the maximum value, the single argument max() returns the maximum value of all values in the group.", ["X": "value", "Y": "further values"], true),
+    function!("min", "The multi-argument min() returns the argument with the minimum value, the single argument min() returns the minimum value of all values in the group.", ["X": "value", "Y": "further values"], true),
+    function!("nullif", "Returns its first argument if the arguments are different and NULL if the arguments are the same.", ["X": "value", "Y": "value compared to X"]),
+    function!("printf", "Alias for format().", ["FORMAT": "format string", "ARGS": "values substituted into FORMAT"], true),
+    function!("quote", "Returns the text of an SQL literal which is the value of its argument suitable for inclusion into an SQL statement.", ["X": "value to quote"]),
+    function!(
+        "random",
+        "Returns a pseudo-random integer between -9223372036854775808 and +9223372036854775807.",
+        []
+    ),
+    function!("randomblob", "Returns an N-byte blob containing pseudo-random bytes.", ["N": "amount of bytes"]),
+    function!("replace", "Returns a string formed by substituting string Z for every occurrence of string Y in string X.", ["X": "string", "Y": "string to replace", "Z": "replacement"]),
+    function!("round", "Returns a floating-point value X rounded to Y digits to the right of the decimal point.", ["X": "numeric value", "Y": "digits, defaults to 0"]),
+    function!("rtrim", "Returns a string formed by removing any and all characters that appear in Y from the right side of X.", ["X": "string", "Y": "characters to remove, defaults to spaces"]),
+    function!("sign", "Returns -1, 0, or +1 if the argument X is a numeric value that is negative, zero, or positive respectively.", ["X": "numeric value"]),
+    function!("substr", "Returns a substring of input string X that begins with the Y-th character and which is Z characters long.", ["X": "string", "Y": "start, 1 based", "Z": "length, defaults to the rest of X"]),
+    function!("substring", "Alias for substr().", ["X": "string", "Y": "start, 1 based", "Z": "length, defaults to the rest of X"]),
+    function!("sum", "Returns the sum of all non-NULL values in the group.", ["X": "value to sum"]),
+    function!("total", "Returns the sum of all non-NULL values in the group as a floating point value, 0.0 for groups of only NULL values.", ["X": "value to sum"]),
+    function!("trim", "Returns a string formed by removing any and all characters that appear in Y from both ends of X.", ["X": "string", "Y": "characters to remove, defaults to spaces"]),
+    function!("typeof", "Returns a string that indicates the datatype of the expression X: null, integer, real, text, or blob.", ["X": "value"]),
+    function!("unicode", "Returns the numeric unicode code point corresponding to the first character of the string X.", ["X": "string"]),
+    function!("upper", "Returns a copy of string X with all ASCII characters converted to upper case.", ["X": "string"]),
+    function!("zeroblob", "Returns a BLOB consisting of N bytes of 0x00.", ["N": "amount of bytes"]),
+];
+
+/// looks up a built-in function by its case insensitive name
+pub fn lookup(name: &str) -> Option<&'static Function> {
+    BUILTINS.iter().find(|f| f.name.eq_ignore_ascii_case(name))
+}
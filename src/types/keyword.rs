@@ -311,6 +311,11 @@ static KEYWORDS: &[&str] = &[
 ];
 
 impl Keyword {
+    /// all keywords, as their uppercase textual representation
+    pub fn all() -> &'static [&'static str] {
+        KEYWORDS
+    }
+
     /// suggestions returns three suggestions based on their smallest Levenshtein_distance computed via lev::distance
     pub fn suggestions(s: &str) -> Vec<&str> {
         let input = s.to_uppercase();
@@ -331,6 +336,7 @@ impl Keyword {
         best.into_iter().map(|(k, _)| k).collect()
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Keyword> {
         Some(match s.to_uppercase().as_str() {
             "ABORT" => Keyword::ABORT,
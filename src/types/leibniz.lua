@@ -0,0 +1,58 @@
+-- sqleibniz configuration, generated via `sqleibniz --config-init`, consult:
+-- https://www.lua.org/manual/5.4/ or https://learnxinyminutes.com/docs/lua/ for
+-- syntax help and https://github.com/xnacly/sqleibniz for all available options
+leibniz = {
+    -- diagnostics to ignore, by their rule, the same as -D
+    disabled_rules = {
+        -- "NoContent",               -- source file is empty
+        -- "NoStatements",            -- source file contains no statements
+        -- "Unimplemented",           -- construct is not implemented yet
+        -- "BadSqleibnizInstruction", -- source file contains a bad sqleibniz instruction
+        -- "SqliteUnsupported",       -- source file uses sql features sqlite does not support
+        -- "Quirk",                   -- sqlite or sql quirk: https://www.sqlite.org/quirks.html
+        -- "UnknownKeyword",          -- an unknown keyword was encountered
+        -- "UnterminatedString",      -- a not closed string was found
+        -- "UnknownCharacter",        -- an unknown character was found
+        -- "InvalidNumericLiteral",   -- an invalid numeric literal was found
+        -- "InvalidBlob",             -- an invalid blob literal was found
+        -- "Syntax",                  -- a structure with incorrect syntax was found
+        -- "Semicolon",               -- a semicolon is missing
+        -- "TooManyJoins",            -- a join clause contains more joins than max_joins
+        -- "ReservedTableName",       -- a table name uses the reserved sqlite_ prefix
+        -- "UnknownPragma",           -- a pragma name sqlite does not know
+    },
+    -- opt-in diagnostics, these are disabled unless enabled here or via -E
+    enabled_rules = {
+        -- "ImplicitInsertColumns", -- INSERT is missing an explicit column list
+        -- "LineLength",            -- a line is wider than max_line_length
+    },
+    -- errors with a severity below are not reported, one of "hint", "info",
+    -- "warning" or "error", the same as --min-severity
+    -- min_severity = "hint",
+    -- amount of joins a single join clause may contain before TooManyJoins is
+    -- emitted, sqlite itself refuses to join more than 64 tables
+    -- max_joins = 16,
+    -- maximum width of a line before LineLength is emitted
+    -- max_line_length = 120,
+    -- width of a tab when measuring lines, tabs advance to the next multiple
+    -- tab_width = 4,
+    -- pragma names accepted in addition to the pragmas sqlite knows, such as
+    -- pragmas of extensions or custom builds, these are not UnknownPragma
+    -- pragmas = { "cipher" },
+    -- sqlite version the sql is written for, features introduced in later
+    -- versions are reported as SqliteUnsupported
+    -- sqlite_version = "3.38.0",
+    -- custom rules written in lua, each hook is called with the nodes named
+    -- like its `node` field, or every node if `node` is omitted
+    hooks = {
+        -- {
+        --     name = "idents should be lowercase",
+        --     node = "literal",
+        --     hook = function(node)
+        --         if node.kind == "ident" and string.match(node.content, "%u") then
+        --             error("All idents should be lowercase")
+        --         end
+        --     end
+        -- },
+    },
+}
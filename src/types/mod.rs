@@ -1,8 +1,12 @@
 pub mod config;
 pub mod ctx;
+pub mod dialect;
+pub mod functions;
 mod keyword;
+pub mod pragma;
 pub mod rules;
 pub mod storage;
+pub mod version;
 
 #[allow(unused_imports)]
 /// this shit is really fucking idiotic, but i have to reexport
@@ -36,7 +40,10 @@ pub enum Type {
     /// ## See:
     /// - https://www.sqlite.org/lang_expr.html#literal_values_constants_
     /// - https://www.sqlite.org/syntax/numeric-literal.html
-    Number(f64),
+    Integer(i64),
+    /// A numeric literal with a decimal point or an exponentiation clause, or a decimal literal
+    /// not fitting into an [Type::Integer], see [Type::Integer] for the whole distinction.
+    Float(f64),
     ///  A string constant is formed by enclosing the string in single quotes (').
     ///  C-style escapes using the backslash character are not supported because they are not standard SQL.
     ///
@@ -65,11 +72,14 @@ pub enum Type {
     ///
     ///
     /// ```text
-    ///     :&<str>
+    ///     :<&str>
     ///     @<&str>
     ///     $<&str>
     /// ```
     ///
+    /// Holds the name including its prefix, since sqlite treats `:a` and `$a` as distinct
+    /// parameters.
+    ///
     /// ## See:
     /// - https://www.sqlite.org/limits.html#max_variable_number
     /// - https://www.sqlite.org/c3ref/bind_blob.html
@@ -83,6 +93,8 @@ pub enum Type {
     ///     ?<usize>
     /// ```
     ///
+    /// A bare `?` holds [Type::PARAM_AUTO], sqlite assigns it the next free parameter number.
+    ///
     /// ## See:
     /// - https://www.sqlite.org/limits.html#max_variable_number
     /// - https://www.sqlite.org/c3ref/bind_blob.html
@@ -95,7 +107,6 @@ pub enum Type {
     Percent,
     Comma,
     Equal,
-    Question,
     Colon,
     At,
     Dollar,
@@ -104,21 +115,69 @@ pub enum Type {
     BracketLeft,
     BracketRight,
 
+    // operators, see: https://www.sqlite.org/lang_expr.html#operators_and_parse_affecting_attributes
+    Plus,
+    Minus,
+    Slash,
+    /// `<`
+    LessThan,
+    /// `>`
+    GreaterThan,
+    /// `<=`
+    LessEq,
+    /// `>=`
+    GreaterEq,
+    /// both `!=` and `<>`
+    NotEqual,
+    /// `==`
+    DoubleEqual,
+    /// `||`, string concatenation
+    Concat,
+    /// `&`, bitwise and
+    Ampersand,
+    /// `|`, bitwise or
+    Pipe,
+    /// `~`, bitwise not
+    Tilde,
+    /// `<<`
+    ShiftLeft,
+    /// `>>`
+    ShiftRight,
+    /// `->`, extracts a json subcomponent as json, sqlite 3.38+
+    Arrow,
+    /// `->>`, extracts a json subcomponent as a sql value, sqlite 3.38+
+    DoubleArrow,
+
     /// Instructs the parser to skip all token until Type::Semicolon is hit
     InstructionExpect,
+    /// Instructs the parser to assert the following statement is parsed to the named node, see
+    /// [crate::parser::nodes::Node::name]
+    InstructionNode(String),
+    /// Instructs sqleibniz to not report errors of the rule for the following statement
+    InstructionDisable(rules::Rule),
 
     Eof,
 }
 
 use std::cmp::PartialEq;
 
+impl Type {
+    /// the counter of a bare `?`, sqlite numbers parameters starting at 1, thus 0 is never the
+    /// counter of a `?NNN` parameter
+    pub const PARAM_AUTO: usize = 0;
+    /// mirrors the default SQLITE_MAX_VARIABLE_NUMBER, see:
+    /// https://www.sqlite.org/limits.html#max_variable_number
+    pub const MAX_PARAM: usize = 32766;
+}
+
 impl PartialEq for Type {
     fn eq(&self, other: &Self) -> bool {
         use Type::*;
         match (self, other) {
             (Keyword(a), Keyword(b)) => a == b,
             (Ident(a), Ident(b)) => a == b,
-            (Number(a), Number(b)) => a == b,
+            (Integer(a), Integer(b)) => a == b,
+            (Float(a), Float(b)) => a == b,
             (String(a), String(b)) => a == b,
             (Blob(a), Blob(b)) => a == b,
             (Boolean(a), Boolean(b)) => a == b,
@@ -130,7 +189,6 @@ impl PartialEq for Type {
             (Percent, Percent) => true,
             (Comma, Comma) => true,
             (Equal, Equal) => true,
-            (Question, Question) => true,
             (Colon, Colon) => true,
             (At, At) => true,
             (Dollar, Dollar) => true,
@@ -138,7 +196,26 @@ impl PartialEq for Type {
             (BraceRight, BraceRight) => true,
             (BracketLeft, BracketLeft) => true,
             (BracketRight, BracketRight) => true,
+            (Plus, Plus) => true,
+            (Minus, Minus) => true,
+            (Slash, Slash) => true,
+            (LessThan, LessThan) => true,
+            (GreaterThan, GreaterThan) => true,
+            (LessEq, LessEq) => true,
+            (GreaterEq, GreaterEq) => true,
+            (NotEqual, NotEqual) => true,
+            (DoubleEqual, DoubleEqual) => true,
+            (Concat, Concat) => true,
+            (Ampersand, Ampersand) => true,
+            (Pipe, Pipe) => true,
+            (Tilde, Tilde) => true,
+            (ShiftLeft, ShiftLeft) => true,
+            (ShiftRight, ShiftRight) => true,
+            (Arrow, Arrow) => true,
+            (DoubleArrow, DoubleArrow) => true,
             (InstructionExpect, InstructionExpect) => true,
+            (InstructionNode(a), InstructionNode(b)) => a == b,
+            (InstructionDisable(a), InstructionDisable(b)) => a == b,
             (Eof, Eof) => true,
             _ => false,
         }
@@ -0,0 +1,175 @@
+use crate::lev;
+
+/// all pragmas sqlite knows, including deprecated and debugging pragmas, see:
+/// https://www.sqlite.org/pragma.html#toc
+pub const PRAGMAS: &[&str] = &[
+    "analysis_limit",
+    "application_id",
+    "auto_vacuum",
+    "automatic_index",
+    "busy_timeout",
+    "cache_size",
+    "cache_spill",
+    "case_sensitive_like",
+    "cell_size_check",
+    "checkpoint_fullfsync",
+    "collation_list",
+    "compile_options",
+    "count_changes",
+    "data_store_directory",
+    "data_version",
+    "database_list",
+    "default_cache_size",
+    "defer_foreign_keys",
+    "empty_result_callbacks",
+    "encoding",
+    "foreign_key_check",
+    "foreign_key_list",
+    "foreign_keys",
+    "freelist_count",
+    "full_column_names",
+    "fullfsync",
+    "function_list",
+    "hard_heap_limit",
+    "ignore_check_constraints",
+    "incremental_vacuum",
+    "index_info",
+    "index_list",
+    "index_xinfo",
+    "integrity_check",
+    "journal_mode",
+    "journal_size_limit",
+    "legacy_alter_table",
+    "legacy_file_format",
+    "locking_mode",
+    "max_page_count",
+    "mmap_size",
+    "module_list",
+    "optimize",
+    "page_count",
+    "page_size",
+    "parser_trace",
+    "pragma_list",
+    "query_only",
+    "quick_check",
+    "read_uncommitted",
+    "recursive_triggers",
+    "reverse_unordered_selects",
+    "schema_version",
+    "secure_delete",
+    "short_column_names",
+    "shrink_memory",
+    "soft_heap_limit",
+    "stats",
+    "synchronous",
+    "table_info",
+    "table_list",
+    "table_xinfo",
+    "temp_store",
+    "temp_store_directory",
+    "threads",
+    "trusted_schema",
+    "user_version",
+    "vdbe_addoptrace",
+    "vdbe_debug",
+    "vdbe_listing",
+    "vdbe_trace",
+    "wal_autocheckpoint",
+    "wal_checkpoint",
+    "writable_schema",
+];
+
+/// returns true if name is a pragma sqlite knows
+pub fn is_known(name: &str) -> bool {
+    PRAGMAS.contains(&name.to_lowercase().as_str())
+}
+
+/// returns the known pragma with the smallest Levenshtein distance to name, computed via
+/// lev::distance
+pub fn suggestion(name: &str) -> &'static str {
+    let name = name.to_lowercase();
+    PRAGMAS
+        .iter()
+        .min_by_key(|pragma| lev::distance(name.as_bytes(), pragma.as_bytes()))
+        .copied()
+        .unwrap_or_default()
+}
+
+/// pragmas accepting a boolean value, see: https://www.sqlite.org/pragma.html
+pub const BOOLEAN_PRAGMAS: &[&str] = &[
+    "automatic_index",
+    "case_sensitive_like",
+    "cell_size_check",
+    "checkpoint_fullfsync",
+    "count_changes",
+    "defer_foreign_keys",
+    "empty_result_callbacks",
+    "foreign_keys",
+    "full_column_names",
+    "fullfsync",
+    "ignore_check_constraints",
+    "legacy_alter_table",
+    "legacy_file_format",
+    "query_only",
+    "read_uncommitted",
+    "recursive_triggers",
+    "reverse_unordered_selects",
+    "short_column_names",
+    "trusted_schema",
+    "writable_schema",
+];
+
+/// spellings sqlite accepts for boolean pragma values besides integers, compared case
+/// insensitive, see: https://www.sqlite.org/pragma.html#syntax
+pub const BOOLEAN_SPELLINGS: &[&str] = &["yes", "no", "true", "false", "on", "off"];
+
+/// returns true if name is a pragma accepting a boolean value
+pub fn is_boolean(name: &str) -> bool {
+    BOOLEAN_PRAGMAS.contains(&name.to_lowercase().as_str())
+}
+
+/// returns true if value is a boolean spelling sqlite accepts for boolean pragmas
+pub fn is_boolean_spelling(value: &str) -> bool {
+    BOOLEAN_SPELLINGS.contains(&value.to_lowercase().as_str())
+}
+
+/// pragmas only affecting or inspecting the current connection, these do not belong in a
+/// migration, all other pragmas are considered to change the schema or the database file, see:
+/// https://www.sqlite.org/pragma.html
+pub const RUNTIME_PRAGMAS: &[&str] = &[
+    "analysis_limit",
+    "busy_timeout",
+    "cache_size",
+    "cache_spill",
+    "collation_list",
+    "compile_options",
+    "data_version",
+    "database_list",
+    "foreign_key_check",
+    "foreign_key_list",
+    "function_list",
+    "hard_heap_limit",
+    "incremental_vacuum",
+    "index_info",
+    "index_list",
+    "index_xinfo",
+    "integrity_check",
+    "mmap_size",
+    "module_list",
+    "optimize",
+    "pragma_list",
+    "quick_check",
+    "shrink_memory",
+    "soft_heap_limit",
+    "table_info",
+    "table_list",
+    "table_xinfo",
+    "temp_store",
+    "threads",
+    "wal_checkpoint",
+];
+
+/// returns true if name is a pragma only affecting or inspecting the current connection
+pub fn is_runtime(name: &str) -> bool {
+    RUNTIME_PRAGMAS.contains(&name.to_lowercase().as_str())
+}
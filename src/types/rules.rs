@@ -1,5 +1,8 @@
-#[derive(Debug, PartialEq, Clone, serde::Serialize)]
-/// Rule is attached to each error and can be supplied to sqleibniz via the Config structure serialized in ./leibniz.toml
+use crate::error::Color;
+
+#[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
+/// Rule is attached to each error and can be supplied to sqleibniz via the Config structure
+/// deserialized from ./leibniz.lua or a toml configuration
 #[derive(clap::ValueEnum)]
 pub enum Rule {
     /// Source file is empty
@@ -29,13 +32,99 @@ pub enum Rule {
     Syntax,
     /// The source file is missing a semicolon
     Semicolon,
+    /// INSERT without an explicit column list, opt-in via `enabled_rules` or `-E`
+    ImplicitInsertColumns,
+    /// A join clause contains more joins than configured via `max_joins`
+    TooManyJoins,
+    /// A table is named with the `sqlite_` prefix reserved for sqlites internal tables
+    ReservedTableName,
+    /// A PRAGMA name is not a pragma sqlite knows
+    UnknownPragma,
+    /// A line is wider than configured via `max_line_length`, opt-in via `enabled_rules` or `-E`
+    LineLength,
 }
 
 impl mlua::FromLua for Rule {
+    fn from_lua(value: mlua::Value, lua: &mlua::Lua) -> mlua::Result<Self> {
+        let value: String = lua.unpack(value)?;
+
+        Self::from_name(&value).ok_or_else(|| mlua::Error::FromLuaConversionError {
+            from: "string",
+            to: "sqleibniz::rules::Rule".into(),
+            message: Some("Unknown rule name".into()),
+        })
+    }
+}
+
+/// Severity of the errors of a [Rule], ordered from least to most severe
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Clone,
+    Copy,
+    serde::Serialize,
+    serde::Deserialize,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Stylistic suggestions
+    Hint,
+    /// Noteworthy but intentional sql
+    Info,
+    /// Questionable sql sqlite executes regardless
+    Warning,
+    /// Sql sqlite refuses to execute
+    Error,
+}
+
+impl mlua::FromLua for Severity {
     fn from_lua(value: mlua::Value, lua: &mlua::Lua) -> mlua::Result<Self> {
         let value: String = lua.unpack(value)?;
 
         Ok(match value.as_str() {
+            "hint" => Self::Hint,
+            "info" => Self::Info,
+            "warning" => Self::Warning,
+            "error" => Self::Error,
+            _ => {
+                return Err(mlua::Error::FromLuaConversionError {
+                    from: "string",
+                    to: "sqleibniz::rules::Severity".into(),
+                    message: Some("Unknown severity, expected hint, info, warning or error".into()),
+                });
+            }
+        })
+    }
+}
+
+impl Severity {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Hint => "hint",
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            Self::Hint => Color::Grey,
+            Self::Info => Color::Cyan,
+            Self::Warning => Color::Yellow,
+            Self::Error => Color::Red,
+        }
+    }
+}
+
+impl Rule {
+    /// resolves a rule by its [Rule::name]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
             "NoContent" => Self::NoContent,
             "NoStatements" => Self::NoStatements,
             "Unimplemented" => Self::Unimplemented,
@@ -49,18 +138,15 @@ impl mlua::FromLua for Rule {
             "UnknownKeyword" => Self::UnknownKeyword,
             "SqliteUnsupported" => Self::SqliteUnsupported,
             "Quirk" => Self::Quirk,
-            _ => {
-                return Err(mlua::Error::FromLuaConversionError {
-                    from: "string",
-                    to: "sqleibniz::rules::Rule".into(),
-                    message: Some("Unknown rule name".into()),
-                });
-            }
+            "ImplicitInsertColumns" => Self::ImplicitInsertColumns,
+            "TooManyJoins" => Self::TooManyJoins,
+            "ReservedTableName" => Self::ReservedTableName,
+            "UnknownPragma" => Self::UnknownPragma,
+            "LineLength" => Self::LineLength,
+            _ => return None,
         })
     }
-}
 
-impl Rule {
     pub fn name(&self) -> &str {
         match self {
             Self::NoContent => "NoContent",
@@ -76,6 +162,11 @@ impl Rule {
             Self::BadSqleibnizInstruction => "BadSqleibnizInstruction",
             Self::UnknownKeyword => "UnknownKeyword",
             Self::SqliteUnsupported => "SqliteUnsupported",
+            Self::ImplicitInsertColumns => "ImplicitInsertColumns",
+            Self::TooManyJoins => "TooManyJoins",
+            Self::ReservedTableName => "ReservedTableName",
+            Self::UnknownPragma => "UnknownPragma",
+            Self::LineLength => "LineLength",
         }
     }
 
@@ -98,6 +189,42 @@ impl Rule {
             Self::Quirk => "Sqlite or SQL quirk: https://www.sqlite.org/quirks.html",
             Self::UnknownKeyword => "Source file contains an unknown keyword",
             Self::SqliteUnsupported => "Source file uses sql features sqlite does not support",
+            Self::ImplicitInsertColumns => "INSERT is missing an explicit column list",
+            Self::TooManyJoins => "A join clause contains more joins than configured",
+            Self::ReservedTableName => "A table name uses the reserved sqlite_ prefix",
+            Self::UnknownPragma => "A PRAGMA name is not a pragma sqlite knows",
+            Self::LineLength => "A line is wider than configured",
+        }
+    }
+
+    /// severity of the errors of the rule, rules reporting sql sqlite refuses to execute are
+    /// errors, while questionable but valid sql is a warning
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::NoContent
+            | Self::NoStatements
+            | Self::Unimplemented
+            | Self::Quirk
+            | Self::TooManyJoins
+            | Self::UnknownPragma => Severity::Warning,
+            Self::ImplicitInsertColumns => Severity::Info,
+            Self::LineLength => Severity::Hint,
+            Self::UnterminatedString
+            | Self::UnknownCharacter
+            | Self::InvalidNumericLiteral
+            | Self::InvalidBlob
+            | Self::Syntax
+            | Self::Semicolon
+            | Self::BadSqleibnizInstruction
+            | Self::UnknownKeyword
+            | Self::SqliteUnsupported
+            | Self::ReservedTableName => Severity::Error,
         }
     }
+
+    /// opt-in rules are not reported unless enabled via the configurations `enabled_rules` or
+    /// `-E`
+    pub fn opt_in(&self) -> bool {
+        matches!(self, Self::ImplicitInsertColumns | Self::LineLength)
+    }
 }
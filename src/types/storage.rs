@@ -42,6 +42,7 @@ impl Display for SqliteStorageClass {
 
 impl SqliteStorageClass {
     /// https://sqlite.org/datatype3.html#determination_of_column_affinity
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
         if s.contains_any(vec!["VARCHAR", "CLOB", "TEXT"]) {
             Self::Text
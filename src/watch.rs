@@ -0,0 +1,184 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::mpsc::{Receiver, RecvTimeoutError, Sender},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use notify::{EventKind, RecursiveMode, Watcher};
+
+/// editors often write a file in several steps, events arriving within this duration of each
+/// other are handled as a single change
+pub const DEBOUNCE: Duration = Duration::from_millis(50);
+
+/// Event is received by [run], produced by [watch] and the ctrl-c handler
+#[derive(Debug)]
+pub enum Event {
+    /// the file at the path was modified or replaced
+    Modified(PathBuf),
+    /// stop watching, sent on ctrl-c
+    Stop,
+}
+
+/// watch sends an [Event::Modified] for each file modified or created in the parent directories
+/// of paths, directories are watched instead of the files themselves, since editors often save
+/// by replacing the file. The watcher stops once dropped.
+pub fn watch(paths: &[PathBuf], events: Sender<Event>) -> notify::Result<impl Watcher> {
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else {
+            return;
+        };
+        if matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_)) {
+            for path in event.paths {
+                let _ = events.send(Event::Modified(path));
+            }
+        }
+    })?;
+    let mut dirs = paths
+        .iter()
+        .filter_map(|path| Some(canonical(path).parent()?.to_path_buf()))
+        .collect::<Vec<PathBuf>>();
+    dirs.sort();
+    dirs.dedup();
+    for dir in dirs {
+        watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+    }
+    Ok(watcher)
+}
+
+/// run calls changed with the index of each file of files modified according to events, until
+/// [Event::Stop] is received or all senders are dropped. files and the paths of events are
+/// compared canonicalized.
+pub fn run(files: &[PathBuf], events: &Receiver<Event>, mut changed: impl FnMut(usize)) {
+    let files = files.iter().map(|f| canonical(f)).collect::<Vec<_>>();
+    while let Ok(event) = events.recv() {
+        let mut batch = vec![];
+        let mut next = Ok(event);
+        loop {
+            match next {
+                Ok(Event::Modified(path)) => {
+                    let path = canonical(&path);
+                    if let Some(index) = files.iter().position(|f| *f == path)
+                        && !batch.contains(&index)
+                    {
+                        batch.push(index);
+                    }
+                }
+                Ok(Event::Stop) => return,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => {
+                    batch.into_iter().for_each(&mut changed);
+                    return;
+                }
+            }
+            next = events.recv_timeout(DEBOUNCE);
+        }
+        batch.into_iter().for_each(&mut changed);
+    }
+}
+
+/// canonical returns the canonical form of path, or path itself if it does not exist (anymore)
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// clock formats the current time of day in UTC as hh:mm:ss, for the summary of each analysis
+pub fn clock() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::PathBuf, sync::mpsc::channel, thread};
+
+    use super::{DEBOUNCE, Event, run};
+
+    /// returns the indexes passed to the change callback of run for events
+    fn changes(files: &[&str], events: Vec<Event>) -> Vec<usize> {
+        let files = files.iter().map(PathBuf::from).collect::<Vec<_>>();
+        let (sender, receiver) = channel();
+        for event in events {
+            sender.send(event).unwrap();
+        }
+        drop(sender);
+        let mut changed = vec![];
+        run(&files, &receiver, |index| changed.push(index));
+        changed
+    }
+
+    #[test]
+    fn modified_files_are_reported() {
+        assert_eq!(
+            changes(
+                &["/watch/a.sql", "/watch/b.sql"],
+                vec![Event::Modified("/watch/b.sql".into())]
+            ),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn unwatched_files_are_ignored() {
+        assert_eq!(
+            changes(
+                &["/watch/a.sql"],
+                vec![
+                    Event::Modified("/watch/a.sql.swp".into()),
+                    Event::Modified("/watch/other.sql".into())
+                ]
+            ),
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    fn bursts_are_debounced() {
+        assert_eq!(
+            changes(
+                &["/watch/a.sql", "/watch/b.sql"],
+                vec![
+                    Event::Modified("/watch/b.sql".into()),
+                    Event::Modified("/watch/a.sql".into()),
+                    Event::Modified("/watch/b.sql".into()),
+                ]
+            ),
+            vec![1, 0]
+        );
+    }
+
+    #[test]
+    fn stop_ends_the_loop() {
+        assert_eq!(
+            changes(
+                &["/watch/a.sql"],
+                vec![Event::Stop, Event::Modified("/watch/a.sql".into())]
+            ),
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    fn changes_after_the_debounce_are_reported_again() {
+        let (sender, receiver) = channel();
+        let events = thread::spawn(move || {
+            sender.send(Event::Modified("/watch/a.sql".into())).unwrap();
+            thread::sleep(DEBOUNCE * 4);
+            sender.send(Event::Modified("/watch/a.sql".into())).unwrap();
+        });
+        let mut changed = vec![];
+        run(&[PathBuf::from("/watch/a.sql")], &receiver, |index| {
+            changed.push(index)
+        });
+        events.join().unwrap();
+        assert_eq!(changed, vec![0, 0]);
+    }
+}
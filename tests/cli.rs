@@ -0,0 +1,529 @@
+use std::{
+    fs,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+/// writes content to a file in the systems temporary directory, prefixed with the name of the
+/// test to keep tests running in parallel from overwriting each others files
+fn fixture(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("sqleibniz_cli_{}.sql", name));
+    fs::write(&path, content).expect("failed to write fixture");
+    path
+}
+
+fn sqleibniz() -> Command {
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_sqleibniz"));
+    cmd.arg("--ignore-config");
+    cmd
+}
+
+#[test]
+fn count_only_prints_error_count() {
+    let path = fixture("count_only_bad", "VACUUM 5;\nVACUUM 6;\n");
+    let output = sqleibniz().arg("--count-only").arg(&path).output().unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "2\n");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn count_only_prints_zero_for_valid_files() {
+    let path = fixture("count_only_good", "VACUUM;\n");
+    let output = sqleibniz().arg("--count-only").arg(&path).output().unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "0\n");
+    assert!(output.status.success());
+}
+
+/// removes ansi escape sequences, so the output can be compared to a golden string
+fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // skip until the terminating 'm' of the color sequence
+            for c in chars.by_ref() {
+                if c == 'm' {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[test]
+fn missing_semicolon_at_eof_points_after_last_token() {
+    let path = fixture("missing_semicolon_eof", "VACUUM;\nSELECT 1");
+    let output = sqleibniz().arg(&path).output().unwrap();
+    let stdout = strip_ansi(&String::from_utf8_lossy(&output.stdout));
+    let error = stdout
+        .split("\n\n")
+        .find(|block| block.contains("error[Semicolon]"))
+        .expect("missing semicolon error");
+    let snippet = error
+        .lines()
+        .skip_while(|l| !l.starts_with(" 01 |"))
+        .take(4)
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert_eq!(
+        snippet,
+        " 01 | VACUUM;
+ 02 | SELECT 1
+    |         ^ error occurs here.
+    |"
+    );
+    assert!(error.contains(":2:9"));
+}
+
+#[test]
+fn bad_token_is_underlined_exactly() {
+    let path = fixture("bad_token_underline", "VACUUM;\nSELECT X'1G', 0x;\n");
+    let output = sqleibniz().arg(&path).output().unwrap();
+    let stdout = strip_ansi(&String::from_utf8_lossy(&output.stdout));
+    let carets = |rule: &str| {
+        let error = stdout
+            .split("\n\n")
+            .find(|block| block.contains(rule))
+            .unwrap_or_else(|| panic!("missing {rule} error"));
+        error
+            .lines()
+            .find(|l| l.ends_with("occurs here."))
+            .unwrap()
+            .to_string()
+    };
+    assert_eq!(
+        carets("error[InvalidBlob]"),
+        "    |           ~ error occurs here."
+    );
+}
+
+#[test]
+fn deeply_nested_expressions_do_not_overflow_the_stack() {
+    for (open, close) in [
+        ("(", ")"),
+        ("EXISTS (SELECT ", ")"),
+        ("CASE WHEN 1 THEN ", " END"),
+    ] {
+        let path = fixture(
+            "deeply_nested",
+            &format!("SELECT {}1{};\n", open.repeat(5000), close.repeat(5000)),
+        );
+        let output = sqleibniz().arg("--count-only").arg(&path).output().unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "1\n", "{open}");
+    }
+}
+
+#[test]
+fn context_lines_limit_the_shown_lines() {
+    let path = fixture(
+        "context_lines",
+        "VACUUM;\nVACUUM;\nVACUUM 5;\nVACUUM;\nVACUUM;\n",
+    );
+    let shown = |context: &str| {
+        let output = sqleibniz()
+            .args(["--context-lines", context])
+            .arg(&path)
+            .output()
+            .unwrap();
+        strip_ansi(&String::from_utf8_lossy(&output.stdout))
+            .lines()
+            .filter_map(|l| Some(l.strip_prefix(' ')?.split_once(" | ")?.0.to_string()))
+            .filter(|number| !number.trim().is_empty())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(shown("0"), vec!["03"]);
+    assert_eq!(shown("1"), vec!["02", "03", "04"]);
+    assert_eq!(shown("2"), vec!["01", "02", "03", "04", "05"]);
+}
+
+#[test]
+fn caret_counts_characters_not_bytes() {
+    let path = fixture("caret_utf8", "VACUUM;\nSELECT имя, X'1G' FROM t;\n");
+    let output = sqleibniz().arg(&path).output().unwrap();
+    let stdout = strip_ansi(&String::from_utf8_lossy(&output.stdout));
+    let error = stdout
+        .split("\n\n")
+        .find(|block| block.contains("error[InvalidBlob]"))
+        .expect("missing InvalidBlob error");
+    let caret = error.lines().find(|l| l.ends_with("occurs here.")).unwrap();
+    assert_eq!(
+        caret,
+        format!("    | {}~ error occurs here.", " ".repeat(15))
+    );
+    assert!(error.contains(":2:16"));
+}
+
+#[test]
+fn opt_in_rules_are_disabled_by_default() {
+    let path = fixture("implicit_insert_columns", "INSERT INTO t VALUES (1);\n");
+    let output = sqleibniz().arg("--count-only").arg(&path).output().unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "0\n");
+
+    let output = sqleibniz()
+        .arg("--count-only")
+        .args(["-E", "implicit-insert-columns"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1\n");
+}
+
+#[test]
+fn semantic_errors_are_filtered_like_syntax_errors() {
+    // syntactically valid, the reserved name is only found by the analysis of the ast
+    let path = fixture("semantic_errors", "ALTER TABLE t RENAME TO sqlite_t;\n");
+    let output = sqleibniz().arg("--count-only").arg(&path).output().unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1\n");
+
+    let output = sqleibniz()
+        .arg("--count-only")
+        .args(["-D", "reserved-table-name"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "0\n");
+}
+
+#[test]
+fn line_length_flags_over_long_lines() {
+    let long = format!("SELECT {};\n", vec!["1"; 60].join(", "));
+    let path = fixture("line_length", &format!("VACUUM;\n{long}VACUUM;\n"));
+    let output = sqleibniz()
+        .arg("--count-only")
+        .args(["-E", "line-length"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1\n");
+}
+
+#[test]
+fn format_json_prints_errors_and_summary_as_json_lines() {
+    let path = fixture("format_json", "VACUUM;\nVACUUM 5;\n");
+    let output = sqleibniz()
+        .args(["--format", "json"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines = stdout
+        .lines()
+        .map(|l| serde_json::from_str::<serde_json::Value>(l).expect("line is not json"))
+        .collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["file"], path.to_string_lossy().as_ref());
+    assert_eq!(lines[0]["line"], 2);
+    assert_eq!(lines[0]["rule"], "Syntax");
+    assert_eq!(lines[1]["verified"], 0);
+    assert_eq!(lines[1]["failed"], 1);
+    assert_eq!(lines[1]["files"][0]["errors"], 1);
+}
+
+#[test]
+fn stdin_is_analysed_under_its_filename() {
+    let mut child = sqleibniz()
+        .args([
+            "--format",
+            "json",
+            "--stdin",
+            "--stdin-filename",
+            "query.sql",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"VACUUM;\nVACUUM 5;\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines = stdout
+        .lines()
+        .map(|l| serde_json::from_str::<serde_json::Value>(l).expect("line is not json"))
+        .collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["file"], "query.sql");
+    assert_eq!(lines[0]["line"], 2);
+    assert_eq!(lines[1]["files"][0]["file"], "query.sql");
+}
+
+#[test]
+fn stdin_filename_defaults_to_stdin() {
+    let mut child = sqleibniz()
+        .arg("--stdin")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"VACUUM;\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("<stdin>"));
+}
+
+#[test]
+fn gzipped_files_are_decompressed() {
+    // gzip -9 of "VACUUM 5;\n"
+    let gzipped = [
+        0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0x0b, 0x73, 0x74, 0x0e, 0x0d,
+        0xf5, 0x55, 0x30, 0xb5, 0xe6, 0x02, 0x00, 0xfc, 0x01, 0x43, 0xea, 0x0a, 0x00, 0x00, 0x00,
+    ];
+    let path = std::env::temp_dir().join("sqleibniz_cli_gzipped.sql.gz");
+    fs::write(&path, gzipped).expect("failed to write fixture");
+    let output = sqleibniz()
+        .args(["--format", "json"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let error: serde_json::Value = serde_json::from_str(stdout.lines().next().unwrap()).unwrap();
+    assert_eq!(error["rule"], "Syntax");
+    assert_eq!(error["start"], 7);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn corrupt_gzipped_files_are_reported() {
+    let path = std::env::temp_dir().join("sqleibniz_cli_corrupt.sql.gz");
+    fs::write(&path, "VACUUM;").expect("failed to write fixture");
+    let output = sqleibniz().arg(&path).output().unwrap();
+    assert!(String::from_utf8_lossy(&output.stdout).contains("failed to decompress file"));
+    assert!(!output.status.success());
+}
+
+#[test]
+fn format_sarif_prints_a_sarif_log() {
+    let good = fixture("format_sarif_good", "VACUUM;\n");
+    let bad = fixture("format_sarif_bad", "VACUUM;\nVACUUM 5;\n");
+    let output = sqleibniz()
+        .args(["--format", "sarif"])
+        .arg(&good)
+        .arg(&bad)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let log: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("output is not json");
+    assert_eq!(log["version"], "2.1.0");
+    let results = log["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["ruleId"], "Syntax");
+    let location = &results[0]["locations"][0]["physicalLocation"];
+    assert_eq!(
+        location["artifactLocation"]["uri"],
+        bad.to_string_lossy().as_ref()
+    );
+    assert_eq!(location["region"]["startLine"], 2);
+    assert_eq!(location["region"]["startColumn"], 8);
+}
+
+#[test]
+fn min_severity_ignores_less_severe_errors() {
+    // PRAGMA foreign_keys = 2 is a Quirk, thus a warning
+    let path = fixture("min_severity", "PRAGMA foreign_keys = 2;\nVACUUM 5;\n");
+    let output = sqleibniz()
+        .args(["--format", "json", "--min-severity", "error"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines = stdout
+        .lines()
+        .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["rule"], "Syntax");
+    assert_eq!(lines[0]["severity"], "error");
+    assert_eq!(lines[1]["files"][0]["ignored_errors"], 1);
+}
+
+#[test]
+fn max_errors_truncates_reported_errors() {
+    let first = fixture("max_errors_first", "VACUUM 5;\nVACUUM 6;\n");
+    let second = fixture("max_errors_second", "VACUUM 7;\n");
+    let output = sqleibniz()
+        .args(["--format", "json", "--max-errors", "1"])
+        .arg(&first)
+        .arg(&second)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines = stdout
+        .lines()
+        .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["line"], 1);
+    let summary = &lines[1];
+    assert_eq!(summary["truncated"], true);
+    assert_eq!(summary["files"][0]["errors"], 2);
+    assert_eq!(summary["files"][0]["truncated"], true);
+    assert_eq!(summary["files"][1]["truncated"], true);
+
+    // --count-only is silent, thus all errors are counted
+    let output = sqleibniz()
+        .args(["--count-only", "--max-errors", "1"])
+        .arg(&first)
+        .arg(&second)
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "3\n");
+}
+
+#[test]
+fn fail_fast_stops_after_first_failing_file() {
+    let good = fixture("fail_fast_good", "VACUUM;\n");
+    let bad = fixture("fail_fast_bad", "VACUUM 5;\n");
+    let later = fixture("fail_fast_later", "VACUUM 6;\n");
+    let missing = std::env::temp_dir().join("sqleibniz_cli_fail_fast_missing.sql");
+    let output = sqleibniz()
+        .args(["--format", "json", "--fail-fast"])
+        .arg(&good)
+        .arg(&bad)
+        .arg(&later)
+        .arg(&missing)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // reading the missing file would have failed
+    assert!(!stdout.contains("failed to read file"), "{stdout}");
+    let lines = stdout
+        .lines()
+        .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["file"], bad.to_string_lossy().as_ref());
+    let files = lines[1]["files"].as_array().unwrap();
+    assert_eq!(files.len(), 2);
+    assert_eq!(lines[1]["failed"], 1);
+}
+
+/// creates an empty directory in the systems temporary directory for tests needing a directory
+/// tree, such as config discovery
+fn fixture_dir(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("sqleibniz_cli_{}", name));
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).expect("failed to create fixture directory");
+    path
+}
+
+const DISABLE_SYNTAX: &str = "leibniz = { disabled_rules = { \"Syntax\" } }";
+
+#[test]
+fn config_is_discovered_per_file() {
+    let root = fixture_dir("config_discovery");
+    fs::create_dir_all(root.join("nested/deeper")).unwrap();
+    fs::write(root.join("leibniz.lua"), DISABLE_SYNTAX).unwrap();
+    fs::write(root.join("nested/leibniz.lua"), "leibniz = {}").unwrap();
+    let top = root.join("top.sql");
+    let deeper = root.join("nested/deeper/deeper.sql");
+    fs::write(&top, "VACUUM 5;\n").unwrap();
+    fs::write(&deeper, "VACUUM 5;\n").unwrap();
+
+    let count = |path: &PathBuf| {
+        let output = Command::new(env!("CARGO_BIN_EXE_sqleibniz"))
+            .arg("--count-only")
+            .arg(path)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+    // top.sql uses the root config, deeper.sql the nearer config in nested
+    assert_eq!(count(&top), "0\n");
+    assert_eq!(count(&deeper), "1\n");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_sqleibniz"))
+        .arg("--count-only")
+        .arg(&top)
+        .arg(&deeper)
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1\n");
+}
+
+#[test]
+fn config_discovery_stops_at_root_marker() {
+    let root = fixture_dir("config_discovery_marker");
+    fs::create_dir_all(root.join("project")).unwrap();
+    fs::write(root.join("leibniz.lua"), DISABLE_SYNTAX).unwrap();
+    fs::write(root.join("project/.sqleibniz-root"), "").unwrap();
+    let path = root.join("project/bad.sql");
+    fs::write(&path, "VACUUM 5;\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_sqleibniz"))
+        .arg("--count-only")
+        .arg(&path)
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1\n");
+}
+
+#[test]
+fn explicit_config_overrides_discovery() {
+    let root = fixture_dir("config_discovery_explicit");
+    fs::write(root.join("leibniz.lua"), "leibniz = {}").unwrap();
+    fs::write(root.join("explicit.lua"), DISABLE_SYNTAX).unwrap();
+    let path = root.join("bad.sql");
+    fs::write(&path, "VACUUM 5;\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_sqleibniz"))
+        .arg("--count-only")
+        .arg("--config")
+        .arg(root.join("explicit.lua"))
+        .arg(&path)
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "0\n");
+}
+
+#[test]
+fn ignore_file_excludes_files() {
+    let root = fixture_dir("ignore_file");
+    fs::create_dir_all(root.join("vendor/lib")).unwrap();
+    fs::create_dir_all(root.join("db")).unwrap();
+    fs::write(
+        root.join(".sqleibnizignore"),
+        "vendor/**\n**/*.gen.sql\n!db/keep.gen.sql\n",
+    )
+    .unwrap();
+    let files = [
+        "vendor/lib/a.sql",
+        "db/a.gen.sql",
+        "db/keep.gen.sql",
+        "db/a.sql",
+    ];
+    for file in files {
+        fs::write(root.join(file), "VACUUM 5;\n").unwrap();
+    }
+
+    // keep.gen.sql and a.sql are analysed, the ignore file is found by walking up from db
+    let output = sqleibniz()
+        .arg("--count-only")
+        .args(files.map(|file| root.join(file)))
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "2\n");
+
+    // an explicit ignore file replaces the discovered one
+    fs::write(root.join("other_ignore"), "db/\n").unwrap();
+    let output = sqleibniz()
+        .arg("--count-only")
+        .arg("--ignore-file")
+        .arg(root.join("other_ignore"))
+        .args(files.map(|file| root.join(file)))
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1\n");
+}